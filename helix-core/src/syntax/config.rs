@@ -59,6 +59,11 @@ pub struct LanguageConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub formatter: Option<FormatterConfiguration>,
 
+    /// Kinds of code actions (for example `source.organizeImports`) that are applied to the whole
+    /// document before it is formatted and written.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_actions_on_save: Vec<String>,
+
     /// If set, overrides `editor.path-completion`.
     pub path_completion: Option<bool>,
     /// If set, overrides `editor.word-completion`.
@@ -274,6 +279,33 @@ pub enum LanguageServerFeature {
     RenameSymbol,
     InlayHints,
     DocumentColors,
+    InlineValues,
+}
+
+impl LanguageServerFeature {
+    /// Every feature in the order of declaration.
+    pub const ALL: [Self; 20] = [
+        Self::Format,
+        Self::GotoDeclaration,
+        Self::GotoDefinition,
+        Self::GotoTypeDefinition,
+        Self::GotoReference,
+        Self::GotoImplementation,
+        Self::SignatureHelp,
+        Self::Hover,
+        Self::DocumentHighlight,
+        Self::Completion,
+        Self::CodeAction,
+        Self::WorkspaceCommand,
+        Self::DocumentSymbols,
+        Self::WorkspaceSymbols,
+        Self::Diagnostics,
+        Self::PullDiagnostics,
+        Self::RenameSymbol,
+        Self::InlayHints,
+        Self::DocumentColors,
+        Self::InlineValues,
+    ];
 }
 
 impl Display for LanguageServerFeature {
@@ -299,6 +331,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             RenameSymbol => "rename-symbol",
             InlayHints => "inlay-hints",
             DocumentColors => "document-colors",
+            InlineValues => "inline-values",
         };
         write!(f, "{feature}",)
     }
@@ -416,6 +449,41 @@ pub struct LanguageServerConfiguration {
         deserialize_with = "deserialize_required_root_patterns"
     )]
     pub required_root_patterns: Option<GlobSet>,
+    #[serde(default)]
+    pub trace: LanguageServerTrace,
+}
+
+/// The verbosity of `$/logTrace` notifications requested from a language server.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LanguageServerTrace {
+    #[default]
+    Off,
+    Messages,
+    Verbose,
+}
+
+impl FromStr for LanguageServerTrace {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "messages" => Ok(Self::Messages),
+            "verbose" => Ok(Self::Verbose),
+            _ => anyhow::bail!("Invalid trace level '{s}', expected off, messages or verbose"),
+        }
+    }
+}
+
+impl Display for LanguageServerTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Messages => "messages",
+            Self::Verbose => "verbose",
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -630,3 +698,33 @@ pub fn deserialize_auto_pairs<'de, D>(deserializer: D) -> Result<Option<AutoPair
 fn default_timeout() -> u64 {
     20
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn language_server_feature_names() {
+        // every feature can be listed by its name in the `only-features` of languages.toml
+        let names: Vec<_> = LanguageServerFeature::ALL
+            .iter()
+            .map(|feature| format!("'{feature}'"))
+            .collect();
+        let config = format!(
+            "language-servers = [{{ name = 'server', only-features = [{}] }}]",
+            names.join(", ")
+        );
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_lang_features")]
+            language_servers: Vec<LanguageServerFeatures>,
+        }
+        let config: Config = toml::from_str(&config).unwrap();
+        assert_eq!(
+            config.language_servers[0].only,
+            LanguageServerFeature::ALL.into_iter().collect()
+        );
+    }
+}
@@ -19,7 +19,7 @@
     sync::atomic::{AtomicU64, Ordering},
 };
 use tokio::{
-    io::{AsyncBufRead, AsyncWrite, BufReader, BufWriter},
+    io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWrite, BufReader, BufWriter},
     net::TcpStream,
     process::{Child, Command},
     sync::mpsc::{channel, unbounded_channel, UnboundedReceiver, UnboundedSender},
@@ -46,6 +46,10 @@ pub struct Client {
     pub quirks: DebuggerQuirks,
     /// The config which was used to start this debugger.
     pub config: Option<DebugAdapterConfig>,
+    /// The session which started this one with a `startDebugging` request.
+    pub parent: Option<DebugAdapterId>,
+    /// The debuggee, if it was started by the editor for a `runInTerminal` request.
+    pub(crate) debuggee: Option<Child>,
 }
 
 impl Client {
@@ -93,6 +97,8 @@ pub fn streams(
             active_frame: None,
             quirks: DebuggerQuirks::default(),
             config: None,
+            parent: None,
+            debuggee: None,
         };
 
         tokio::spawn(Self::recv(id, server_rx, client_tx));
@@ -106,7 +112,14 @@ pub async fn tcp(
     ) -> Result<(Self, UnboundedReceiver<(DebugAdapterId, Payload)>)> {
         let stream = TcpStream::connect(addr).await?;
         let (rx, tx) = stream.into_split();
-        Self::streams(Box::new(BufReader::new(rx)), Box::new(tx), None, id, None)
+        let mut result = Self::streams(Box::new(BufReader::new(rx)), Box::new(tx), None, id, None);
+
+        // Child sessions connect to the same address
+        if let Ok((client, _)) = &mut result {
+            client.socket = Some(addr);
+        }
+
+        result
     }
 
     pub fn stdio(
@@ -142,6 +155,89 @@ pub fn stdio(
         )
     }
 
+    /// Starts the debuggee of a `runInTerminal` request as a child process. The returned stream
+    /// forwards its stdout and stderr as `output` events of this client.
+    pub(crate) fn run_debuggee(
+        &mut self,
+        arguments: &requests::RunInTerminalArguments,
+    ) -> Result<UnboundedReceiver<(DebugAdapterId, Payload)>> {
+        let Some((program, args)) = arguments.args.split_first() else {
+            return Result::Err(Error::Other(anyhow!("Command not provided")));
+        };
+
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(&arguments.cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // make sure the debuggee does not outlive the session
+            .kill_on_drop(true);
+        for (key, value) in arguments.env.iter().flatten() {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        let mut process = command.spawn()?;
+
+        let (tx, rx) = unbounded_channel();
+        if let Some(stdout) = process.stdout.take() {
+            tokio::spawn(Self::forward_output(self.id, stdout, "stdout", tx.clone()));
+        }
+        if let Some(stderr) = process.stderr.take() {
+            tokio::spawn(Self::forward_output(self.id, stderr, "stderr", tx));
+        }
+
+        self.debuggee = Some(process);
+        Ok(rx)
+    }
+
+    async fn forward_output(
+        id: DebugAdapterId,
+        mut output: impl AsyncRead + Unpin,
+        category: &'static str,
+        tx: UnboundedSender<(DebugAdapterId, Payload)>,
+    ) {
+        let mut buf = [0; 4096];
+        // Bytes of a character which was split between two reads.
+        let mut pending = Vec::new();
+        loop {
+            let n = match output.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            pending.extend_from_slice(&buf[..n]);
+            let valid = match std::str::from_utf8(&pending) {
+                Ok(_) => pending.len(),
+                // An incomplete character at the end is kept for the next read.
+                Err(err) if err.error_len().is_none() => err.valid_up_to(),
+                Err(_) => pending.len(),
+            };
+            let text = String::from_utf8_lossy(&pending[..valid]).into_owned();
+            pending.drain(..valid);
+
+            let body = events::OutputBody {
+                output: text,
+                category: Some(category.to_owned()),
+                group: None,
+                line: None,
+                column: None,
+                variables_reference: None,
+                source: None,
+                data: None,
+            };
+            let event = crate::transport::Event {
+                event: <events::Output as events::Event>::EVENT.to_owned(),
+                body: serde_json::to_value(body).ok(),
+            };
+            if tx.send((id, Payload::Event(event))).is_err() {
+                break;
+            }
+        }
+    }
+
     async fn get_port() -> Option<u16> {
         Some(
             tokio::net::TcpListener::bind(SocketAddr::new(
@@ -346,7 +442,7 @@ pub async fn initialize(&mut self, adapter_id: String) -> Result<()> {
             supports_variable_type: Some(true),
             supports_variable_paging: Some(false),
             supports_run_in_terminal_request: Some(true),
-            supports_memory_references: Some(false),
+            supports_memory_references: Some(true),
             supports_progress_reporting: Some(false),
             supports_invalidated_event: Some(false),
         };
@@ -419,6 +515,55 @@ pub async fn set_breakpoints(
         Ok(response.breakpoints)
     }
 
+    pub async fn set_function_breakpoints(
+        &self,
+        breakpoints: Vec<FunctionBreakpoint>,
+    ) -> Result<Vec<Breakpoint>> {
+        let args = requests::SetFunctionBreakpointsArguments { breakpoints };
+
+        let response = self
+            .request::<requests::SetFunctionBreakpoints>(args)
+            .await?;
+        Ok(response.breakpoints)
+    }
+
+    pub async fn data_breakpoint_info(
+        &self,
+        variables_reference: Option<usize>,
+        name: String,
+        frame_id: Option<usize>,
+    ) -> Result<requests::DataBreakpointInfoResponse> {
+        let args = requests::DataBreakpointInfoArguments {
+            variables_reference,
+            name,
+            frame_id,
+        };
+
+        self.request::<requests::DataBreakpointInfo>(args).await
+    }
+
+    pub async fn set_data_breakpoints(
+        &self,
+        breakpoints: Vec<DataBreakpoint>,
+    ) -> Result<Vec<Breakpoint>> {
+        let args = requests::SetDataBreakpointsArguments { breakpoints };
+
+        let response = self.request::<requests::SetDataBreakpoints>(args).await?;
+        Ok(response.breakpoints)
+    }
+
+    pub async fn set_instruction_breakpoints(
+        &self,
+        breakpoints: Vec<InstructionBreakpoint>,
+    ) -> Result<Vec<Breakpoint>> {
+        let args = requests::SetInstructionBreakpointsArguments { breakpoints };
+
+        let response = self
+            .request::<requests::SetInstructionBreakpoints>(args)
+            .await?;
+        Ok(response.breakpoints)
+    }
+
     pub async fn configuration_done(&self) -> Result<()> {
         self.request::<requests::ConfigurationDone>(()).await
     }
@@ -448,14 +593,20 @@ pub fn threads(&self) -> impl Future<Output = Result<Value>> {
         self.call::<requests::Threads>(())
     }
 
-    pub async fn scopes(&self, frame_id: usize) -> Result<Vec<Scope>> {
+    pub fn scopes(&self, frame_id: usize) -> impl Future<Output = Result<Vec<Scope>>> {
         let args = requests::ScopesArguments { frame_id };
 
-        let response = self.request::<requests::Scopes>(args).await?;
-        Ok(response.scopes)
+        let call = self.call::<requests::Scopes>(args);
+        async move {
+            let response: requests::ScopesResponse = serde_json::from_value(call.await?)?;
+            Ok(response.scopes)
+        }
     }
 
-    pub async fn variables(&self, variables_reference: usize) -> Result<Vec<Variable>> {
+    pub fn variables(
+        &self,
+        variables_reference: usize,
+    ) -> impl Future<Output = Result<Vec<Variable>>> {
         let args = requests::VariablesArguments {
             variables_reference,
             filter: None,
@@ -464,8 +615,11 @@ pub async fn variables(&self, variables_reference: usize) -> Result<Vec<Variable
             format: None,
         };
 
-        let response = self.request::<requests::Variables>(args).await?;
-        Ok(response.variables)
+        let call = self.call::<requests::Variables>(args);
+        async move {
+            let response: requests::VariablesResponse = serde_json::from_value(call.await?)?;
+            Ok(response.variables)
+        }
     }
 
     pub fn step_in(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
@@ -496,25 +650,117 @@ pub fn next(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
         self.call::<requests::Next>(args)
     }
 
+    /// Steps into the next instruction rather than the next statement.
+    pub fn step_in_instruction(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
+        let args = requests::StepInArguments {
+            thread_id,
+            target_id: None,
+            granularity: Some("instruction".to_owned()),
+        };
+
+        self.call::<requests::StepIn>(args)
+    }
+
+    /// Steps over the next instruction rather than the next statement.
+    pub fn next_instruction(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
+        let args = requests::NextArguments {
+            thread_id,
+            granularity: Some("instruction".to_owned()),
+        };
+
+        self.call::<requests::Next>(args)
+    }
+
     pub fn pause(&self, thread_id: ThreadId) -> impl Future<Output = Result<Value>> {
         let args = requests::PauseArguments { thread_id };
 
         self.call::<requests::Pause>(args)
     }
 
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// after the instruction at `memory_reference`. The offset may be negative.
+    pub fn disassemble(
+        &self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: usize,
+    ) -> impl Future<Output = Result<Vec<DisassembledInstruction>>> {
+        let args = requests::DisassembleArguments {
+            memory_reference,
+            offset: None,
+            instruction_offset: Some(instruction_offset),
+            instruction_count,
+            resolve_symbols: Some(true),
+        };
+
+        let call = self.call::<requests::Disassemble>(args);
+        async move {
+            let response: requests::DisassembleResponse = serde_json::from_value(call.await?)?;
+            Ok(response.instructions)
+        }
+    }
+
+    pub fn read_memory(
+        &self,
+        memory_reference: String,
+        offset: i64,
+        count: usize,
+    ) -> impl Future<Output = Result<requests::ReadMemoryResponse>> {
+        let args = requests::ReadMemoryArguments {
+            memory_reference,
+            offset: Some(offset),
+            count,
+        };
+
+        let call = self.call::<requests::ReadMemory>(args);
+        async move { Ok(serde_json::from_value(call.await?)?) }
+    }
+
     pub async fn eval(
         &self,
         expression: String,
         frame_id: Option<usize>,
     ) -> Result<requests::EvaluateResponse> {
+        self.eval_in_context(expression, frame_id, None).await
+    }
+
+    /// Evaluates `expression`, hinting the adapter about where the expression comes from with
+    /// `context`, e.g. `"repl"` or `"watch"`.
+    pub fn eval_in_context(
+        &self,
+        expression: String,
+        frame_id: Option<usize>,
+        context: Option<&str>,
+    ) -> impl Future<Output = Result<requests::EvaluateResponse>> {
         let args = requests::EvaluateArguments {
             expression,
             frame_id,
-            context: None,
+            context: context.map(ToOwned::to_owned),
             format: None,
         };
 
-        self.request::<requests::Evaluate>(args).await
+        let call = self.call::<requests::Evaluate>(args);
+        async move { Ok(serde_json::from_value(call.await?)?) }
+    }
+
+    pub fn completions(
+        &self,
+        text: String,
+        column: usize,
+        frame_id: Option<usize>,
+    ) -> impl Future<Output = Result<Vec<CompletionItem>>> {
+        let args = requests::CompletionsArguments {
+            frame_id,
+            text,
+            column,
+            line: None,
+        };
+
+        let call = self.call::<requests::Completions>(args);
+        async move {
+            let response: requests::CompletionsResponse = serde_json::from_value(call.await?)?;
+            Ok(response.targets)
+        }
     }
 
     pub fn set_exception_breakpoints(
@@ -526,6 +772,13 @@ pub fn set_exception_breakpoints(
         self.call::<requests::SetExceptionBreakpoints>(args)
     }
 
+    /// The stack frames of the current thread, starting with the top frame.
+    pub fn current_stack(&self) -> &[StackFrame] {
+        self.thread_id
+            .and_then(|thread_id| self.stack_frames.get(&thread_id))
+            .map_or(&[], Vec::as_slice)
+    }
+
     pub fn current_stack_frame(&self) -> Option<&StackFrame> {
         self.stack_frames
             .get(&self.thread_id?)?
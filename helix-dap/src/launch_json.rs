@@ -0,0 +1,301 @@
+//! Launch configurations from a `.vscode/launch.json` file.
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::path::Path;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LaunchJson {
+    pub configurations: Vec<LaunchConfiguration>,
+    pub inputs: Vec<LaunchInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LaunchConfiguration {
+    pub name: String,
+    /// Type of the debugger, matched against the name of the configured debug adapters.
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub request: String,
+    /// The remaining properties, passed to the debug adapter as arguments of the request.
+    #[serde(flatten)]
+    pub arguments: Map<String, Value>,
+}
+
+/// A value the user is asked for when a configuration refers to it with `${input:<id>}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LaunchInput {
+    #[serde(rename_all = "camelCase")]
+    PromptString {
+        id: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PickString {
+        id: String,
+        #[serde(default)]
+        description: Option<String>,
+        options: Vec<PickStringOption>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    /// Inputs computed by editor commands, which are not supported.
+    #[serde(rename_all = "camelCase")]
+    Command { id: String, command: String },
+}
+
+impl LaunchInput {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::PromptString { id, .. }
+            | Self::PickString { id, .. }
+            | Self::Command { id, .. } => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PickStringOption {
+    Value(String),
+    Labeled { label: String, value: String },
+}
+
+impl PickStringOption {
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Value(value) | Self::Labeled { value, .. } => value,
+        }
+    }
+}
+
+impl LaunchJson {
+    /// Reads `.vscode/launch.json` in `workspace`. Returns `None` if there is no such file.
+    pub fn load(workspace: &Path) -> anyhow::Result<Option<Self>> {
+        let path = workspace.join(".vscode").join("launch.json");
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map(Some),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Parses the contents of a `launch.json` file, which may contain comments and trailing
+    /// commas.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(&strip_jsonc(text))?)
+    }
+
+    pub fn input(&self, id: &str) -> Option<&LaunchInput> {
+        self.inputs.iter().find(|input| input.id() == id)
+    }
+}
+
+/// Identifiers of the `${input:<id>}` variables in the string values of `value`, in order of
+/// appearance and without duplicates.
+pub fn input_variables(value: &Value) -> Vec<String> {
+    fn collect(value: &Value, ids: &mut Vec<String>) {
+        match value {
+            Value::String(string) => {
+                let mut rest = string.as_str();
+                while let Some(start) = rest.find("${input:") {
+                    rest = &rest[start + "${input:".len()..];
+                    let Some(end) = rest.find('}') else {
+                        break;
+                    };
+                    let id = &rest[..end];
+                    if !ids.iter().any(|other| other == id) {
+                        ids.push(id.to_string());
+                    }
+                    rest = &rest[end..];
+                }
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect(value, ids)),
+            Value::Object(map) => map.values().for_each(|value| collect(value, ids)),
+            _ => (),
+        }
+    }
+
+    let mut ids = Vec::new();
+    collect(value, &mut ids);
+    ids
+}
+
+/// Replaces the `${...}` variables in the string values of `value` using `expand`, which
+/// receives the name of the variable, for example `workspaceFolder` or `input:port`.
+pub fn expand_variables(
+    value: &mut Value,
+    expand: &mut impl FnMut(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    match value {
+        Value::String(string) => {
+            let mut expanded = String::with_capacity(string.len());
+            let mut rest = string.as_str();
+            while let Some(start) = rest.find("${") {
+                expanded.push_str(&rest[..start]);
+                let Some(end) = rest[start..].find('}') else {
+                    anyhow::bail!("unterminated variable in '{string}'");
+                };
+                expanded.push_str(&expand(&rest[start + 2..start + end])?);
+                rest = &rest[start + end + 1..];
+            }
+            expanded.push_str(rest);
+            *string = expanded;
+        }
+        Value::Array(values) => {
+            for value in values {
+                expand_variables(value, expand)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                expand_variables(value, expand)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Removes comments and trailing commas from JSON with comments (JSONC).
+fn strip_jsonc(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    // Index in `output` of a comma that is dropped if the next token closes an object or array.
+    let mut pending_comma = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                pending_comma = None;
+                output.push(c);
+                while let Some(c) = chars.next() {
+                    output.push(c);
+                    match c {
+                        '\\' => output.extend(chars.next()),
+                        '"' => break,
+                        _ => (),
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+                output.push(' ');
+            }
+            ',' => {
+                pending_comma = Some(output.len());
+                output.push(c);
+            }
+            '}' | ']' => {
+                if let Some(idx) = pending_comma.take() {
+                    output.replace_range(idx..idx + 1, " ");
+                }
+                output.push(c);
+            }
+            c if c.is_whitespace() => output.push(c),
+            c => {
+                pending_comma = None;
+                output.push(c);
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_jsonc() {
+        let text = r#"{
+            // Use IntelliSense to learn about possible attributes.
+            "version": "0.2.0",
+            "configurations": [
+                {
+                    "name": "Debug // tests",
+                    "type": "lldb-dap", /* inline */
+                    "request": "launch",
+                    "program": "${workspaceFolder}/target/debug/app",
+                    "args": ["--port", "${input:port}",],
+                },
+            ],
+            "inputs": [
+                { "id": "port", "type": "promptString", "default": "8080" },
+                { "id": "mode", "type": "pickString", "options": ["a", { "label": "B", "value": "b" }] },
+            ]
+        }"#;
+
+        let launch = LaunchJson::parse(text).unwrap();
+        assert_eq!(launch.configurations.len(), 1);
+        let configuration = &launch.configurations[0];
+        assert_eq!(configuration.name, "Debug // tests");
+        assert_eq!(configuration.ty, "lldb-dap");
+        assert_eq!(configuration.request, "launch");
+        assert_eq!(
+            configuration.arguments["args"],
+            json!(["--port", "${input:port}"])
+        );
+        assert!(matches!(
+            launch.input("port"),
+            Some(LaunchInput::PromptString { default: Some(default), .. }) if default == "8080"
+        ));
+        let Some(LaunchInput::PickString { options, .. }) = launch.input("mode") else {
+            panic!("expected a pickString input");
+        };
+        let values: Vec<_> = options.iter().map(PickStringOption::value).collect();
+        assert_eq!(values, ["a", "b"]);
+    }
+
+    #[test]
+    fn expand() {
+        let mut value = json!({
+            "program": "${workspaceFolder}/app",
+            "args": ["${input:port}", "--host=${input:host}", "${input:port}"],
+            "stopOnEntry": true,
+        });
+        assert_eq!(input_variables(&value), ["port", "host"]);
+
+        expand_variables(&mut value, &mut |variable| {
+            Ok(match variable {
+                "workspaceFolder" => "/work".to_string(),
+                "input:port" => "8080".to_string(),
+                "input:host" => "localhost".to_string(),
+                _ => anyhow::bail!("unknown variable {variable}"),
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "program": "/work/app",
+                "args": ["8080", "--host=localhost", "8080"],
+                "stopOnEntry": true,
+            })
+        );
+
+        let mut value = json!("${unknown}");
+        assert!(expand_variables(&mut value, &mut |_| anyhow::bail!("unknown")).is_err());
+    }
+}
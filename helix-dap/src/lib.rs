@@ -1,4 +1,5 @@
 mod client;
+pub mod launch_json;
 pub mod registry;
 mod transport;
 mod types;
@@ -1,4 +1,5 @@
-use crate::{Client, Payload, Result, StackFrame};
+use crate::{requests::RunInTerminalArguments, Client, Error, Payload, Result, StackFrame};
+use anyhow::anyhow;
 use futures_executor::block_on;
 use futures_util::stream::SelectAll;
 use helix_core::syntax::config::DebugAdapterConfig;
@@ -58,8 +59,84 @@ pub fn start_client(
         })
     }
 
+    /// Starts a child session of the client `parent` for a `startDebugging` request.
+    ///
+    /// The child session connects to the debug adapter server of its parent, so the parent must
+    /// use the TCP transport. Adapters using stdio expect child sessions over their single
+    /// connection which isn't supported.
+    pub fn start_child_client(&mut self, parent: DebugAdapterId) -> Result<DebugAdapterId> {
+        let client = self
+            .inner
+            .get(parent)
+            .ok_or_else(|| Error::Other(anyhow!("Debugger not found")))?;
+        let Some(socket) = client.socket else {
+            return Err(Error::Other(anyhow!(
+                "Child debugging sessions are only supported for debug adapters using the TCP transport"
+            )));
+        };
+        let config = client
+            .config
+            .clone()
+            .ok_or_else(|| Error::Other(anyhow!("No configuration found for the debugger")))?;
+
+        let id = self.start_client(Some(socket), &config)?;
+        self.inner[id].parent = Some(parent);
+        Ok(id)
+    }
+
+    /// Starts the debuggee of a `runInTerminal` request of the client `id` as a child process,
+    /// returning its process ID. Its output is received as `output` events of the client.
+    pub fn run_debuggee(
+        &mut self,
+        id: DebugAdapterId,
+        arguments: &RunInTerminalArguments,
+    ) -> Result<Option<u32>> {
+        let client = self
+            .inner
+            .get_mut(id)
+            .ok_or_else(|| Error::Other(anyhow!("Debugger not found")))?;
+        let receiver = client.run_debuggee(arguments)?;
+        self.incoming.push(UnboundedReceiverStream::new(receiver));
+        Ok(client.debuggee.as_ref().and_then(|process| process.id()))
+    }
+
+    /// Removes the client together with the child sessions it started. If the active client is
+    /// removed, the parent of the client becomes active.
     pub fn remove_client(&mut self, id: DebugAdapterId) {
-        self.inner.remove(id);
+        let Some(client) = self.inner.remove(id) else {
+            return;
+        };
+        let mut removed = vec![id];
+        while let Some(parent) = removed.pop() {
+            let children: Vec<_> = self.children(parent).collect();
+            for child in children {
+                self.inner.remove(child);
+                removed.push(child);
+            }
+        }
+        if self
+            .current_client_id
+            .is_some_and(|current| !self.inner.contains_key(current))
+        {
+            self.current_client_id = client.parent.filter(|id| self.inner.contains_key(*id));
+        }
+    }
+
+    /// The clients of all running sessions.
+    pub fn iter(&self) -> impl Iterator<Item = &Client> {
+        self.inner.values()
+    }
+
+    /// The child sessions started by the client `id`.
+    pub fn children(&self, id: DebugAdapterId) -> impl Iterator<Item = DebugAdapterId> + '_ {
+        self.inner
+            .iter()
+            .filter(move |(_, client)| client.parent == Some(id))
+            .map(|(id, _)| id)
+    }
+
+    pub fn active_client_id(&self) -> Option<DebugAdapterId> {
+        self.current_client_id
     }
 
     pub fn get_client(&self, id: DebugAdapterId) -> Option<&Client> {
@@ -112,3 +189,171 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", self.0)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{events, transport::Event};
+    use futures_util::StreamExt;
+    use serde_json::{json, Value};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    /// Adds a client connected to an in-memory stream, like a debug adapter using stdio.
+    fn insert_client(registry: &mut Registry, parent: Option<DebugAdapterId>) -> DebugAdapterId {
+        registry.inner.insert_with_key(|id| {
+            let (stream, _) = tokio::io::duplex(1024);
+            let (rx, tx) = tokio::io::split(stream);
+            let (mut client, _) =
+                Client::streams(Box::new(BufReader::new(rx)), Box::new(tx), None, id, None)
+                    .unwrap();
+            client.parent = parent;
+            client
+        })
+    }
+
+    fn config() -> DebugAdapterConfig {
+        DebugAdapterConfig {
+            name: "test".to_string(),
+            transport: "tcp".to_string(),
+            command: String::new(),
+            args: Vec::new(),
+            port_arg: None,
+            templates: Vec::new(),
+            quirks: Default::default(),
+        }
+    }
+
+    /// Accepts a single connection and answers its `initialize` request.
+    async fn serve_initialize(listener: TcpListener) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (rx, mut tx) = stream.into_split();
+        let mut rx = BufReader::new(rx);
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            rx.read_line(&mut header).await.unwrap();
+            match header.trim().strip_prefix("Content-Length: ") {
+                Some(length) => content_length = length.parse().unwrap(),
+                None if header.trim().is_empty() => break,
+                None => (),
+            }
+        }
+        let mut content = vec![0; content_length];
+        rx.read_exact(&mut content).await.unwrap();
+        let request: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(request["command"], "initialize");
+
+        let response = json!({
+            "type": "response",
+            "seq": 1,
+            "request_seq": request["seq"],
+            "success": true,
+            "command": "initialize",
+            "body": {},
+        })
+        .to_string();
+        tx.write_all(format!("Content-Length: {}\r\n\r\n{response}", response.len()).as_bytes())
+            .await
+            .unwrap();
+        // keep the connection open until the client is dropped
+        rx.read_to_end(&mut Vec::new()).await.ok();
+    }
+
+    #[tokio::test]
+    async fn remove_client_activates_parent() {
+        let mut registry = Registry::new();
+        let parent = insert_client(&mut registry, None);
+        let child = insert_client(&mut registry, Some(parent));
+        let grandchild = insert_client(&mut registry, Some(child));
+        let sibling = insert_client(&mut registry, Some(parent));
+
+        // removing a session removes the sessions it started and activates its parent
+        registry.set_active_client(grandchild);
+        registry.remove_client(child);
+        assert!(registry.get_client(grandchild).is_none());
+        assert_eq!(registry.active_client_id(), Some(parent));
+        assert_eq!(registry.children(parent).collect::<Vec<_>>(), vec![sibling]);
+
+        // the active session is kept if it is not removed
+        registry.remove_client(sibling);
+        assert_eq!(registry.active_client_id(), Some(parent));
+
+        registry.remove_client(parent);
+        assert_eq!(registry.active_client_id(), None);
+        assert!(registry.iter().next().is_none());
+    }
+
+    #[tokio::test]
+    async fn start_child_client_requires_tcp() {
+        let mut registry = Registry::new();
+        let parent = insert_client(&mut registry, None);
+        registry.get_client_mut(parent).unwrap().config = Some(config());
+
+        assert!(registry.start_child_client(parent).is_err());
+        assert_eq!(registry.iter().count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn start_child_client_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let socket = listener.local_addr().unwrap();
+        tokio::spawn(serve_initialize(listener));
+
+        let mut registry = Registry::new();
+        let parent = insert_client(&mut registry, None);
+        let client = registry.get_client_mut(parent).unwrap();
+        client.socket = Some(socket);
+        client.config = Some(config());
+
+        let child = registry.start_child_client(parent).unwrap();
+        let child_client = registry.get_client(child).unwrap();
+        assert_eq!(child_client.parent, Some(parent));
+        // grandchildren connect to the same server
+        assert_eq!(child_client.socket, Some(socket));
+        assert_eq!(registry.children(parent).collect::<Vec<_>>(), vec![child]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_debuggee_forwards_output() {
+        let mut registry = Registry::new();
+        let id = insert_client(&mut registry, None);
+        let arguments = RunInTerminalArguments {
+            kind: None,
+            title: None,
+            cwd: ".".to_string(),
+            args: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "printf \"$GREETING\"; printf err >&2".to_string(),
+            ],
+            env: Some([("GREETING".to_string(), Some("hello".to_string()))].into()),
+        };
+
+        let process_id = registry.run_debuggee(id, &arguments).unwrap();
+        assert!(process_id.is_some());
+
+        let mut output = Vec::new();
+        while let Some((client, payload)) = registry.incoming.next().await {
+            assert_eq!(client, id);
+            let Payload::Event(Event { event, body }) = payload else {
+                panic!("expected an event");
+            };
+            assert_eq!(event, <events::Output as events::Event>::EVENT);
+            let body: events::OutputBody = serde_json::from_value(body.unwrap()).unwrap();
+            output.push((body.category.unwrap(), body.output));
+        }
+        output.sort();
+        assert_eq!(
+            output,
+            vec![
+                ("stderr".to_string(), "err".to_string()),
+                ("stdout".to_string(), "hello".to_string()),
+            ]
+        );
+    }
+}
@@ -173,6 +173,48 @@ pub struct SourceBreakpoint {
     pub log_message: Option<String>,
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionBreakpoint {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DataBreakpointAccessType {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataBreakpoint {
+    pub data_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_type: Option<DataBreakpointAccessType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionBreakpoint {
+    pub instruction_reference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Breakpoint {
@@ -308,6 +350,28 @@ pub struct Variable {
     pub memory_reference: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_length: Option<usize>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Module {
@@ -332,6 +396,29 @@ pub struct Module {
     pub address_range: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisassembledInstruction {
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_bytes: Option<String>,
+    pub instruction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Source>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_hint: Option<String>,
+}
+
 fn from_number<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>,
@@ -711,6 +798,32 @@ impl Request for Evaluate {
         const COMMAND: &'static str = "evaluate";
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CompletionsArguments {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub frame_id: Option<usize>,
+        pub text: String,
+        pub column: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub line: Option<usize>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CompletionsResponse {
+        pub targets: Vec<CompletionItem>,
+    }
+
+    #[derive(Debug)]
+    pub enum Completions {}
+
+    impl Request for Completions {
+        type Arguments = CompletionsArguments;
+        type Result = CompletionsResponse;
+        const COMMAND: &'static str = "completions";
+    }
+
     #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct SetExceptionBreakpointsArguments {
@@ -735,6 +848,189 @@ impl Request for SetExceptionBreakpoints {
         const COMMAND: &'static str = "setExceptionBreakpoints";
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetFunctionBreakpointsArguments {
+        pub breakpoints: Vec<FunctionBreakpoint>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetFunctionBreakpointsResponse {
+        pub breakpoints: Vec<Breakpoint>,
+    }
+
+    #[derive(Debug)]
+    pub enum SetFunctionBreakpoints {}
+
+    impl Request for SetFunctionBreakpoints {
+        type Arguments = SetFunctionBreakpointsArguments;
+        type Result = SetFunctionBreakpointsResponse;
+        const COMMAND: &'static str = "setFunctionBreakpoints";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DataBreakpointInfoArguments {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub variables_reference: Option<usize>,
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub frame_id: Option<usize>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DataBreakpointInfoResponse {
+        pub data_id: Option<String>,
+        pub description: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub access_types: Option<Vec<DataBreakpointAccessType>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub can_persist: Option<bool>,
+    }
+
+    #[derive(Debug)]
+    pub enum DataBreakpointInfo {}
+
+    impl Request for DataBreakpointInfo {
+        type Arguments = DataBreakpointInfoArguments;
+        type Result = DataBreakpointInfoResponse;
+        const COMMAND: &'static str = "dataBreakpointInfo";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDataBreakpointsArguments {
+        pub breakpoints: Vec<DataBreakpoint>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDataBreakpointsResponse {
+        pub breakpoints: Vec<Breakpoint>,
+    }
+
+    #[derive(Debug)]
+    pub enum SetDataBreakpoints {}
+
+    impl Request for SetDataBreakpoints {
+        type Arguments = SetDataBreakpointsArguments;
+        type Result = SetDataBreakpointsResponse;
+        const COMMAND: &'static str = "setDataBreakpoints";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetInstructionBreakpointsArguments {
+        pub breakpoints: Vec<InstructionBreakpoint>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetInstructionBreakpointsResponse {
+        pub breakpoints: Vec<Breakpoint>,
+    }
+
+    #[derive(Debug)]
+    pub enum SetInstructionBreakpoints {}
+
+    impl Request for SetInstructionBreakpoints {
+        type Arguments = SetInstructionBreakpointsArguments;
+        type Result = SetInstructionBreakpointsResponse;
+        const COMMAND: &'static str = "setInstructionBreakpoints";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DisassembleArguments {
+        pub memory_reference: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub offset: Option<i64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub instruction_offset: Option<i64>,
+        pub instruction_count: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resolve_symbols: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DisassembleResponse {
+        pub instructions: Vec<DisassembledInstruction>,
+    }
+
+    #[derive(Debug)]
+    pub enum Disassemble {}
+
+    impl Request for Disassemble {
+        type Arguments = DisassembleArguments;
+        type Result = DisassembleResponse;
+        const COMMAND: &'static str = "disassemble";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ReadMemoryArguments {
+        pub memory_reference: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub offset: Option<i64>,
+        pub count: usize,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ReadMemoryResponse {
+        pub address: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub unreadable_bytes: Option<usize>,
+        /// The bytes read, encoded in base64.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub data: Option<String>,
+    }
+
+    impl ReadMemoryResponse {
+        /// Decodes the bytes read. Returns `None` if the data is not valid base64.
+        pub fn bytes(&self) -> Option<Vec<u8>> {
+            match &self.data {
+                Some(data) => decode_base64(data),
+                None => Some(Vec::new()),
+            }
+        }
+    }
+
+    fn decode_base64(data: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(data.len() / 4 * 3);
+        let mut buf = 0u32;
+        let mut bits = 0;
+        for c in data.bytes().take_while(|&c| c != b'=') {
+            let value = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => return None,
+            };
+            buf = (buf << 6) | value as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buf >> bits) as u8);
+            }
+        }
+        Some(bytes)
+    }
+
+    #[derive(Debug)]
+    pub enum ReadMemory {}
+
+    impl Request for ReadMemory {
+        type Arguments = ReadMemoryArguments;
+        type Result = ReadMemoryResponse;
+        const COMMAND: &'static str = "readMemory";
+    }
+
     // Reverse Requests
 
     #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -1042,3 +1338,18 @@ fn test_deserialize_module_id_from_string() {
     let module: Module = serde_json::from_str(raw).expect("Error!");
     assert_eq!(module.id, "0");
 }
+
+#[test]
+fn test_read_memory_response_bytes() {
+    let raw = r#"{"address": "0x1000", "data": "aGVsaXg="}"#;
+    let response: requests::ReadMemoryResponse = serde_json::from_str(raw).expect("Error!");
+    assert_eq!(response.bytes().unwrap(), b"helix");
+
+    let raw = r#"{"address": "0x1000", "unreadableBytes": 16}"#;
+    let response: requests::ReadMemoryResponse = serde_json::from_str(raw).expect("Error!");
+    assert_eq!(response.bytes().unwrap(), b"");
+
+    let raw = r#"{"address": "0x1000", "data": "not base64!"}"#;
+    let response: requests::ReadMemoryResponse = serde_json::from_str(raw).expect("Error!");
+    assert_eq!(response.bytes(), None);
+}
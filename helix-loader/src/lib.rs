@@ -144,6 +144,29 @@ pub fn workspace_config_file() -> PathBuf {
     find_workspace().0.join(".helix").join("config.toml")
 }
 
+/// File storing the breakpoints of the current workspace. It is kept in the `.helix` directory
+/// of the workspace if there is one, otherwise in the cache directory.
+pub fn workspace_breakpoints_file() -> PathBuf {
+    let (workspace, _) = find_workspace();
+    let helix_dir = workspace.join(".helix");
+    if helix_dir.is_dir() {
+        return helix_dir.join("breakpoints.json");
+    }
+
+    let name: String = workspace
+        .to_string_lossy()
+        .chars()
+        .map(|c| {
+            if std::path::is_separator(c) || c == ':' {
+                '%'
+            } else {
+                c
+            }
+        })
+        .collect();
+    cache_dir().join("breakpoints").join(format!("{name}.json"))
+}
+
 pub fn lang_config_file() -> PathBuf {
     config_dir().join("languages.toml")
 }
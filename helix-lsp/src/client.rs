@@ -1,7 +1,8 @@
 use crate::{
     file_operations::FileOperationsInterest,
     find_lsp_workspace, jsonrpc,
-    transport::{Payload, Transport},
+    trace::{self, TraceEntry},
+    transport::{Payload, PendingRequest, PendingRequests, Transport},
     Call, Error, LanguageServerId, OffsetEncoding, Result,
 };
 
@@ -10,7 +11,11 @@
     DidChangeWorkspaceFoldersParams, OneOf, PositionEncodingKind, SignatureHelp, Url,
     WorkspaceFolder, WorkspaceFoldersChangeEvent,
 };
-use helix_core::{find_workspace, syntax::config::LanguageServerFeature, ChangeSet, Rope};
+use helix_core::{
+    find_workspace,
+    syntax::config::{LanguageServerFeature, LanguageServerTrace},
+    ChangeSet, Rope,
+};
 use helix_loader::VERSION_AND_GIT_HASH;
 use helix_stdx::path;
 use parking_lot::Mutex;
@@ -30,6 +35,7 @@
     io::{BufReader, BufWriter},
     process::{Child, Command},
     sync::{
+        broadcast,
         mpsc::{channel, UnboundedReceiver, UnboundedSender},
         Notify, OnceCell,
     },
@@ -46,6 +52,27 @@ fn workspace_for_uri(uri: lsp::Url) -> WorkspaceFolder {
     }
 }
 
+fn workspace_folders_capabilities(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::WorkspaceFoldersServerCapabilities> {
+    capabilities
+        .workspace
+        .as_ref()
+        .and_then(|cap| cap.workspace_folders.as_ref())
+        .filter(|cap| cap.supported.unwrap_or(false))
+}
+
+/// `textDocument/inlineValue` responds with an array of inline values while the request type of
+/// `lsp-types` expects a single value.
+enum InlineValueRequest {}
+
+impl lsp::request::Request for InlineValueRequest {
+    type Params = lsp::InlineValueParams;
+    type Result = Option<Vec<lsp::InlineValue>>;
+    const METHOD: &'static str =
+        <lsp::request::InlineValueRequest as lsp::request::Request>::METHOD;
+}
+
 #[derive(Debug)]
 pub struct Client {
     id: LanguageServerId,
@@ -62,6 +89,9 @@ pub struct Client {
     initialize_notify: Arc<Notify>,
     /// workspace folders added while the server is still initializing
     req_timeout: u64,
+    trace: Mutex<LanguageServerTrace>,
+    trace_tx: broadcast::Sender<TraceEntry>,
+    pending_requests: PendingRequests,
 }
 
 impl Client {
@@ -116,12 +146,8 @@ pub fn try_add_doc(
             // wait and see if anyone ever runs into it.
             tokio::spawn(async move {
                 client.initialize_notify.notified().await;
-                if let Some(workspace_folders_caps) = client
-                    .capabilities()
-                    .workspace
-                    .as_ref()
-                    .and_then(|cap| cap.workspace_folders.as_ref())
-                    .filter(|cap| cap.supported.unwrap_or(false))
+                if let Some(workspace_folders_caps) =
+                    workspace_folders_capabilities(client.capabilities())
                 {
                     client.add_workspace_folder(
                         root_uri,
@@ -132,12 +158,7 @@ pub fn try_add_doc(
             return true;
         };
 
-        if let Some(workspace_folders_caps) = capabilities
-            .workspace
-            .as_ref()
-            .and_then(|cap| cap.workspace_folders.as_ref())
-            .filter(|cap| cap.supported.unwrap_or(false))
-        {
+        if let Some(workspace_folders_caps) = workspace_folders_capabilities(capabilities) {
             self.add_workspace_folder(
                 root_uri,
                 workspace_folders_caps.change_notifications.as_ref(),
@@ -176,6 +197,62 @@ fn add_workspace_folder(
         self.did_change_workspace(vec![workspace_for_uri(root_uri)], Vec::new())
     }
 
+    /// Whether the server supports multiple workspace folders.
+    ///
+    /// Client has to be initialized otherwise this function panics
+    pub fn supports_workspace_folders(&self) -> bool {
+        workspace_folders_capabilities(self.capabilities()).is_some()
+    }
+
+    /// Adds `uri` to the workspace folders of the server unless it is registered already.
+    ///
+    /// Returns `false` if the server does not support workspace folders.
+    /// Client has to be initialized otherwise this function panics
+    pub fn add_workspace_folder_uri(&self, uri: lsp::Url) -> bool {
+        let Some(workspace_folders_caps) = workspace_folders_capabilities(self.capabilities())
+        else {
+            return false;
+        };
+        if !self
+            .workspace_folders
+            .lock()
+            .iter()
+            .any(|workspace| workspace.uri == uri)
+        {
+            self.add_workspace_folder(
+                Some(uri),
+                workspace_folders_caps.change_notifications.as_ref(),
+            );
+        }
+        true
+    }
+
+    /// Removes `uri` from the workspace folders of the server.
+    ///
+    /// Returns `false` if the server does not support workspace folders or `uri` is not one of
+    /// its workspace folders.
+    /// Client has to be initialized otherwise this function panics
+    pub fn remove_workspace_folder(&self, uri: &lsp::Url) -> bool {
+        let Some(workspace_folders_caps) = workspace_folders_capabilities(self.capabilities())
+        else {
+            return false;
+        };
+        let removed = {
+            let mut workspace_folders = self.workspace_folders.lock();
+            let Some(idx) = workspace_folders
+                .iter()
+                .position(|workspace| &workspace.uri == uri)
+            else {
+                return false;
+            };
+            workspace_folders.remove(idx)
+        };
+        if Some(&OneOf::Left(false)) != workspace_folders_caps.change_notifications.as_ref() {
+            self.did_change_workspace(Vec::new(), vec![removed]);
+        }
+        true
+    }
+
     /// Merge FormattingOptions with 'config.format' and return it
     fn get_merged_formatting_options(
         &self,
@@ -210,6 +287,7 @@ pub fn start(
         id: LanguageServerId,
         name: String,
         req_timeout: u64,
+        trace: LanguageServerTrace,
     ) -> Result<(
         Self,
         UnboundedReceiver<(LanguageServerId, Call)>,
@@ -236,7 +314,7 @@ pub fn start(
         let reader = BufReader::new(process.stdout.take().expect("Failed to open stdout"));
         let stderr = BufReader::new(process.stderr.take().expect("Failed to open stderr"));
 
-        let (server_rx, server_tx, initialize_notify) =
+        let (server_rx, server_tx, initialize_notify, trace_tx, pending_requests) =
             Transport::start(reader, writer, stderr, id, name.clone());
 
         let workspace_folders = root_uri
@@ -258,6 +336,9 @@ pub fn start(
             root_uri,
             workspace_folders: Mutex::new(workspace_folders),
             initialize_notify: initialize_notify.clone(),
+            trace: Mutex::new(trace),
+            trace_tx,
+            pending_requests,
         };
 
         Ok((client, server_rx, initialize_notify))
@@ -287,6 +368,10 @@ fn value_into_params(value: Value) -> jsonrpc::Params {
         }
     }
 
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
     pub fn is_initialized(&self) -> bool {
         self.capabilities.get().is_some()
     }
@@ -381,6 +466,10 @@ pub fn supports_feature(&self, feature: LanguageServerFeature) -> bool {
                 capabilities.inlay_hint_provider,
                 Some(OneOf::Left(true) | OneOf::Right(InlayHintServerCapabilities::Options(_)))
             ),
+            LanguageServerFeature::InlineValues => matches!(
+                capabilities.inline_value_provider,
+                Some(OneOf::Left(true) | OneOf::Right(_))
+            ),
             LanguageServerFeature::DocumentColors => matches!(
                 capabilities.color_provider,
                 Some(
@@ -412,9 +501,26 @@ pub fn config(&self) -> Option<&Value> {
         self.config.as_ref()
     }
 
-    pub async fn workspace_folders(
+    /// The trace level last requested from the server.
+    pub fn trace(&self) -> LanguageServerTrace {
+        *self.trace.lock()
+    }
+
+    /// Subscribes to the JSON-RPC messages exchanged with this server from now on.
+    ///
+    /// Messages are only captured while at least one receiver is alive.
+    pub fn subscribe_trace(&self) -> broadcast::Receiver<TraceEntry> {
+        self.trace_tx.subscribe()
+    }
+
+    /// Requests that were sent to the server and have not been answered yet.
+    pub fn pending_requests(
         &self,
-    ) -> parking_lot::MutexGuard<'_, Vec<lsp::WorkspaceFolder>> {
+    ) -> parking_lot::MutexGuard<'_, HashMap<jsonrpc::Id, PendingRequest>> {
+        self.pending_requests.lock()
+    }
+
+    pub fn workspace_folders(&self) -> parking_lot::MutexGuard<'_, Vec<lsp::WorkspaceFolder>> {
         self.workspace_folders.lock()
     }
 
@@ -669,6 +775,7 @@ pub(crate) async fn initialize(&self, enable_snippets: bool) -> Result<lsp::Init
                                     lsp::CodeActionKind::REFACTOR_REWRITE,
                                     lsp::CodeActionKind::SOURCE,
                                     lsp::CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                                    lsp::CodeActionKind::SOURCE_FIX_ALL,
                                 ]
                                 .iter()
                                 .map(|kind| kind.as_str().to_string())
@@ -701,6 +808,9 @@ pub(crate) async fn initialize(&self, enable_snippets: bool) -> Result<lsp::Init
                         dynamic_registration: Some(false),
                         resolve_support: None,
                     }),
+                    inline_value: Some(lsp::InlineValueClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
                     ..Default::default()
                 }),
                 window: Some(lsp::WindowClientCapabilities {
@@ -717,7 +827,7 @@ pub(crate) async fn initialize(&self, enable_snippets: bool) -> Result<lsp::Init
                 }),
                 ..Default::default()
             },
-            trace: None,
+            trace: Some(trace::trace_value(self.trace())),
             client_info: Some(lsp::ClientInfo {
                 name: String::from("helix"),
                 version: Some(String::from(VERSION_AND_GIT_HASH)),
@@ -737,6 +847,14 @@ pub fn exit(&self) {
         self.notify::<lsp::notification::Exit>(())
     }
 
+    /// Changes the verbosity of the `$/logTrace` notifications sent by the server.
+    pub fn set_trace(&self, trace: LanguageServerTrace) {
+        *self.trace.lock() = trace;
+        self.notify::<lsp::notification::SetTrace>(lsp::SetTraceParams {
+            value: trace::trace_value(trace),
+        })
+    }
+
     /// Tries to shut down the language server but returns
     /// early if server responds with an error.
     pub async fn shutdown_and_exit(&self) -> Result<()> {
@@ -1135,6 +1253,27 @@ pub fn text_document_range_inlay_hints(
         Some(self.call::<lsp::request::InlayHintRequest>(params))
     }
 
+    pub fn text_document_inline_value(
+        &self,
+        text_document: lsp::TextDocumentIdentifier,
+        range: lsp::Range,
+        context: lsp::InlineValueContext,
+    ) -> Option<impl Future<Output = Result<Option<Vec<lsp::InlineValue>>>>> {
+        match self.capabilities.get().unwrap().inline_value_provider {
+            Some(lsp::OneOf::Left(true) | lsp::OneOf::Right(_)) => (),
+            _ => return None,
+        }
+
+        let params = lsp::InlineValueParams {
+            text_document,
+            range,
+            context,
+            work_done_progress_params: lsp::WorkDoneProgressParams::default(),
+        };
+
+        Some(self.call::<InlineValueRequest>(params))
+    }
+
     pub fn text_document_document_color(
         &self,
         text_document: lsp::TextDocumentIdentifier,
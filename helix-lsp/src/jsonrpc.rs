@@ -312,6 +312,15 @@ pub enum Output {
     Success(Success),
 }
 
+impl Output {
+    pub fn id(&self) -> &Id {
+        match self {
+            Output::Success(success) => &success.id,
+            Output::Failure(failure) => &failure.id,
+        }
+    }
+}
+
 impl From<Output> for Result<Value, Error> {
     fn from(output: Output) -> Self {
         match output {
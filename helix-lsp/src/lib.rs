@@ -2,6 +2,7 @@
 pub mod file_event;
 mod file_operations;
 pub mod jsonrpc;
+pub mod trace;
 mod transport;
 
 use arc_swap::ArcSwap;
@@ -10,12 +11,14 @@
 pub use helix_lsp_types as lsp;
 pub use jsonrpc::Call;
 pub use lsp::{Position, Url};
+pub use transport::PendingRequest;
 
 use futures_util::stream::select_all::SelectAll;
 use helix_core::syntax::config::{
-    LanguageConfiguration, LanguageServerConfiguration, LanguageServerFeatures,
+    LanguageConfiguration, LanguageServerConfiguration, LanguageServerFeatures, LanguageServerTrace,
 };
 use helix_stdx::path;
+use parking_lot::Mutex;
 use slotmap::SlotMap;
 use tokio::sync::mpsc::UnboundedReceiver;
 
@@ -63,6 +66,17 @@ pub enum OffsetEncoding {
     Utf16,
 }
 
+impl std::fmt::Display for OffsetEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let encoding = match self {
+            OffsetEncoding::Utf8 => "utf-8",
+            OffsetEncoding::Utf32 => "utf-32",
+            OffsetEncoding::Utf16 => "utf-16",
+        };
+        f.write_str(encoding)
+    }
+}
+
 pub mod util {
     use super::*;
     use helix_core::line_ending::{line_end_byte_index, line_end_char_index};
@@ -513,6 +527,7 @@ pub enum Notification {
     PublishDiagnostics(lsp::PublishDiagnosticsParams),
     ShowMessage(lsp::ShowMessageParams),
     LogMessage(lsp::LogMessageParams),
+    LogTrace(lsp::LogTraceParams),
     ProgressMessage(lsp::ProgressParams),
 }
 
@@ -536,6 +551,10 @@ pub fn parse(method: &str, params: jsonrpc::Params) -> Result<Notification> {
                 let params: lsp::LogMessageParams = params.parse()?;
                 Self::LogMessage(params)
             }
+            lsp::notification::LogTrace::METHOD => {
+                let params: lsp::LogTraceParams = params.parse()?;
+                Self::LogTrace(params)
+            }
             lsp::notification::Progress::METHOD => {
                 let params: lsp::ProgressParams = params.parse()?;
                 Self::ProgressMessage(params)
@@ -554,6 +573,9 @@ pub struct Registry {
     inner: SlotMap<LanguageServerId, Arc<Client>>,
     inner_by_name: HashMap<LanguageServerName, Vec<Arc<Client>>>,
     syn_loader: Arc<ArcSwap<helix_core::syntax::Loader>>,
+    /// Workspace folders that were added manually during this session. They are added to every
+    /// language server that supports workspace folders, including those started later on.
+    workspace_folders: Arc<Mutex<Vec<lsp::Url>>>,
     pub incoming: SelectAll<UnboundedReceiverStream<(LanguageServerId, Call)>>,
     pub file_event_handler: file_event::Handler,
 }
@@ -564,6 +586,7 @@ pub fn new(syn_loader: Arc<ArcSwap<helix_core::syntax::Loader>>) -> Self {
             inner: SlotMap::with_key(),
             inner_by_name: HashMap::new(),
             syn_loader,
+            workspace_folders: Arc::default(),
             incoming: SelectAll::new(),
             file_event_handler: file_event::Handler::new(),
         }
@@ -596,6 +619,7 @@ fn start_client(
         doc_path: Option<&std::path::PathBuf>,
         root_dirs: &[PathBuf],
         enable_snippets: bool,
+        trace: Option<LanguageServerTrace>,
     ) -> Result<Arc<Client>, StartupError> {
         let syn_loader = self.syn_loader.load();
         let config = syn_loader
@@ -611,6 +635,8 @@ fn start_client(
                 doc_path,
                 root_dirs,
                 enable_snippets,
+                trace,
+                self.workspace_folders.clone(),
             )
             .map(|client| {
                 self.incoming.push(UnboundedReceiverStream::new(client.1));
@@ -630,6 +656,8 @@ pub fn restart_server(
         root_dirs: &[PathBuf],
         enable_snippets: bool,
     ) -> Option<Result<Arc<Client>>> {
+        // keep the trace level that was set with `:lsp-trace` across restarts
+        let mut trace = None;
         if let Some(old_clients) = self.inner_by_name.remove(name) {
             if old_clients.is_empty() {
                 log::info!("restarting client for '{name}' which was manually stopped");
@@ -637,6 +665,7 @@ pub fn restart_server(
                 log::info!("stopping existing clients for '{name}'");
             }
             for old_client in old_clients {
+                trace = Some(old_client.trace());
                 self.file_event_handler.remove_client(old_client.id());
                 self.inner.remove(old_client.id());
                 tokio::spawn(async move {
@@ -650,6 +679,7 @@ pub fn restart_server(
             doc_path,
             root_dirs,
             enable_snippets,
+            trace,
         ) {
             Ok(client) => client,
             Err(StartupError::NoRequiredRootFound) => return None,
@@ -712,6 +742,7 @@ pub fn get<'a>(
                     doc_path,
                     root_dirs,
                     enable_snippets,
+                    None,
                 ) {
                     Ok(client) => {
                         self.inner_by_name
@@ -730,6 +761,54 @@ pub fn get<'a>(
     pub fn iter_clients(&self) -> impl Iterator<Item = &Arc<Client>> {
         self.inner.values()
     }
+
+    /// The workspace folders that were added manually during this session.
+    pub fn workspace_folders(&self) -> Vec<lsp::Url> {
+        self.workspace_folders.lock().clone()
+    }
+
+    /// Adds a workspace folder to every running language server that supports workspace folders
+    /// and remembers it for language servers started later in this session.
+    ///
+    /// Returns the names of the running language servers that don't support workspace folders.
+    pub fn add_workspace_folder(&mut self, uri: lsp::Url) -> Vec<LanguageServerName> {
+        {
+            let mut workspace_folders = self.workspace_folders.lock();
+            if !workspace_folders.contains(&uri) {
+                workspace_folders.push(uri.clone());
+            }
+        }
+
+        // Servers that are still initializing pick up the folder once they are initialized.
+        let mut unsupported: Vec<_> = self
+            .iter_clients()
+            .filter(|client| client.is_initialized())
+            .filter(|client| !client.add_workspace_folder_uri(uri.clone()))
+            .map(|client| client.name().to_owned())
+            .collect();
+        unsupported.sort_unstable();
+        unsupported.dedup();
+        unsupported
+    }
+
+    /// Removes a workspace folder from every running language server and forgets it for this
+    /// session.
+    ///
+    /// Returns `false` if neither a language server nor the session used the workspace folder.
+    pub fn remove_workspace_folder(&mut self, uri: &lsp::Url) -> bool {
+        let mut removed = {
+            let mut workspace_folders = self.workspace_folders.lock();
+            let len = workspace_folders.len();
+            workspace_folders.retain(|folder| folder != uri);
+            workspace_folders.len() != len
+        };
+        for client in self.iter_clients() {
+            if client.is_initialized() {
+                removed |= client.remove_workspace_folder(uri);
+            }
+        }
+        removed
+    }
 }
 
 #[derive(Debug)]
@@ -864,6 +943,7 @@ fn from(value: T) -> Self {
 
 /// start_client takes both a LanguageConfiguration and a LanguageServerConfiguration to ensure that
 /// it is only called when it makes sense.
+#[allow(clippy::too_many_arguments)]
 fn start_client(
     id: LanguageServerId,
     name: String,
@@ -872,6 +952,8 @@ fn start_client(
     doc_path: Option<&std::path::PathBuf>,
     root_dirs: &[PathBuf],
     enable_snippets: bool,
+    trace: Option<LanguageServerTrace>,
+    workspace_folders: Arc<Mutex<Vec<lsp::Url>>>,
 ) -> Result<NewClient, StartupError> {
     let (workspace, workspace_is_cwd) = helix_loader::find_workspace();
     let workspace = path::normalize(workspace);
@@ -911,6 +993,7 @@ fn start_client(
         id,
         name,
         ls_config.timeout,
+        trace.unwrap_or(ls_config.trace),
     )?;
 
     let client = Arc::new(client);
@@ -937,6 +1020,14 @@ fn start_client(
         _client.notify::<lsp::notification::Initialized>(lsp::InitializedParams {});
 
         initialize_notify.notify_one();
+
+        // add the workspace folders that were added manually during this session
+        let workspace_folders = workspace_folders.lock().clone();
+        for uri in workspace_folders {
+            if !_client.add_workspace_folder_uri(uri) {
+                break;
+            }
+        }
     });
 
     Ok(NewClient(client, incoming))
@@ -0,0 +1,173 @@
+//! Capturing of the JSON-RPC traffic between helix and a language server.
+//!
+//! The [`Transport`](crate::transport::Transport) publishes a [`TraceEntry`] for every message
+//! it sends or receives while there is at least one subscriber, see [`Client::subscribe_trace`].
+//!
+//! [`Client::subscribe_trace`]: crate::Client::subscribe_trace
+
+use std::fmt;
+use std::time::Duration;
+
+use helix_core::syntax::config::LanguageServerTrace;
+
+use crate::{
+    jsonrpc,
+    lsp::{self, notification::Notification as _},
+};
+
+/// Payloads longer than this (in bytes) are truncated in trace entries.
+pub const MAX_PAYLOAD_LEN: usize = 1024;
+
+/// The number of entries a slow subscriber may lag behind before messages are dropped.
+pub(crate) const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent from helix to the language server.
+    Outgoing,
+    /// Received by helix from the language server.
+    Incoming,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Request,
+    Notification,
+    Response,
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceEntry {
+    pub direction: Direction,
+    pub kind: MessageKind,
+    /// The method of the message. For responses this is the method of the original request,
+    /// if it is known.
+    pub method: Option<String>,
+    pub id: Option<jsonrpc::Id>,
+    /// Time between sending a request and receiving its response.
+    pub latency: Option<Duration>,
+    /// The serialized message, truncated to [`MAX_PAYLOAD_LEN`].
+    pub payload: String,
+}
+
+impl TraceEntry {
+    pub(crate) fn new(
+        direction: Direction,
+        kind: MessageKind,
+        method: Option<String>,
+        id: Option<jsonrpc::Id>,
+        latency: Option<Duration>,
+        payload: &str,
+    ) -> Self {
+        Self {
+            direction,
+            kind,
+            method,
+            id,
+            latency,
+            payload: truncate(payload, MAX_PAYLOAD_LEN),
+        }
+    }
+
+    /// An entry for a `$/logTrace` notification, showing the logged message instead of the
+    /// serialized notification.
+    pub(crate) fn log_trace(params: &lsp::LogTraceParams) -> Self {
+        let message = match &params.verbose {
+            Some(verbose) => format!("{}\n{verbose}", params.message),
+            None => params.message.clone(),
+        };
+        Self::new(
+            Direction::Incoming,
+            MessageKind::Notification,
+            Some(lsp::notification::LogTrace::METHOD.to_string()),
+            None,
+            None,
+            &message,
+        )
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self.direction {
+            Direction::Outgoing => "-->",
+            Direction::Incoming => "<--",
+        };
+        let kind = match self.kind {
+            MessageKind::Request => "request",
+            MessageKind::Notification => "notification",
+            MessageKind::Response => "response",
+        };
+        write!(f, "{arrow} {kind}")?;
+        if let Some(method) = &self.method {
+            write!(f, " {method}")?;
+        }
+        if let Some(id) = &self.id {
+            write!(f, " (id {id})")?;
+        }
+        if let Some(latency) = self.latency {
+            write!(f, " in {}ms", latency.as_millis())?;
+        }
+        write!(f, ": {}", self.payload)
+    }
+}
+
+fn truncate(payload: &str, max_len: usize) -> String {
+    if payload.len() <= max_len {
+        return payload.to_string();
+    }
+    let mut end = max_len;
+    while !payload.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… ({} bytes)", &payload[..end], payload.len())
+}
+
+pub(crate) fn trace_value(trace: LanguageServerTrace) -> lsp::TraceValue {
+    match trace {
+        LanguageServerTrace::Off => lsp::TraceValue::Off,
+        LanguageServerTrace::Messages => lsp::TraceValue::Messages,
+        LanguageServerTrace::Verbose => lsp::TraceValue::Verbose,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdef", 3), "abc… (6 bytes)");
+        // 'é' is two bytes wide, the cut must not split it
+        assert_eq!(truncate("aé", 2), "a… (3 bytes)");
+    }
+
+    #[test]
+    fn formats_entry() {
+        let entry = TraceEntry::new(
+            Direction::Incoming,
+            MessageKind::Response,
+            Some("textDocument/hover".to_string()),
+            Some(jsonrpc::Id::Num(3)),
+            Some(Duration::from_millis(42)),
+            r#"{"result":null}"#,
+        );
+        assert_eq!(
+            entry.to_string(),
+            r#"<-- response textDocument/hover (id 3) in 42ms: {"result":null}"#
+        );
+    }
+
+    #[test]
+    fn formats_log_trace() {
+        let entry = TraceEntry::log_trace(&lsp::LogTraceParams {
+            message: "indexing".to_string(),
+            verbose: Some("3 files".to_string()),
+        });
+        assert_eq!(
+            entry.to_string(),
+            "<-- notification $/logTrace: indexing\n3 files"
+        );
+    }
+}
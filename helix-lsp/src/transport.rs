@@ -1,20 +1,24 @@
 use crate::{
     jsonrpc,
     lsp::{self, notification::Notification as _},
+    trace::{self, Direction, MessageKind, TraceEntry},
     Error, LanguageServerId, Result,
 };
 use anyhow::Context;
 use log::{error, info};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::{
     io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter},
     process::{ChildStderr, ChildStdin, ChildStdout},
     sync::{
+        broadcast,
         mpsc::{unbounded_channel, Sender, UnboundedReceiver, UnboundedSender},
-        Mutex, Notify,
+        Notify,
     },
 };
 
@@ -39,14 +43,33 @@ enum ServerMessage {
     Call(jsonrpc::Call),
 }
 
+/// A request sent to the language server that is still awaiting its response.
+#[derive(Debug)]
+pub struct PendingRequest {
+    chan: Sender<Result<Value>>,
+    pub method: String,
+    pub sent_at: Instant,
+}
+
+impl PendingRequest {
+    /// Whether the response is still awaited. It isn't once the request timed out.
+    pub fn is_awaited(&self) -> bool {
+        !self.chan.is_closed()
+    }
+}
+
+pub(crate) type PendingRequests = Arc<Mutex<HashMap<jsonrpc::Id, PendingRequest>>>;
+
 #[derive(Debug)]
 pub struct Transport {
     id: LanguageServerId,
     name: String,
-    pending_requests: Mutex<HashMap<jsonrpc::Id, Sender<Result<Value>>>>,
+    pending_requests: PendingRequests,
+    trace: broadcast::Sender<TraceEntry>,
 }
 
 impl Transport {
+    #[allow(clippy::type_complexity)]
     pub fn start(
         server_stdout: BufReader<ChildStdout>,
         server_stdin: BufWriter<ChildStdin>,
@@ -57,15 +80,20 @@ pub fn start(
         UnboundedReceiver<(LanguageServerId, jsonrpc::Call)>,
         UnboundedSender<Payload>,
         Arc<Notify>,
+        broadcast::Sender<TraceEntry>,
+        PendingRequests,
     ) {
         let (client_tx, rx) = unbounded_channel();
         let (tx, client_rx) = unbounded_channel();
         let notify = Arc::new(Notify::new());
+        let (trace_tx, _) = broadcast::channel(trace::CHANNEL_CAPACITY);
+        let pending_requests = PendingRequests::default();
 
         let transport = Self {
             id,
             name,
-            pending_requests: Mutex::new(HashMap::default()),
+            pending_requests: pending_requests.clone(),
+            trace: trace_tx.clone(),
         };
 
         let transport = Arc::new(transport);
@@ -84,7 +112,80 @@ pub fn start(
             notify.clone(),
         ));
 
-        (rx, tx, notify)
+        (rx, tx, notify, trace_tx, pending_requests)
+    }
+
+    fn is_tracing(&self) -> bool {
+        self.trace.receiver_count() > 0
+    }
+
+    fn trace(&self, entry: TraceEntry) {
+        // Sending only fails if every subscriber went away in the meantime.
+        let _ = self.trace.send(entry);
+    }
+
+    async fn trace_server_message(&self, msg: &ServerMessage) {
+        if !self.is_tracing() {
+            return;
+        }
+        let payload = match serde_json::to_string(msg) {
+            Ok(payload) => payload,
+            Err(err) => {
+                error!("{} failed to serialize traced message: {err}", self.name);
+                return;
+            }
+        };
+        let entry = match msg {
+            ServerMessage::Output(output) => {
+                let id = output.id().clone();
+                let pending_requests = self.pending_requests.lock();
+                let request = pending_requests.get(&id);
+                TraceEntry::new(
+                    Direction::Incoming,
+                    MessageKind::Response,
+                    request.map(|request| request.method.clone()),
+                    Some(id),
+                    request.map(|request| request.sent_at.elapsed()),
+                    &payload,
+                )
+            }
+            ServerMessage::Call(jsonrpc::Call::MethodCall(call)) => TraceEntry::new(
+                Direction::Incoming,
+                MessageKind::Request,
+                Some(call.method.clone()),
+                Some(call.id.clone()),
+                None,
+                &payload,
+            ),
+            ServerMessage::Call(jsonrpc::Call::Notification(notification))
+                if notification.method == lsp::notification::LogTrace::METHOD =>
+            {
+                match notification.params.clone().parse() {
+                    Ok(params) => TraceEntry::log_trace(&params),
+                    Err(err) => {
+                        error!("{} sent invalid $/logTrace params: {err}", self.name);
+                        return;
+                    }
+                }
+            }
+            ServerMessage::Call(jsonrpc::Call::Notification(notification)) => TraceEntry::new(
+                Direction::Incoming,
+                MessageKind::Notification,
+                Some(notification.method.clone()),
+                None,
+                None,
+                &payload,
+            ),
+            ServerMessage::Call(jsonrpc::Call::Invalid { id }) => TraceEntry::new(
+                Direction::Incoming,
+                MessageKind::Request,
+                None,
+                Some(id.clone()),
+                None,
+                &payload,
+            ),
+        };
+        self.trace(entry);
     }
 
     async fn recv_server_message(
@@ -161,17 +262,46 @@ async fn send_payload_to_server(
         payload: Payload,
     ) -> Result<()> {
         //TODO: reuse string
-        let json = match payload {
+        let (json, kind, method, id) = match payload {
             Payload::Request { chan, value } => {
-                self.pending_requests
-                    .lock()
-                    .await
-                    .insert(value.id.clone(), chan);
-                serde_json::to_string(&value)?
+                self.pending_requests.lock().insert(
+                    value.id.clone(),
+                    PendingRequest {
+                        chan,
+                        method: value.method.clone(),
+                        sent_at: Instant::now(),
+                    },
+                );
+                (
+                    serde_json::to_string(&value)?,
+                    MessageKind::Request,
+                    Some(value.method),
+                    Some(value.id),
+                )
             }
-            Payload::Notification(value) => serde_json::to_string(&value)?,
-            Payload::Response(error) => serde_json::to_string(&error)?,
+            Payload::Notification(value) => (
+                serde_json::to_string(&value)?,
+                MessageKind::Notification,
+                Some(value.method),
+                None,
+            ),
+            Payload::Response(output) => (
+                serde_json::to_string(&output)?,
+                MessageKind::Response,
+                None,
+                Some(output.id().clone()),
+            ),
         };
+        if self.is_tracing() {
+            self.trace(TraceEntry::new(
+                Direction::Outgoing,
+                kind,
+                method,
+                id,
+                None,
+                &json,
+            ));
+        }
         self.send_string_to_server(server_stdin, json, &self.name)
             .await
     }
@@ -231,7 +361,8 @@ async fn process_request_response(
             }
         };
 
-        if let Some(tx) = self.pending_requests.lock().await.remove(&id) {
+        let request = self.pending_requests.lock().remove(&id);
+        if let Some(PendingRequest { chan: tx, .. }) = request {
             match tx.send(result).await {
                 Ok(_) => (),
                 Err(_) => error!(
@@ -267,6 +398,7 @@ async fn recv(
             .await
             {
                 Ok(msg) => {
+                    transport.trace_server_message(&msg).await;
                     match transport
                         .process_server_message(&client_tx, msg, &transport.name)
                         .await
@@ -287,7 +419,9 @@ async fn recv(
                     }
 
                     // Close any outstanding requests.
-                    for (id, tx) in transport.pending_requests.lock().await.drain() {
+                    let pending_requests: Vec<_> =
+                        transport.pending_requests.lock().drain().collect();
+                    for (id, PendingRequest { chan: tx, .. }) in pending_requests {
                         match tx.send(Err(Error::StreamClosed)).await {
                             Ok(_) => (),
                             Err(_) => {
@@ -4,7 +4,7 @@
 use helix_lsp::{
     lsp::{self, notification::Notification},
     util::lsp_range_to_range,
-    LanguageServerId, LspProgressMap,
+    LanguageServerId,
 };
 use helix_stdx::path::get_relative_path;
 use helix_view::{
@@ -76,7 +76,6 @@ pub struct Application {
 
     signals: Signals,
     jobs: Jobs,
-    lsp_progress: LspProgressMap,
 
     theme_mode: Option<theme::Mode>,
 }
@@ -145,6 +144,10 @@ pub fn new(args: Args, config: Config, lang_loader: syntax::Loader) -> Result<Se
             theme_mode,
         );
 
+        if let Err(err) = editor.load_breakpoints() {
+            log::error!("Failed to load breakpoints: {err}");
+        }
+
         let keys = Box::new(Map::new(Arc::clone(&config), |config: &Config| {
             &config.keys
         }));
@@ -261,7 +264,6 @@ pub fn new(args: Args, config: Config, lang_loader: syntax::Loader) -> Result<Se
             config,
             signals,
             jobs: Jobs::new(),
-            lsp_progress: LspProgressMap::new(),
             theme_mode,
         };
 
@@ -840,6 +842,9 @@ macro_rules! language_server {
                     Notification::LogMessage(params) => {
                         log::info!("window/logMessage: {:?}", params);
                     }
+                    Notification::LogTrace(params) => {
+                        log::info!("$/logTrace: {:?}", params);
+                    }
                     Notification::ProgressMessage(params)
                         if !self
                             .compositor
@@ -869,8 +874,8 @@ macro_rules! language_server {
                                 if message.is_some() {
                                     (None, message, &None)
                                 } else {
-                                    self.lsp_progress.end_progress(server_id, &token);
-                                    if !self.lsp_progress.is_progressing(server_id) {
+                                    self.editor.lsp_progress.end_progress(server_id, &token);
+                                    if !self.editor.lsp_progress.is_progressing(server_id) {
                                         editor_view.spinners_mut().get_or_create(server_id).stop();
                                     }
                                     self.editor.clear_status();
@@ -883,7 +888,7 @@ macro_rules! language_server {
 
                         if self.editor.config().lsp.display_progress_messages {
                             let title =
-                                title.or_else(|| self.lsp_progress.title(server_id, &token));
+                                title.or_else(|| self.editor.lsp_progress.title(server_id, &token));
                             if title.is_some() || percentage.is_some() || message.is_some() {
                                 use std::fmt::Write as _;
                                 let mut status = format!("{}: ", language_server!().name());
@@ -905,16 +910,22 @@ macro_rules! language_server {
 
                         match work {
                             lsp::WorkDoneProgress::Begin(begin_status) => {
-                                self.lsp_progress
-                                    .begin(server_id, token.clone(), begin_status);
+                                self.editor.lsp_progress.begin(
+                                    server_id,
+                                    token.clone(),
+                                    begin_status,
+                                );
                             }
                             lsp::WorkDoneProgress::Report(report_status) => {
-                                self.lsp_progress
-                                    .update(server_id, token.clone(), report_status);
+                                self.editor.lsp_progress.update(
+                                    server_id,
+                                    token.clone(),
+                                    report_status,
+                                );
                             }
                             lsp::WorkDoneProgress::End(_) => {
-                                self.lsp_progress.end_progress(server_id, &token);
-                                if !self.lsp_progress.is_progressing(server_id) {
+                                self.editor.lsp_progress.end_progress(server_id, &token);
+                                if !self.editor.lsp_progress.is_progressing(server_id) {
                                     editor_view.spinners_mut().get_or_create(server_id).stop();
                                 };
                             }
@@ -981,7 +992,7 @@ macro_rules! language_server {
                         })
                     }
                     Ok(MethodCall::WorkDoneProgressCreate(params)) => {
-                        self.lsp_progress.create(server_id, params.token);
+                        self.editor.lsp_progress.create(server_id, params.token);
 
                         let editor_view = self
                             .compositor
@@ -1020,7 +1031,7 @@ macro_rules! language_server {
                         }
                     }
                     Ok(MethodCall::WorkspaceFolders) => {
-                        Ok(json!(&*language_server!().workspace_folders().await))
+                        Ok(json!(&*language_server!().workspace_folders()))
                     }
                     Ok(MethodCall::WorkspaceConfiguration(params)) => {
                         let language_server = language_server!();
@@ -1290,6 +1301,11 @@ pub async fn close(&mut self) -> Vec<anyhow::Error> {
             errs.push(err);
         }
 
+        if let Err(err) = self.editor.save_breakpoints() {
+            log::error!("Failed to save breakpoints: {err}");
+            errs.push(err);
+        }
+
         if self.editor.close_language_servers(None).await.is_err() {
             log::error!("Timed out waiting for language servers to shutdown");
             errs.push(anyhow::format_err!(
@@ -10,7 +10,7 @@
     path::{self, find_paths},
     rope::{self, RopeSliceExt},
 };
-use helix_vcs::{FileChange, Hunk};
+use helix_vcs::{CommitInfo, FileChange, Hunk};
 pub use lsp::*;
 pub use syntax::*;
 use tui::{
@@ -63,9 +63,12 @@
 
 use crate::{
     compositor::{self, Component, Compositor},
-    filter_picker_entry,
+    ctrl, filter_picker_entry,
     job::Callback,
-    ui::{self, overlay::overlaid, Picker, PickerColumn, Popup, Prompt, PromptEvent},
+    ui::{
+        self, overlay::overlaid, picker::PathOrId, Picker, PickerColumn, Popup, Prompt,
+        PromptEvent, VcsStatus,
+    },
 };
 
 use crate::job::{self, Jobs};
@@ -412,6 +415,8 @@ pub fn doc(&self) -> &str {
         syntax_symbol_picker, "Open symbol picker from syntax information",
         lsp_or_syntax_symbol_picker, "Open symbol picker from LSP or syntax information",
         changed_file_picker, "Open changed file picker",
+        file_history_picker, "Open picker of commits changing the current file",
+        log_picker, "Open commit history picker",
         select_references_to_symbol_under_cursor, "Select symbol references",
         workspace_symbol_picker, "Open workspace symbol picker",
         syntax_workspace_symbol_picker, "Open workspace symbol picker from syntax information",
@@ -457,6 +462,8 @@ pub fn doc(&self) -> &str {
         goto_prev_change, "Goto previous change",
         goto_first_change, "Goto first change",
         goto_last_change, "Goto last change",
+        goto_next_conflict, "Goto next merge conflict, or next (X)HTML element without conflicts",
+        goto_prev_conflict, "Goto previous merge conflict, or previous (X)HTML element without conflicts",
         goto_line_start, "Goto line start",
         goto_line_end, "Goto line end",
         goto_column, "Goto column",
@@ -584,17 +591,25 @@ pub fn doc(&self) -> &str {
         dap_launch, "Launch debug target",
         dap_restart, "Restart debugging session",
         dap_toggle_breakpoint, "Toggle breakpoint",
+        dap_breakpoints, "Open breakpoint picker",
         dap_continue, "Continue program execution",
         dap_pause, "Pause program execution",
         dap_step_in, "Step in",
         dap_step_out, "Step out",
         dap_next, "Step to next",
+        dap_step_in_instruction, "Step in by one instruction",
+        dap_next_instruction, "Step to next instruction",
+        dap_disassemble, "Show disassembly of current stack frame",
         dap_variables, "List variables",
+        dap_repl, "Evaluate expression in debug console",
+        dap_watches, "Show watch expressions",
         dap_terminate, "End debug session",
         dap_edit_condition, "Edit breakpoint condition on current line",
         dap_edit_log, "Edit breakpoint log message on current line",
+        dap_edit_hit_condition, "Edit breakpoint hit condition on current line",
         dap_switch_thread, "Switch current thread",
         dap_switch_stack_frame, "Switch stack frame",
+        dap_switch_session, "Switch debugging session",
         dap_enable_exceptions, "Enable exception breakpoints",
         dap_disable_exceptions, "Disable exception breakpoints",
         shell_pipe, "Pipe selections through shell command",
@@ -3154,28 +3169,33 @@ fn buffer_picker(cx: &mut Context) {
     struct BufferMeta {
         id: DocumentId,
         path: Option<PathBuf>,
+        scratch_name: String,
         is_modified: bool,
         is_current: bool,
         focused_at: std::time::Instant,
     }
 
-    let new_meta = |doc: &Document| BufferMeta {
-        id: doc.id(),
-        path: doc.path().cloned(),
-        is_modified: doc.is_modified(),
-        is_current: doc.id() == current,
-        focused_at: doc.focused_at,
-    };
+    let buffers = move |editor: &Editor| {
+        let new_meta = |doc: &Document| BufferMeta {
+            id: doc.id(),
+            path: doc.path().cloned(),
+            scratch_name: doc.scratch_name().to_string(),
+            is_modified: doc.is_modified(),
+            is_current: doc.id() == current,
+            focused_at: doc.focused_at,
+        };
 
-    let mut items = cx
-        .editor
-        .documents
-        .values()
-        .map(new_meta)
-        .collect::<Vec<BufferMeta>>();
+        let mut items = editor
+            .documents
+            .values()
+            .map(new_meta)
+            .collect::<Vec<BufferMeta>>();
 
-    // mru
-    items.sort_unstable_by_key(|item| std::cmp::Reverse(item.focused_at));
+        // mru
+        items.sort_unstable_by_key(|item| std::cmp::Reverse(item.focused_at));
+        items
+    };
+    let items = buffers(cx.editor);
 
     let columns = [
         PickerColumn::new("id", |meta: &BufferMeta, _| meta.id.to_string().into()),
@@ -3189,6 +3209,13 @@ struct BufferMeta {
             }
             flags.into()
         }),
+        PickerColumn::new(
+            "status",
+            |meta: &BufferMeta, status: &VcsStatus| match &meta.path {
+                Some(path) => status.format(path).into(),
+                None => Cell::default(),
+            },
+        ),
         PickerColumn::new("path", |meta: &BufferMeta, _| {
             let path = meta
                 .path
@@ -3196,13 +3223,14 @@ struct BufferMeta {
                 .map(helix_stdx::path::get_relative_path);
             path.as_deref()
                 .and_then(Path::to_str)
-                .unwrap_or(SCRATCH_BUFFER_NAME)
+                .unwrap_or(&meta.scratch_name)
                 .to_string()
                 .into()
         }),
     ];
     let initial_cursor = if items.len() <= 1 { 0 } else { 1 };
-    let picker = Picker::new(columns, 2, items, (), |cx, meta, action| {
+    let status = VcsStatus::new(cx.editor);
+    let picker = Picker::new(columns, 3, items, status, |cx, meta, action| {
         cx.editor.switch(meta.id, action);
     })
     .with_initial_cursor(initial_cursor)
@@ -3214,6 +3242,23 @@ struct BufferMeta {
         });
         Some((meta.id.into(), lines))
     });
+    let refresh_status = cx
+        .editor
+        .diff_providers
+        .refresh_status(helix_stdx::env::current_working_dir());
+    // the buffers are filtered by the status they were injected with
+    tokio::spawn(async move {
+        refresh_status.await;
+        job::dispatch(move |editor, compositor| {
+            if let Some(ui::overlay::Overlay {
+                content: picker, ..
+            }) = compositor.find::<ui::overlay::Overlay<Picker<BufferMeta, VcsStatus>>>()
+            {
+                picker.set_items(buffers(editor));
+            }
+        })
+        .await;
+    });
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
@@ -3402,6 +3447,135 @@ pub struct FileChangeData {
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
+fn file_history_picker(cx: &mut Context) {
+    let Some(path) = doc!(cx.editor).path().cloned() else {
+        cx.editor
+            .set_error("File history is not available for scratch buffers");
+        return;
+    };
+    commit_picker(cx, Some(path));
+}
+
+fn log_picker(cx: &mut Context) {
+    commit_picker(cx, None);
+}
+
+/// Opens a picker of the commits that changed `file`, or of all commits. Selecting a commit
+/// opens the current file at that commit, `Ctrl-g` compares the current file against it.
+fn commit_picker(cx: &mut Context, file: Option<PathBuf>) {
+    let doc = doc!(cx.editor);
+    let doc_id = doc.id();
+    let doc_path = doc.path().cloned();
+    let cwd = match &file {
+        Some(file) => file.parent().map(Path::to_path_buf).unwrap_or_default(),
+        None => helix_stdx::env::current_working_dir(),
+    };
+    if !cwd.exists() {
+        cx.editor
+            .set_error("Current working directory does not exist");
+        return;
+    }
+
+    let columns = [
+        PickerColumn::new("commit", |commit: &CommitInfo, _| commit.id.as_ref().into()),
+        PickerColumn::new("author", |commit: &CommitInfo, _| {
+            commit.author.as_ref().into()
+        }),
+        PickerColumn::new("date", |commit: &CommitInfo, _| {
+            commit.relative_time().into()
+        }),
+        PickerColumn::new("summary", |commit: &CommitInfo, _| {
+            commit.summary.as_ref().into()
+        }),
+    ];
+
+    let picker = Picker::new(
+        columns,
+        3, // summary
+        [],
+        (),
+        move |cx, commit: &CommitInfo, action| {
+            let Some(path) = &doc_path else {
+                cx.editor.set_error("Scratch buffers have no history");
+                return;
+            };
+            if let Err(err) = open_revision(cx.editor, path, &commit.id, action) {
+                cx.editor.set_error(err.to_string());
+            }
+        },
+    )
+    .with_preview(|_editor, commit| Some((PathOrId::Text(&commit.id), None)))
+    .with_text_preview("diff", {
+        let diff_providers = cx.editor.diff_providers.clone();
+        let cwd = cwd.clone();
+        move |revision| diff_providers.show_commit(&cwd, revision)
+    })
+    .with_key_action(ctrl!('g'), move |cx, commit| {
+        let Some(doc) = cx.editor.documents.get_mut(&doc_id) else {
+            return;
+        };
+        doc.set_diff_base_revision(Some(commit.id.to_string()));
+        doc.update_diff_bases(&cx.editor.diff_providers);
+        if doc.diff_handle().is_none() {
+            cx.editor.set_error(format!(
+                "The current buffer does not exist at {}",
+                commit.id
+            ));
+            return;
+        }
+        cx.editor
+            .set_status(format!("Showing changes against {}", commit.id));
+    });
+    let injector = picker.injector();
+
+    cx.editor
+        .diff_providers
+        .clone()
+        .for_each_commit(cwd, file, move |commit| match commit {
+            Ok(commit) => injector.push(commit).is_ok(),
+            Err(err) => {
+                status::report_blocking(err);
+                true
+            }
+        });
+    cx.push_layer(Box::new(overlaid(picker)));
+}
+
+/// Opens the contents of `path` at `revision` as a read-only scratch buffer named
+/// `path@revision`, decoded and highlighted like `path`.
+fn open_revision(
+    editor: &mut Editor,
+    path: &Path,
+    revision: &str,
+    action: Action,
+) -> anyhow::Result<()> {
+    let Some(contents) = editor.diff_providers.get_diff_base_at(path, revision) else {
+        bail!("{} does not exist at {revision}", path.display());
+    };
+    let encoding = editor.document_by_path(path).map(Document::encoding);
+    let (text, encoding, has_bom) =
+        helix_view::document::from_reader(&mut contents.as_slice(), encoding)?;
+    let mut doc = Document::from(
+        text,
+        Some((encoding, has_bom)),
+        editor.config.clone(),
+        editor.syn_loader.clone(),
+    );
+    doc.readonly = true;
+    doc.set_scratch_name(Some(format!(
+        "{}@{revision}",
+        helix_stdx::path::get_relative_path(path).display()
+    )));
+    let loader = editor.syn_loader.load();
+    if let Some(language) = loader.language_for_filename(path) {
+        let config = loader.language(language).config().clone();
+        doc.set_language(Some(config), &loader);
+    }
+    drop(loader);
+    editor.new_file_from_document(action, doc);
+    Ok(())
+}
+
 pub fn command_palette(cx: &mut Context) {
     let register = cx.register;
     let count = cx.count;
@@ -4109,6 +4283,64 @@ fn hunk_range(hunk: Hunk, text: RopeSlice) -> Range {
     Range::new(anchor, head)
 }
 
+fn goto_next_conflict(cx: &mut Context) {
+    goto_next_conflict_impl(cx, Direction::Forward)
+}
+
+fn goto_prev_conflict(cx: &mut Context) {
+    goto_next_conflict_impl(cx, Direction::Backward)
+}
+
+fn goto_next_conflict_impl(cx: &mut Context, direction: Direction) {
+    // conflicts share their keybindings with (X)HTML elements which are rarely needed
+    // while resolving conflicts
+    if doc!(cx.editor).conflicts().is_empty() {
+        return goto_ts_object_impl(cx, "xml-element", direction);
+    }
+    let count = cx.count() - 1;
+    let motion = move |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+        let doc_text = doc.text().slice(..);
+        let conflicts = doc.conflicts();
+
+        let selection = doc.selection(view.id).clone().transform(|range| {
+            let cursor_line = range.cursor_line(doc_text);
+            let idx = match direction {
+                Direction::Forward => conflicts
+                    .iter()
+                    .position(|conflict| conflict.lines.start > cursor_line)
+                    .map(|idx| (idx + count).min(conflicts.len() - 1)),
+                Direction::Backward => conflicts
+                    .iter()
+                    .rposition(|conflict| conflict.lines.end <= cursor_line)
+                    .map(|idx| idx.saturating_sub(count)),
+            };
+            let Some(idx) = idx else {
+                return range;
+            };
+            let lines = &conflicts[idx].lines;
+            let new_range = Range::new(
+                doc_text.line_to_char(lines.start),
+                doc_text.line_to_char(lines.end),
+            );
+            if editor.mode == Mode::Select {
+                let head = if new_range.head < range.anchor {
+                    new_range.anchor
+                } else {
+                    new_range.head
+                };
+
+                Range::new(range.anchor, head)
+            } else {
+                new_range.with_direction(direction)
+            }
+        });
+
+        doc.set_selection(view.id, selection)
+    };
+    cx.editor.apply_motion(motion);
+}
+
 pub mod insert {
     use crate::{events::PostInsertChar, key};
 
@@ -5737,7 +5969,7 @@ fn vsplit_new(cx: &mut Context) {
 }
 
 fn wclose(cx: &mut Context) {
-    if cx.editor.tree.views().count() == 1 {
+    if cx.editor.is_last_view(view!(cx.editor).id) {
         if let Err(err) = typed::buffers_remaining_impl(cx.editor) {
             cx.editor.set_error(err.to_string());
             return;
@@ -1,25 +1,41 @@
 use super::{Context, Editor};
 use crate::{
-    compositor::{self, Compositor},
-    job::{Callback, Jobs},
-    ui::{self, overlay::overlaid, Picker, Popup, Prompt, PromptEvent, Text},
+    compositor::{self, Component, Compositor},
+    job::{self, Callback, Jobs},
+    ui::{self, overlay::overlaid, Picker, Popup, Prompt, PromptEvent},
 };
 use dap::{StackFrame, Thread, ThreadStates};
-use helix_core::syntax::config::{DebugArgumentValue, DebugConfigCompletion, DebugTemplate};
-use helix_dap::{self as dap, requests::TerminateArguments};
+use helix_core::syntax::config::{
+    DebugAdapterConfig, DebugArgumentValue, DebugConfigCompletion, DebugTemplate,
+};
+use helix_dap::{
+    self as dap,
+    launch_json::{self, LaunchConfiguration, LaunchInput, LaunchJson},
+    registry::DebugAdapterId,
+    requests::TerminateArguments,
+};
 use helix_lsp::block_on;
-use helix_view::editor::Breakpoint;
+use helix_view::{
+    editor::{Action, Breakpoint, ConsoleCategory},
+    events::DebugFrameDidChange,
+    expansion,
+};
 
 use serde_json::{to_value, Value};
-use tui::text::Spans;
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 use anyhow::{anyhow, bail};
 
-use helix_view::handlers::dap::{breakpoints_changed, jump_to_stack_frame, select_thread_id};
+use helix_view::handlers::dap::{
+    breakpoints_changed, clear_inline_values, data_breakpoints_changed,
+    function_breakpoints_changed, instruction_breakpoints_changed, jump_to_stack_frame,
+    select_thread_id,
+};
 
 fn thread_picker(
     cx: &mut Context,
@@ -125,12 +141,6 @@ pub fn dap_start_impl(
         .and_then(|config| config.debugger.as_ref())
         .ok_or_else(|| anyhow!("No debug adapter available for language"))?;
 
-    let id = cx
-        .editor
-        .debug_adapters
-        .start_client(socket, config)
-        .map_err(|e| anyhow!("Failed to start debug client: {}", e))?;
-
     // TODO: avoid refetching all of this... pass a config in
     let template = match name {
         Some(name) => config.templates.iter().find(|t| t.name == name),
@@ -198,6 +208,24 @@ pub fn dap_start_impl(
     args.insert("cwd", to_value(helix_stdx::env::current_working_dir())?);
 
     let args = to_value(args).unwrap();
+    let config = config.clone();
+    let request = template.request.clone();
+    dap_start_request(cx, &config, socket, &request, args)
+}
+
+/// Starts the debug adapter and sends the `launch` or `attach` request.
+fn dap_start_request(
+    cx: &mut compositor::Context,
+    config: &DebugAdapterConfig,
+    socket: Option<std::net::SocketAddr>,
+    request: &str,
+    args: Value,
+) -> Result<(), anyhow::Error> {
+    let id = cx
+        .editor
+        .debug_adapters
+        .start_client(socket, config)
+        .map_err(|e| anyhow!("Failed to start debug client: {}", e))?;
 
     let callback = |_editor: &mut Editor, _compositor: &mut Compositor, _response: Value| {
         // if let Err(e) = result {
@@ -212,7 +240,7 @@ pub fn dap_start_impl(
         }
     };
 
-    match &template.request[..] {
+    match request {
         "launch" => {
             let call = debugger.launch(args);
             dap_callback(cx.jobs, call, callback);
@@ -228,6 +256,23 @@ pub fn dap_start_impl(
     Ok(())
 }
 
+/// A launch configuration offered by `dap_launch`.
+enum LaunchItem {
+    /// A template of the debug adapter configured for the language of the current document.
+    Template(DebugTemplate),
+    /// A configuration of the workspace's `.vscode/launch.json`.
+    LaunchJson(LaunchConfiguration),
+}
+
+impl LaunchItem {
+    fn name(&self) -> &str {
+        match self {
+            Self::Template(template) => &template.name,
+            Self::LaunchJson(configuration) => &configuration.name,
+        }
+    }
+}
+
 pub fn dap_launch(cx: &mut Context) {
     // TODO: Now that we support multiple Clients, we could run multiple debuggers at once but for now keep this as is
     if cx.editor.debug_adapters.get_active_client().is_some() {
@@ -237,52 +282,234 @@ pub fn dap_launch(cx: &mut Context) {
 
     let doc = doc!(cx.editor);
 
-    let config = match doc
+    let templates = doc
         .language_config()
         .and_then(|config| config.debugger.as_ref())
-    {
-        Some(c) => c,
-        None => {
+        .map(|config| config.templates.clone())
+        .unwrap_or_default();
+
+    let (launch_json, has_launch_json) = match LaunchJson::load(&helix_loader::find_workspace().0) {
+        Ok(Some(launch_json)) => (launch_json, true),
+        Ok(None) => (LaunchJson::default(), false),
+        Err(err) => {
             cx.editor
-                .set_error("No debug adapter available for language");
-            return;
+                .set_error(format!("Failed to read .vscode/launch.json: {err}"));
+            if templates.is_empty() {
+                return;
+            }
+            (LaunchJson::default(), false)
         }
     };
 
-    let templates = config.templates.clone();
+    let items: Vec<_> = templates
+        .into_iter()
+        .map(LaunchItem::Template)
+        .chain(
+            launch_json
+                .configurations
+                .iter()
+                .cloned()
+                .map(LaunchItem::LaunchJson),
+        )
+        .collect();
+    if items.is_empty() {
+        if has_launch_json {
+            cx.editor
+                .set_error("No launch configurations found in .vscode/launch.json");
+        } else {
+            cx.editor
+                .set_error("No debug adapter available for language");
+        }
+        return;
+    }
+    let launch_json = Arc::new(launch_json);
 
-    let columns = [ui::PickerColumn::new(
-        "template",
-        |item: &DebugTemplate, _| item.name.as_str().into(),
-    )];
+    let columns = [
+        ui::PickerColumn::new("template", |item: &LaunchItem, _| item.name().into()),
+        ui::PickerColumn::new("source", |item: &LaunchItem, _| match item {
+            LaunchItem::Template(_) => "languages.toml".into(),
+            LaunchItem::LaunchJson(_) => ".vscode/launch.json".into(),
+        }),
+    ];
 
     cx.push_layer(Box::new(overlaid(Picker::new(
         columns,
         0,
-        templates,
+        items,
         (),
-        |cx, template, _action| {
-            if template.completion.is_empty() {
-                if let Err(err) = dap_start_impl(cx, Some(&template.name), None, None) {
-                    cx.editor.set_error(err.to_string());
+        move |cx, item, _action| match item {
+            LaunchItem::Template(template) => {
+                if template.completion.is_empty() {
+                    if let Err(err) = dap_start_impl(cx, Some(&template.name), None, None) {
+                        cx.editor.set_error(err.to_string());
+                    }
+                } else {
+                    let completions = template.completion.clone();
+                    let name = template.name.clone();
+                    let callback = Box::pin(async move {
+                        let call: Callback =
+                            Callback::EditorCompositor(Box::new(move |_editor, compositor| {
+                                let input = debug_parameter_input(completions, name, Vec::new());
+                                compositor.push(input);
+                            }));
+                        Ok(call)
+                    });
+                    cx.jobs.callback(callback);
                 }
-            } else {
-                let completions = template.completion.clone();
-                let name = template.name.clone();
-                let callback = Box::pin(async move {
-                    let call: Callback =
-                        Callback::EditorCompositor(Box::new(move |_editor, compositor| {
-                            let prompt = debug_parameter_prompt(completions, name, Vec::new());
-                            compositor.push(Box::new(prompt));
-                        }));
-                    Ok(call)
-                });
-                cx.jobs.callback(callback);
+            }
+            LaunchItem::LaunchJson(configuration) => {
+                let inputs =
+                    launch_json::input_variables(&Value::Object(configuration.arguments.clone()));
+                dap_launch_json_configuration(
+                    cx,
+                    launch_json.clone(),
+                    configuration.clone(),
+                    inputs,
+                    HashMap::new(),
+                );
             }
         },
     ))));
 }
 
+/// Asks for the values of the `${input:<id>}` variables of a `.vscode/launch.json`
+/// configuration one after another, then starts it.
+fn dap_launch_json_configuration(
+    cx: &mut compositor::Context,
+    launch_json: Arc<LaunchJson>,
+    configuration: LaunchConfiguration,
+    mut inputs: Vec<String>,
+    values: HashMap<String, String>,
+) {
+    if inputs.is_empty() {
+        if let Err(err) = dap_start_launch_json(cx, configuration, &values) {
+            cx.editor.set_error(err.to_string());
+        }
+        return;
+    }
+
+    let id = inputs.remove(0);
+    let (description, default, options) = match launch_json.input(&id) {
+        Some(LaunchInput::PromptString {
+            description,
+            default,
+            ..
+        }) => (description.clone(), default.clone(), Vec::new()),
+        Some(LaunchInput::PickString {
+            description,
+            options,
+            default,
+            ..
+        }) => (
+            description.clone(),
+            default.clone(),
+            options
+                .iter()
+                .map(|option| option.value().to_string())
+                .collect(),
+        ),
+        Some(LaunchInput::Command { command, .. }) => {
+            cx.editor.set_error(format!(
+                "Input '{id}' uses the command '{command}', command inputs are not supported"
+            ));
+            return;
+        }
+        None => {
+            cx.editor.set_error(format!(
+                "Input '{id}' is not defined in .vscode/launch.json"
+            ));
+            return;
+        }
+    };
+
+    let callback = Box::pin(async move {
+        let call: Callback = Callback::EditorCompositor(Box::new(move |editor, compositor| {
+            let label = description.unwrap_or_else(|| id.clone());
+            let completer = move |_editor: &Editor, input: &str| {
+                options
+                    .iter()
+                    .filter(|option| option.starts_with(input))
+                    .map(|option| ((0..), option.clone().into()))
+                    .collect()
+            };
+            let mut prompt = Prompt::new(
+                format!("{label}: ").into(),
+                None,
+                completer,
+                move |cx, input: &str, event: PromptEvent| {
+                    if event != PromptEvent::Validate {
+                        return;
+                    }
+                    let mut values = values.clone();
+                    values.insert(id.clone(), input.to_owned());
+                    dap_launch_json_configuration(
+                        cx,
+                        launch_json.clone(),
+                        configuration.clone(),
+                        inputs.clone(),
+                        values,
+                    );
+                },
+            );
+            if let Some(default) = default {
+                prompt.insert_str(&default, editor);
+            }
+            compositor.push(Box::new(prompt));
+        }));
+        Ok(call)
+    });
+    cx.jobs.callback(callback);
+}
+
+/// Starts a `.vscode/launch.json` configuration with the debug adapter whose name matches its
+/// `type`.
+fn dap_start_launch_json(
+    cx: &mut compositor::Context,
+    configuration: LaunchConfiguration,
+    inputs: &HashMap<String, String>,
+) -> Result<(), anyhow::Error> {
+    let config = cx
+        .editor
+        .syn_loader
+        .load()
+        .language_configs()
+        .find_map(|language| {
+            language
+                .debugger
+                .as_ref()
+                .filter(|debugger| debugger.name == configuration.ty)
+        })
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "No debug adapter named '{}' is configured",
+                configuration.ty
+            )
+        })?;
+
+    let mut args = Value::Object(configuration.arguments);
+    launch_json::expand_variables(
+        &mut args,
+        &mut |variable| match variable.strip_prefix("input:") {
+            Some(id) => inputs
+                .get(id)
+                .cloned()
+                .ok_or_else(|| anyhow!("No value for input '{id}'")),
+            None => expansion::expand_vscode_variable(cx.editor, variable).map(Cow::into_owned),
+        },
+    )?;
+    if let Value::Object(args) = &mut args {
+        if !args.contains_key("cwd") {
+            args.insert(
+                "cwd".to_string(),
+                to_value(helix_stdx::env::current_working_dir())?,
+            );
+        }
+    }
+
+    dap_start_request(cx, &config, None, &configuration.request, args)
+}
+
 pub fn dap_restart(cx: &mut Context) {
     let debugger = match cx.editor.debug_adapters.get_active_client() {
         Some(debugger) => debugger,
@@ -313,10 +540,66 @@ pub fn dap_restart(cx: &mut Context) {
     );
 }
 
-fn debug_parameter_prompt(
+/// Asks for the next parameter of a debug template. Process IDs are picked from the running
+/// processes where they can be listed, other parameters are entered in a prompt.
+fn debug_parameter_input(
+    completions: Vec<DebugConfigCompletion>,
+    config_name: String,
+    params: Vec<String>,
+) -> Box<dyn Component> {
+    let completion = completions.get(params.len()).unwrap();
+    let is_pid = match completion {
+        DebugConfigCompletion::Advanced(cfg) => cfg.completion.as_deref() == Some("pid"),
+        DebugConfigCompletion::Named(name) => name == "pid",
+    };
+    if is_pid {
+        if let Some(processes) = processes().filter(|processes| !processes.is_empty()) {
+            return Box::new(overlaid(process_picker(
+                processes,
+                completions,
+                config_name,
+                params,
+            )));
+        }
+    }
+    Box::new(debug_parameter_prompt(completions, config_name, params))
+}
+
+/// Records the value of a debug template parameter, asking for the next one or starting the
+/// debugging session once all of them are known.
+fn debug_parameter_entered(
+    cx: &mut compositor::Context,
     completions: Vec<DebugConfigCompletion>,
     config_name: String,
     mut params: Vec<String>,
+    value: String,
+) {
+    params.push(value);
+
+    if params.len() < completions.len() {
+        let callback = Box::pin(async move {
+            let call: Callback =
+                Callback::EditorCompositor(Box::new(move |_editor, compositor| {
+                    let input = debug_parameter_input(completions, config_name, params);
+                    compositor.push(input);
+                }));
+            Ok(call)
+        });
+        cx.jobs.callback(callback);
+    } else if let Err(err) = dap_start_impl(
+        cx,
+        Some(&config_name),
+        None,
+        Some(params.iter().map(|x| x.into()).collect()),
+    ) {
+        cx.editor.set_error(err.to_string());
+    }
+}
+
+fn debug_parameter_prompt(
+    completions: Vec<DebugConfigCompletion>,
+    config_name: String,
+    params: Vec<String>,
 ) -> Prompt {
     let completion = completions.get(params.len()).unwrap();
     let field_type = if let DebugConfigCompletion::Advanced(cfg) = completion {
@@ -357,33 +640,98 @@ fn debug_parameter_prompt(
             if value.is_empty() {
                 value = default_val.clone();
             }
-            params.push(value);
-
-            if params.len() < completions.len() {
-                let completions = completions.clone();
-                let config_name = config_name.clone();
-                let params = params.clone();
-                let callback = Box::pin(async move {
-                    let call: Callback =
-                        Callback::EditorCompositor(Box::new(move |_editor, compositor| {
-                            let prompt = debug_parameter_prompt(completions, config_name, params);
-                            compositor.push(Box::new(prompt));
-                        }));
-                    Ok(call)
-                });
-                cx.jobs.callback(callback);
-            } else if let Err(err) = dap_start_impl(
+            debug_parameter_entered(
                 cx,
-                Some(&config_name),
-                None,
-                Some(params.iter().map(|x| x.into()).collect()),
-            ) {
-                cx.editor.set_error(err.to_string());
-            }
+                completions.clone(),
+                config_name.clone(),
+                params.clone(),
+                value,
+            );
         },
     )
 }
 
+/// A running process offered by the process picker of attach templates.
+struct ProcessItem {
+    pid: u32,
+    name: String,
+    command_line: String,
+}
+
+/// Lists the running processes, most recently started first. Returns `None` where processes
+/// cannot be listed.
+#[cfg(target_os = "linux")]
+fn processes() -> Option<Vec<ProcessItem>> {
+    let mut processes: Vec<_> = std::fs::read_dir("/proc")
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let path = entry.path();
+            // Arguments are separated by null bytes. Kernel threads have no command line and
+            // cannot be attached to.
+            let command_line = std::fs::read(path.join("cmdline")).ok()?;
+            let command_line = String::from_utf8_lossy(&command_line)
+                .split('\0')
+                .filter(|arg| !arg.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if command_line.is_empty() {
+                return None;
+            }
+            let name = std::fs::read_to_string(path.join("comm"))
+                .map(|name| name.trim_end().to_owned())
+                .unwrap_or_default();
+            // The start time is the 22nd field of the stat file, in clock ticks after boot. The
+            // 2nd field is the name in parentheses, which may contain spaces and parentheses.
+            let stat = std::fs::read_to_string(path.join("stat")).ok()?;
+            let (_, fields) = stat.rsplit_once(')')?;
+            let start_time: u64 = fields.split_whitespace().nth(19)?.parse().ok()?;
+            Some((
+                start_time,
+                ProcessItem {
+                    pid,
+                    name,
+                    command_line,
+                },
+            ))
+        })
+        .collect();
+    processes.sort_unstable_by(|(a_start, a), (b_start, b)| {
+        b_start.cmp(a_start).then(b.pid.cmp(&a.pid))
+    });
+    Some(processes.into_iter().map(|(_, process)| process).collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn processes() -> Option<Vec<ProcessItem>> {
+    None
+}
+
+fn process_picker(
+    processes: Vec<ProcessItem>,
+    completions: Vec<DebugConfigCompletion>,
+    config_name: String,
+    params: Vec<String>,
+) -> Picker<ProcessItem, ()> {
+    let columns = [
+        ui::PickerColumn::new("pid", |item: &ProcessItem, _| item.pid.to_string().into()),
+        ui::PickerColumn::new("name", |item: &ProcessItem, _| item.name.as_str().into()),
+        ui::PickerColumn::new("command", |item: &ProcessItem, _| {
+            item.command_line.as_str().into()
+        }),
+    ];
+    Picker::new(columns, 2, processes, (), move |cx, item, _action| {
+        debug_parameter_entered(
+            cx,
+            completions.clone(),
+            config_name.clone(),
+            params.clone(),
+            item.pid.to_string(),
+        );
+    })
+}
+
 pub fn dap_toggle_breakpoint(cx: &mut Context) {
     let (view, doc) = current!(cx.editor);
     let path = match doc.path() {
@@ -430,6 +778,7 @@ pub fn dap_continue(cx: &mut Context) {
 
     if let Some(thread_id) = debugger.thread_id {
         let request = debugger.continue_thread(thread_id);
+        clear_inline_values(cx.editor);
 
         dap_callback(
             cx.jobs,
@@ -460,6 +809,7 @@ pub fn dap_step_in(cx: &mut Context) {
 
     if let Some(thread_id) = debugger.thread_id {
         let request = debugger.step_in(thread_id);
+        clear_inline_values(cx.editor);
 
         dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
             debugger!(editor).resume_application();
@@ -475,6 +825,7 @@ pub fn dap_step_out(cx: &mut Context) {
 
     if let Some(thread_id) = debugger.thread_id {
         let request = debugger.step_out(thread_id);
+        clear_inline_values(cx.editor);
         dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
             debugger!(editor).resume_application();
         });
@@ -489,6 +840,38 @@ pub fn dap_next(cx: &mut Context) {
 
     if let Some(thread_id) = debugger.thread_id {
         let request = debugger.next(thread_id);
+        clear_inline_values(cx.editor);
+        dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
+            debugger!(editor).resume_application();
+        });
+    } else {
+        cx.editor
+            .set_error("Currently active thread is not stopped. Switch the thread.");
+    }
+}
+
+/// Steps by a single instruction with `step`, for debug adapters supporting stepping
+/// granularity.
+fn dap_step_instruction<F>(cx: &mut Context, step: impl FnOnce(&dap::Client, dap::ThreadId) -> F)
+where
+    F: Future<Output = helix_dap::Result<Value>> + 'static + Send,
+{
+    let debugger = debugger!(cx.editor);
+
+    let supported = debugger
+        .caps
+        .as_ref()
+        .and_then(|caps| caps.supports_stepping_granularity)
+        .unwrap_or(false);
+    if !supported {
+        cx.editor
+            .set_error("Debugger does not support stepping by instruction");
+        return;
+    }
+
+    if let Some(thread_id) = debugger.thread_id {
+        let request = step(debugger, thread_id);
+        clear_inline_values(cx.editor);
         dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
             debugger!(editor).resume_application();
         });
@@ -498,6 +881,21 @@ pub fn dap_next(cx: &mut Context) {
     }
 }
 
+pub fn dap_step_in_instruction(cx: &mut Context) {
+    dap_step_instruction(cx, dap::Client::step_in_instruction)
+}
+
+pub fn dap_next_instruction(cx: &mut Context) {
+    dap_step_instruction(cx, dap::Client::next_instruction)
+}
+
+pub fn dap_disassemble(cx: &mut Context) {
+    debugger!(cx.editor);
+
+    cx.editor.open_disassembly(Action::VerticalSplit);
+    crate::handlers::dap::refresh_disassembly(cx.editor);
+}
+
 pub fn dap_variables(cx: &mut Context) {
     let debugger = debugger!(cx.editor);
 
@@ -543,43 +941,18 @@ pub fn dap_variables(cx: &mut Context) {
     };
 
     // TODO: allow expanding variables into sub-fields
-    let mut variables = Vec::new();
-
-    let theme = &cx.editor.theme;
-    let scope_style = theme.get("ui.linenr.selected");
-    let type_style = theme.get("ui.text");
-    let text_style = theme.get("ui.text.focus");
-
-    for scope in scopes.iter() {
-        // use helix_view::graphics::Style;
-        use tui::text::Span;
-        let response = block_on(debugger.variables(scope.variables_reference));
-
-        variables.push(Spans::from(Span::styled(
-            format!("▸ {}", scope.name),
-            scope_style,
-        )));
-
-        if let Ok(vars) = response {
-            variables.reserve(vars.len());
-            for var in vars {
-                let mut spans = Vec::with_capacity(5);
-
-                spans.push(Span::styled(var.name.to_owned(), text_style));
-                if let Some(ty) = var.ty {
-                    spans.push(Span::raw(": "));
-                    spans.push(Span::styled(ty.to_owned(), type_style));
-                }
-                spans.push(Span::raw(" = "));
-                spans.push(Span::styled(var.value.to_owned(), text_style));
-                variables.push(Spans::from(spans));
-            }
-        }
-    }
-
-    let contents = Text::from(tui::text::Text::from(variables));
-    let popup = Popup::new("dap-variables", contents);
-    cx.replace_or_push_layer("dap-variables", popup);
+    let scopes = scopes
+        .into_iter()
+        .map(|scope| {
+            let variables =
+                block_on(debugger.variables(scope.variables_reference)).unwrap_or_default();
+            (scope, variables)
+        })
+        .collect();
+
+    let panel = ui::DebugVariablesPanel::new(frame_id, scopes);
+    let popup = Popup::new(ui::DebugVariablesPanel::ID, panel);
+    cx.replace_or_push_layer(ui::DebugVariablesPanel::ID, popup);
 }
 
 pub fn dap_terminate(cx: &mut Context) {
@@ -597,6 +970,161 @@ pub fn dap_terminate(cx: &mut Context) {
     });
 }
 
+pub fn dap_repl(cx: &mut Context) {
+    // Open the console next to the current view so that the results are visible.
+    let console_open = cx
+        .editor
+        .debug_console
+        .doc
+        .is_some_and(|doc_id| cx.editor.documents.contains_key(&doc_id));
+    if !console_open {
+        let view_id = view!(cx.editor).id;
+        let filter = cx.editor.debug_console.filter;
+        cx.editor.open_debug_console(Action::VerticalSplit, filter);
+        cx.editor.focus(view_id);
+    }
+
+    let prompt = Prompt::new(
+        "eval:".into(),
+        Some('>'),
+        debug_console_completions(),
+        move |cx, input: &str, event: PromptEvent| {
+            if event != PromptEvent::Validate || input.is_empty() {
+                return;
+            }
+            debug_console_eval(cx, input);
+        },
+    );
+    cx.push_layer(Box::new(prompt));
+}
+
+/// Evaluates `expression` in the current stack frame, recording it and its result in the debug
+/// console.
+pub fn debug_console_eval(cx: &mut compositor::Context, expression: &str) {
+    let Some(debugger) = cx.editor.debug_adapters.get_active_client() else {
+        cx.editor.set_error("No active debug session");
+        return;
+    };
+    let frame_id = debugger.current_stack_frame().map(|frame| frame.id);
+    let future = debugger.eval_in_context(expression.to_string(), frame_id, Some("repl"));
+    cx.editor
+        .debug_console_push(ConsoleCategory::Input, expression.to_string());
+
+    cx.jobs.callback(async move {
+        let result = match future.await {
+            Ok(response) => response.result,
+            Err(err) => format!("Error: {err}"),
+        };
+        let call: Callback = Callback::Editor(Box::new(move |editor: &mut Editor| {
+            editor.debug_console_push(ConsoleCategory::Result, result)
+        }));
+        Ok(call)
+    });
+}
+
+/// Completes the debug console input with the completions of the active debug adapter.
+///
+/// The adapter is asked in the background, the prompt is updated once the completions for its
+/// input arrived. The completions are kept per input for as long as the prompt is open.
+fn debug_console_completions() -> impl FnMut(&Editor, &str) -> Vec<ui::prompt::Completion> {
+    let completions: Arc<Mutex<HashMap<String, Vec<ui::prompt::Completion>>>> = Arc::default();
+    let mut requested = HashSet::new();
+
+    move |editor: &Editor, input: &str| {
+        if let Some(completions) = completions.lock().unwrap().get(input) {
+            return completions.clone();
+        }
+        let Some(debugger) = editor.debug_adapters.get_active_client() else {
+            return Vec::new();
+        };
+        let supported = debugger
+            .caps
+            .as_ref()
+            .and_then(|caps| caps.supports_completions_request)
+            .unwrap_or(false);
+        if !supported || input.is_empty() || !requested.insert(input.to_string()) {
+            return Vec::new();
+        }
+
+        let frame_id = debugger.current_stack_frame().map(|frame| frame.id);
+        // The column is measured in UTF-16 code units and starts at one.
+        let column = input.encode_utf16().count() + 1;
+        let request = debugger.completions(input.to_string(), column, frame_id);
+        let completions = completions.clone();
+        let input = input.to_string();
+        tokio::spawn(async move {
+            let targets = match request.await {
+                Ok(targets) => targets,
+                Err(err) => {
+                    log::debug!("debug console completion request failed: {err}");
+                    return;
+                }
+            };
+            completions
+                .lock()
+                .unwrap()
+                .insert(input.clone(), console_completions(&input, targets));
+            job::dispatch(move |editor, compositor| {
+                if let Some(prompt) = compositor.find::<Prompt>() {
+                    if *prompt.line() == input {
+                        prompt.recalculate_completion(editor);
+                    }
+                }
+            })
+            .await;
+        });
+        Vec::new()
+    }
+}
+
+fn console_completions(
+    input: &str,
+    mut targets: Vec<dap::CompletionItem>,
+) -> Vec<ui::prompt::Completion> {
+    targets.sort_by(|a, b| {
+        let a = a.sort_text.as_ref().unwrap_or(&a.label);
+        let b = b.sort_text.as_ref().unwrap_or(&b.label);
+        a.cmp(b)
+    });
+
+    // Without an explicit start the completion replaces the word before the cursor.
+    let word_start = input
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| ch.is_alphanumeric() || *ch == '_')
+        .last()
+        .map_or(input.len(), |(idx, _)| idx);
+
+    targets
+        .into_iter()
+        .map(|target| {
+            let start = target
+                .start
+                .and_then(|start| utf16_to_byte_idx(input, start.saturating_sub(1)))
+                .unwrap_or(word_start);
+            let text = target.text.unwrap_or(target.label);
+            ((start..), tui::text::Span::raw(text))
+        })
+        .collect()
+}
+
+fn utf16_to_byte_idx(text: &str, utf16_idx: usize) -> Option<usize> {
+    let mut utf16_len = 0;
+    for (byte_idx, ch) in text.char_indices() {
+        if utf16_len >= utf16_idx {
+            return (utf16_len == utf16_idx).then_some(byte_idx);
+        }
+        utf16_len += ch.len_utf16();
+    }
+    (utf16_len == utf16_idx).then_some(text.len())
+}
+
+pub fn dap_watches(cx: &mut Context) {
+    let panel = ui::DebugWatchPanel::new(cx.editor);
+    let popup = Popup::new(ui::DebugWatchPanel::ID, panel);
+    cx.replace_or_push_layer(ui::DebugWatchPanel::ID, popup);
+}
+
 pub fn dap_enable_exceptions(cx: &mut Context) {
     let debugger = debugger!(cx.editor);
 
@@ -632,7 +1160,26 @@ pub fn dap_disable_exceptions(cx: &mut Context) {
 
 // TODO: both edit condition and edit log need to be stable: we might get new breakpoints from the debugger which can change offsets
 pub fn dap_edit_condition(cx: &mut Context) {
-    if let Some((pos, breakpoint)) = get_breakpoint_at_current_line(cx.editor) {
+    edit_breakpoint_field(cx, "condition:", |breakpoint| &mut breakpoint.condition);
+}
+
+pub fn dap_edit_hit_condition(cx: &mut Context) {
+    edit_breakpoint_field(cx, "hit-condition:", |breakpoint| {
+        &mut breakpoint.hit_condition
+    });
+}
+
+pub fn dap_edit_log(cx: &mut Context) {
+    edit_breakpoint_field(cx, "log-message:", |breakpoint| &mut breakpoint.log_message);
+}
+
+/// Prompts for the `field` of the breakpoint at the current line, an empty input clears it.
+fn edit_breakpoint_field(
+    cx: &mut Context,
+    prompt: &'static str,
+    field: fn(&mut Breakpoint) -> &mut Option<String>,
+) {
+    if let Some((pos, mut breakpoint)) = get_breakpoint_at_current_line(cx.editor) {
         let path = match doc!(cx.editor).path() {
             Some(path) => path.clone(),
             None => return,
@@ -640,7 +1187,7 @@ pub fn dap_edit_condition(cx: &mut Context) {
         let callback = Box::pin(async move {
             let call: Callback = Callback::EditorCompositor(Box::new(move |editor, compositor| {
                 let mut prompt = Prompt::new(
-                    "condition:".into(),
+                    prompt.into(),
                     None,
                     ui::completers::none,
                     move |cx, input: &str, event: PromptEvent| {
@@ -649,21 +1196,20 @@ pub fn dap_edit_condition(cx: &mut Context) {
                         }
 
                         let breakpoints = &mut cx.editor.breakpoints.get_mut(&path).unwrap();
-                        breakpoints[pos].condition = match input {
+                        *field(&mut breakpoints[pos]) = match input {
                             "" => None,
                             input => Some(input.to_owned()),
                         };
 
                         let debugger = debugger!(cx.editor);
-
                         if let Err(e) = breakpoints_changed(debugger, path.clone(), breakpoints) {
                             cx.editor
                                 .set_error(format!("Failed to set breakpoints: {}", e));
                         }
                     },
                 );
-                if let Some(condition) = breakpoint.condition {
-                    prompt.insert_str(&condition, editor)
+                if let Some(value) = field(&mut breakpoint) {
+                    prompt.insert_str(value, editor);
                 }
                 compositor.push(Box::new(prompt));
             }));
@@ -673,50 +1219,206 @@ pub fn dap_edit_condition(cx: &mut Context) {
     }
 }
 
-pub fn dap_edit_log(cx: &mut Context) {
-    if let Some((pos, breakpoint)) = get_breakpoint_at_current_line(cx.editor) {
-        let path = match doc!(cx.editor).path() {
-            Some(path) => path.clone(),
-            None => return,
-        };
-        let callback = Box::pin(async move {
-            let call: Callback = Callback::EditorCompositor(Box::new(move |editor, compositor| {
-                let mut prompt = Prompt::new(
-                    "log-message:".into(),
-                    None,
-                    ui::completers::none,
-                    move |cx, input: &str, event: PromptEvent| {
-                        if event != PromptEvent::Validate {
-                            return;
-                        }
+/// Identifies a breakpoint of any kind in the breakpoint picker.
+#[derive(Clone, PartialEq, Eq)]
+enum BreakpointKind {
+    Source { path: PathBuf, line: usize },
+    Function(String),
+    Data(String),
+    Instruction(String, Option<i64>),
+}
 
-                        let breakpoints = &mut cx.editor.breakpoints.get_mut(&path).unwrap();
-                        breakpoints[pos].log_message = match input {
-                            "" => None,
-                            input => Some(input.to_owned()),
-                        };
+struct BreakpointItem {
+    kind: BreakpointKind,
+    location: String,
+    condition: Option<String>,
+    hit_condition: Option<String>,
+    enabled: bool,
+    verified: bool,
+}
 
-                        let debugger = debugger!(cx.editor);
-                        if let Err(e) = breakpoints_changed(debugger, path.clone(), breakpoints) {
-                            cx.editor
-                                .set_error(format!("Failed to set breakpoints: {}", e));
-                        }
-                    },
-                );
-                if let Some(log_message) = breakpoint.log_message {
-                    prompt.insert_str(&log_message, editor);
-                }
-                compositor.push(Box::new(prompt));
-            }));
-            Ok(call)
-        });
-        cx.jobs.callback(callback);
+impl BreakpointItem {
+    fn kind_name(&self) -> &'static str {
+        match self.kind {
+            BreakpointKind::Source { .. } => "source",
+            BreakpointKind::Function(_) => "function",
+            BreakpointKind::Data(_) => "data",
+            BreakpointKind::Instruction(..) => "instruction",
+        }
+    }
+
+    fn state(&self) -> &'static str {
+        match (self.enabled, self.verified) {
+            (false, _) => "disabled",
+            (true, true) => "verified",
+            (true, false) => "unverified",
+        }
     }
 }
 
+fn breakpoint_items(editor: &Editor) -> Vec<BreakpointItem> {
+    let mut items = Vec::new();
+    let mut paths: Vec<_> = editor.breakpoints.keys().collect();
+    paths.sort();
+    for path in paths {
+        for breakpoint in &editor.breakpoints[path] {
+            items.push(BreakpointItem {
+                kind: BreakpointKind::Source {
+                    path: path.clone(),
+                    line: breakpoint.line,
+                },
+                location: format!(
+                    "{}:{}",
+                    helix_stdx::path::get_relative_path(path).display(),
+                    breakpoint.line + 1
+                ),
+                condition: breakpoint.condition.clone(),
+                hit_condition: breakpoint.hit_condition.clone(),
+                enabled: breakpoint.enabled,
+                verified: breakpoint.verified,
+            });
+        }
+    }
+    items.extend(
+        editor
+            .function_breakpoints
+            .iter()
+            .map(|breakpoint| BreakpointItem {
+                kind: BreakpointKind::Function(breakpoint.name.clone()),
+                location: breakpoint.name.clone(),
+                condition: breakpoint.condition.clone(),
+                hit_condition: breakpoint.hit_condition.clone(),
+                enabled: breakpoint.enabled,
+                verified: breakpoint.verified,
+            }),
+    );
+    items.extend(
+        editor
+            .data_breakpoints
+            .iter()
+            .map(|breakpoint| BreakpointItem {
+                kind: BreakpointKind::Data(breakpoint.data_id.clone()),
+                location: breakpoint.description.clone(),
+                condition: breakpoint.condition.clone(),
+                hit_condition: breakpoint.hit_condition.clone(),
+                enabled: breakpoint.enabled,
+                verified: breakpoint.verified,
+            }),
+    );
+    items.extend(
+        editor
+            .instruction_breakpoints
+            .iter()
+            .map(|breakpoint| BreakpointItem {
+                kind: BreakpointKind::Instruction(
+                    breakpoint.instruction_reference.clone(),
+                    breakpoint.offset,
+                ),
+                location: match breakpoint.offset {
+                    Some(offset) => format!("{}{:+}", breakpoint.instruction_reference, offset),
+                    None => breakpoint.instruction_reference.clone(),
+                },
+                condition: breakpoint.condition.clone(),
+                hit_condition: breakpoint.hit_condition.clone(),
+                enabled: breakpoint.enabled,
+                verified: breakpoint.verified,
+            }),
+    );
+    items
+}
+
+/// Enables or disables a breakpoint and updates the breakpoints of the active debug session.
+fn toggle_breakpoint_enabled(editor: &mut Editor, kind: &BreakpointKind) -> anyhow::Result<()> {
+    let debugger = editor.debug_adapters.get_active_client_mut();
+    match kind {
+        BreakpointKind::Source { path, line } => {
+            let Some(breakpoints) = editor.breakpoints.get_mut(path) else {
+                return Ok(());
+            };
+            if let Some(breakpoint) = breakpoints.iter_mut().find(|b| b.line == *line) {
+                breakpoint.enabled = !breakpoint.enabled;
+            }
+            if let Some(debugger) = debugger {
+                breakpoints_changed(debugger, path.clone(), breakpoints)?;
+            }
+        }
+        BreakpointKind::Function(name) => {
+            let breakpoints = &mut editor.function_breakpoints;
+            if let Some(breakpoint) = breakpoints.iter_mut().find(|b| &b.name == name) {
+                breakpoint.enabled = !breakpoint.enabled;
+            }
+            if let Some(debugger) = debugger {
+                function_breakpoints_changed(debugger, breakpoints)?;
+            }
+        }
+        BreakpointKind::Data(data_id) => {
+            let breakpoints = &mut editor.data_breakpoints;
+            if let Some(breakpoint) = breakpoints.iter_mut().find(|b| &b.data_id == data_id) {
+                breakpoint.enabled = !breakpoint.enabled;
+            }
+            if let Some(debugger) = debugger {
+                data_breakpoints_changed(debugger, breakpoints)?;
+            }
+        }
+        BreakpointKind::Instruction(reference, offset) => {
+            let breakpoints = &mut editor.instruction_breakpoints;
+            if let Some(breakpoint) = breakpoints
+                .iter_mut()
+                .find(|b| &b.instruction_reference == reference && b.offset == *offset)
+            {
+                breakpoint.enabled = !breakpoint.enabled;
+            }
+            if let Some(debugger) = debugger {
+                instruction_breakpoints_changed(debugger, breakpoints)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn dap_breakpoints(cx: &mut Context) {
+    let items = breakpoint_items(cx.editor);
+    if items.is_empty() {
+        cx.editor.set_status("No breakpoints");
+        return;
+    }
+
+    let columns = [
+        ui::PickerColumn::new("kind", |item: &BreakpointItem, _| item.kind_name().into()),
+        ui::PickerColumn::new("location", |item: &BreakpointItem, _| {
+            item.location.as_str().into()
+        }),
+        ui::PickerColumn::new("condition", |item: &BreakpointItem, _| {
+            item.condition.as_deref().unwrap_or_default().into()
+        }),
+        ui::PickerColumn::new("hits", |item: &BreakpointItem, _| {
+            item.hit_condition.as_deref().unwrap_or_default().into()
+        }),
+        ui::PickerColumn::new("state", |item: &BreakpointItem, _| item.state().into()),
+    ];
+    let picker = Picker::new(columns, 1, items, (), |cx, item, _action| {
+        if let Err(err) = toggle_breakpoint_enabled(cx.editor, &item.kind) {
+            cx.editor
+                .set_error(format!("Failed to set breakpoints: {err}"));
+            return;
+        }
+        let state = if item.enabled { "disabled" } else { "enabled" };
+        cx.editor
+            .set_status(format!("Breakpoint {} {state}", item.location));
+    })
+    .with_preview(|_editor, item| match &item.kind {
+        BreakpointKind::Source { path, line } => {
+            Some((path.as_path().into(), Some((*line, *line))))
+        }
+        _ => None,
+    });
+    cx.push_layer(Box::new(overlaid(picker)));
+}
+
 pub fn dap_switch_thread(cx: &mut Context) {
     thread_picker(cx, |editor, thread| {
         block_on(select_thread_id(editor, thread.id, true));
+        helix_event::dispatch(DebugFrameDidChange { editor });
     })
 }
 pub fn dap_switch_stack_frame(cx: &mut Context) {
@@ -749,6 +1451,7 @@ pub fn dap_switch_stack_frame(cx: &mut Context) {
         if let Some(frame) = &frame {
             jump_to_stack_frame(cx.editor, frame);
         }
+        helix_event::dispatch(DebugFrameDidChange { editor: cx.editor });
     })
     .with_preview(move |_editor, frame| {
         frame
@@ -767,3 +1470,90 @@ pub fn dap_switch_stack_frame(cx: &mut Context) {
     });
     cx.push_layer(Box::new(picker))
 }
+
+/// A debugging session offered by `dap_switch_session`.
+struct SessionItem {
+    id: DebugAdapterId,
+    /// Number of parents of the session, used to indent child sessions.
+    depth: usize,
+    name: String,
+    adapter: String,
+    stopped: bool,
+}
+
+fn session_items(
+    registry: &dap::registry::Registry,
+    parent: Option<DebugAdapterId>,
+    depth: usize,
+    items: &mut Vec<SessionItem>,
+) {
+    for client in registry.iter().filter(|client| client.parent == parent) {
+        let adapter = client
+            .config
+            .as_ref()
+            .map(|config| config.name.clone())
+            .unwrap_or_default();
+        let name = client
+            .starting_request_args()
+            .and_then(|args| args.get("name"))
+            .and_then(Value::as_str)
+            .map_or_else(|| adapter.clone(), str::to_owned);
+        items.push(SessionItem {
+            id: client.id(),
+            depth,
+            name,
+            adapter,
+            stopped: client
+                .thread_states
+                .values()
+                .any(|state| state == "stopped"),
+        });
+        session_items(registry, Some(client.id()), depth + 1, items);
+    }
+}
+
+pub fn dap_switch_session(cx: &mut Context) {
+    let mut sessions = Vec::new();
+    session_items(&cx.editor.debug_adapters, None, 0, &mut sessions);
+    if sessions.is_empty() {
+        cx.editor.set_error("No debugging session is running");
+        return;
+    }
+
+    let active = cx.editor.debug_adapters.active_client_id();
+    let columns = [
+        ui::PickerColumn::new(
+            "session",
+            |item: &SessionItem, active: &Option<DebugAdapterId>| {
+                let marker = if Some(item.id) == *active { "*" } else { " " };
+                format!("{marker}{}{}", "  ".repeat(item.depth), item.name).into()
+            },
+        ),
+        ui::PickerColumn::new("adapter", |item: &SessionItem, _| {
+            item.adapter.as_str().into()
+        }),
+        ui::PickerColumn::new("state", |item: &SessionItem, _| {
+            if item.stopped { "stopped" } else { "running" }.into()
+        }),
+    ];
+    let picker = Picker::new(columns, 0, sessions, active, |cx, session, _action| {
+        cx.editor.debug_adapters.set_active_client(session.id);
+        if let Some(frame) = cx.editor.current_stack_frame().cloned() {
+            jump_to_stack_frame(cx.editor, &frame);
+        }
+        helix_event::dispatch(DebugFrameDidChange { editor: cx.editor });
+    })
+    .with_preview(|editor, session| {
+        let frame = editor
+            .debug_adapters
+            .get_client(session.id)?
+            .current_stack_frame()?;
+        let path = frame.source.as_ref()?.path.as_ref()?.as_path();
+        let pos = Some((
+            frame.line.saturating_sub(1),
+            frame.end_line.unwrap_or(frame.line).saturating_sub(1),
+        ));
+        Some((path.into(), pos))
+    });
+    cx.push_layer(Box::new(picker))
+}
@@ -1,4 +1,4 @@
-use futures_util::{stream::FuturesOrdered, FutureExt};
+use futures_util::{future, stream::FuturesOrdered, FutureExt};
 use helix_lsp::{
     block_on,
     lsp::{
@@ -23,16 +23,22 @@
     editor::Action,
     handlers::lsp::SignatureHelpInvoked,
     theme::Style,
-    Document, View,
+    Document, DocumentId, View,
 };
 
 use crate::{
     compositor::{self, Compositor},
-    job::Callback,
+    job::{Callback, Job, Jobs},
     ui::{self, overlay::overlaid, FileLocation, Picker, Popup, PromptEvent},
 };
 
-use std::{cmp::Ordering, collections::HashSet, fmt::Display, future::Future, path::Path};
+use std::{
+    cmp::Ordering,
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+    future::Future,
+    path::Path,
+};
 
 /// Gets the first language server that is attached to a document which supports a specific feature.
 /// If there is no configured language server that supports the feature, this displays a status message.
@@ -825,6 +831,161 @@ pub fn code_action(cx: &mut Context) {
     });
 }
 
+/// Applies the code actions configured with `code-actions-on-save` to the whole document and
+/// then calls `write`.
+///
+/// Each kind is requested from all language servers that support code actions at once, and the
+/// first matching action of each server is applied once all of them responded. The kinds are
+/// requested one after another so that every action sees the edits of the previous ones.
+/// Actions without edits are skipped: their commands would only edit the document after it
+/// was written.
+pub fn code_actions_on_save(
+    editor: &mut Editor,
+    jobs: &Jobs,
+    doc_id: DocumentId,
+    write: impl FnOnce(&mut Editor, &Jobs) + Send + 'static,
+) {
+    let kinds = editor
+        .documents
+        .get(&doc_id)
+        .and_then(|doc| doc.language_config())
+        .map(|config| {
+            config
+                .code_actions_on_save
+                .iter()
+                .cloned()
+                .map(lsp::CodeActionKind::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    request_code_actions_on_save(editor, jobs, doc_id, kinds, Box::new(write));
+}
+
+type WriteCallback = Box<dyn FnOnce(&mut Editor, &Jobs) + Send>;
+
+fn request_code_actions_on_save(
+    editor: &mut Editor,
+    jobs: &Jobs,
+    doc_id: DocumentId,
+    mut kinds: VecDeque<lsp::CodeActionKind>,
+    write: WriteCallback,
+) {
+    let Some(doc) = editor.documents.get(&doc_id) else {
+        return;
+    };
+    let Some(kind) = kinds.pop_front() else {
+        write(editor, jobs);
+        return;
+    };
+
+    let mut seen_language_servers = HashSet::new();
+    let futures: Vec<_> = doc
+        .language_servers_with_feature(LanguageServerFeature::CodeAction)
+        .filter(|ls| seen_language_servers.insert(ls.id()))
+        .filter_map(|language_server| {
+            let language_server = editor.language_servers.get_by_id(language_server.id())?;
+            let offset_encoding = language_server.offset_encoding();
+            let text = doc.text();
+            let range = range_to_lsp_range(
+                text,
+                helix_core::Range::new(0, text.len_chars()),
+                offset_encoding,
+            );
+            let code_action_context = lsp::CodeActionContext {
+                diagnostics: doc
+                    .diagnostics()
+                    .iter()
+                    .filter(|diag| diag.provider.language_server_id() == Some(language_server.id()))
+                    .map(|diag| diagnostic_to_lsp_diagnostic(text, diag, offset_encoding))
+                    .collect(),
+                only: Some(vec![kind.clone()]),
+                trigger_kind: Some(CodeActionTriggerKind::AUTOMATIC),
+            };
+            let request =
+                language_server.code_actions(doc.identifier(), range, code_action_context)?;
+            let language_server = language_server.clone();
+            let kind = kind.clone();
+            Some(async move {
+                let actions = match request.await {
+                    Ok(actions) => actions.unwrap_or_default(),
+                    Err(err) => {
+                        log::error!(
+                            "failed to request '{}' code actions from {}: {err}",
+                            kind.as_str(),
+                            language_server.name()
+                        );
+                        return None;
+                    }
+                };
+                // Servers may ignore `only` so make sure the action is of the requested kind or
+                // one of its sub-kinds, for example `source.fixAll.eslint` for `source.fixAll`.
+                let code_action = actions
+                    .into_iter()
+                    .filter_map(|action| match action {
+                        CodeActionOrCommand::CodeAction(
+                            action @ CodeAction { disabled: None, .. },
+                        ) => Some(action),
+                        _ => None,
+                    })
+                    .filter(|action| {
+                        action.kind.as_ref().is_some_and(|action_kind| {
+                            let action_kind = action_kind.as_str();
+                            action_kind == kind.as_str()
+                                || action_kind
+                                    .strip_prefix(kind.as_str())
+                                    .is_some_and(|rest| rest.starts_with('.'))
+                        })
+                    })
+                    // the first preferred action or the first action if none is preferred
+                    .min_by_key(|action| action.is_preferred != Some(true))?;
+
+                let code_action = match code_action.edit {
+                    Some(_) => code_action,
+                    None => match language_server.resolve_code_action(&code_action) {
+                        Some(future) => future.await.ok()?,
+                        None => code_action,
+                    },
+                };
+                let edit = code_action.edit?;
+                Some((edit, offset_encoding))
+            })
+        })
+        .collect();
+
+    let doc_version = doc.version();
+    let callback = async move {
+        let edits = future::join_all(futures).await;
+        let call = move |editor: &mut Editor, jobs: &Jobs| {
+            for (edit, offset_encoding) in edits.into_iter().flatten() {
+                // the edits are based on the text at the time of the request so only the first
+                // edit applies when several servers respond
+                if editor
+                    .documents
+                    .get(&doc_id)
+                    .is_none_or(|doc| doc.version() != doc_version)
+                {
+                    log::info!(
+                        "discarded '{}' code action on save because the document changed",
+                        kind.as_str()
+                    );
+                    break;
+                }
+                log::debug!("code action on save: {:?}", edit);
+                if let Err(err) = editor.apply_workspace_edit(offset_encoding, &edit) {
+                    log::error!(
+                        "failed to apply '{}' code action on save: {}",
+                        kind.as_str(),
+                        err.kind
+                    );
+                }
+            }
+            request_code_actions_on_save(editor, jobs, doc_id, kinds, write);
+        };
+        Ok(Callback::EditorJobs(Box::new(call)))
+    };
+    jobs.add(Job::with_callback(callback).wait_before_exiting());
+}
+
 #[derive(Debug)]
 pub struct ApplyEditError {
     pub kind: ApplyEditErrorKind,
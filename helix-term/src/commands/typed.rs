@@ -11,8 +11,12 @@
 use helix_core::indent::MAX_INDENT;
 use helix_core::line_ending;
 use helix_stdx::path::home_dir;
-use helix_view::document::{read_to_string, DEFAULT_LANGUAGE_NAME};
-use helix_view::editor::{CloseError, ConfigEvent};
+use helix_vcs::Resolution;
+use helix_view::document::{from_reader, read_to_string, LineBlame, DEFAULT_LANGUAGE_NAME};
+use helix_view::editor::{
+    CloseError, ConfigEvent, ConsoleCategory, DebugWatch, DiffSplit, FunctionBreakpoint,
+    InstructionBreakpoint,
+};
 use helix_view::expansion;
 use serde_json::Value;
 use ui::completers::{self, Completer};
@@ -76,7 +80,7 @@ fn quit(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow
     }
 
     // last view and we have unsaved changes
-    if cx.editor.tree.views().count() == 1 {
+    if cx.editor.is_last_view(view!(cx.editor).id) {
         buffers_remaining_impl(cx.editor)?
     }
 
@@ -342,32 +346,66 @@ fn write_impl(
     path: Option<&str>,
     options: WriteOptions,
 ) -> anyhow::Result<()> {
-    let config = cx.editor.config();
-    let jobs = &mut cx.jobs;
-    let (view, doc) = current!(cx.editor);
+    let (view, doc) = current_ref!(cx.editor);
+    let (doc_id, view_id) = (doc.id(), view.id);
+    let path = path.map(PathBuf::from);
+
+    if options.auto_format && has_code_actions_on_save(doc) {
+        code_actions_on_save(cx.editor, cx.jobs, doc_id, move |editor, jobs| {
+            if let Err(err) = write_document(editor, jobs, doc_id, view_id, path, options) {
+                editor.set_error(format!("Error saving: {}", err));
+            }
+        });
+        return Ok(());
+    }
+
+    write_document(cx.editor, cx.jobs, doc_id, view_id, path, options)
+}
+
+fn has_code_actions_on_save(doc: &Document) -> bool {
+    doc.language_config()
+        .is_some_and(|config| !config.code_actions_on_save.is_empty())
+}
+
+/// Cleans up, formats and writes a document. The document is written once formatting
+/// finished if it is auto formatted.
+fn write_document(
+    editor: &mut Editor,
+    jobs: &Jobs,
+    doc_id: DocumentId,
+    view_id: ViewId,
+    path: Option<PathBuf>,
+    options: WriteOptions,
+) -> anyhow::Result<()> {
+    if !editor.documents.contains_key(&doc_id) || !editor.tree.contains(view_id) {
+        return Ok(());
+    }
+    let config = editor.config();
+    let doc = doc_mut!(editor, &doc_id);
+    let view = view_mut!(editor, view_id);
 
     if doc.trim_trailing_whitespace() {
-        trim_trailing_whitespace(doc, view.id);
+        trim_trailing_whitespace(doc, view_id);
     }
     if config.trim_final_newlines {
-        trim_final_newlines(doc, view.id);
+        trim_final_newlines(doc, view_id);
     }
     if doc.insert_final_newline() {
-        insert_final_newline(doc, view.id);
+        insert_final_newline(doc, view_id);
     }
 
     // Save an undo checkpoint for any outstanding changes.
     doc.append_changes_to_history(view);
 
-    let (view, doc) = current_ref!(cx.editor);
+    let doc = doc!(editor, &doc_id);
     let fmt = if config.auto_format && options.auto_format {
-        doc.auto_format(cx.editor).map(|fmt| {
+        doc.auto_format(editor).map(|fmt| {
             let callback = make_format_callback(
-                doc.id(),
+                doc_id,
                 doc.version(),
-                view.id,
+                view_id,
                 fmt,
-                Some((path.map(Into::into), options.force)),
+                Some((path.clone(), options.force)),
             );
 
             jobs.add(Job::with_callback(callback).wait_before_exiting());
@@ -377,8 +415,7 @@ fn write_impl(
     };
 
     if fmt.is_none() {
-        let id = doc.id();
-        cx.editor.save(id, path, options.force)?;
+        editor.save(doc_id, path, options.force)?;
     }
 
     Ok(())
@@ -776,8 +813,6 @@ pub fn write_all_impl(
     options: WriteAllOptions,
 ) -> anyhow::Result<()> {
     let mut errors: Vec<&'static str> = Vec::new();
-    let config = cx.editor.config();
-    let jobs = &mut cx.jobs;
     let saves: Vec<_> = cx
         .editor
         .documents
@@ -803,41 +838,20 @@ pub fn write_all_impl(
         })
         .collect();
 
+    let write_options = WriteOptions {
+        force: options.force,
+        auto_format: options.auto_format,
+    };
     for (doc_id, target_view) in saves {
-        let doc = doc_mut!(cx.editor, &doc_id);
-        let view = view_mut!(cx.editor, target_view);
-
-        if doc.trim_trailing_whitespace() {
-            trim_trailing_whitespace(doc, target_view);
-        }
-        if config.trim_final_newlines {
-            trim_final_newlines(doc, target_view);
-        }
-        if doc.insert_final_newline() {
-            insert_final_newline(doc, target_view);
-        }
-
-        // Save an undo checkpoint for any outstanding changes.
-        doc.append_changes_to_history(view);
-
-        let fmt = if options.auto_format && config.auto_format {
-            let doc = doc!(cx.editor, &doc_id);
-            doc.auto_format(cx.editor).map(|fmt| {
-                let callback = make_format_callback(
-                    doc_id,
-                    doc.version(),
-                    target_view,
-                    fmt,
-                    Some((None, options.force)),
-                );
-                jobs.add(Job::with_callback(callback).wait_before_exiting());
-            })
+        if options.auto_format && has_code_actions_on_save(doc!(cx.editor, &doc_id)) {
+            code_actions_on_save(cx.editor, cx.jobs, doc_id, move |editor, jobs| {
+                let result = write_document(editor, jobs, doc_id, target_view, None, write_options);
+                if let Err(err) = result {
+                    editor.set_error(format!("Error saving: {}", err));
+                }
+            });
         } else {
-            None
-        };
-
-        if fmt.is_none() {
-            cx.editor.save::<PathBuf>(doc_id, None, options.force)?;
+            write_document(cx.editor, cx.jobs, doc_id, target_view, None, write_options)?;
         }
     }
 
@@ -1709,6 +1723,348 @@ fn lsp_stop(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> any
     Ok(())
 }
 
+fn workspace_folder_uri(path: &str) -> anyhow::Result<helix_lsp::Url> {
+    let path = helix_stdx::path::canonicalize(path);
+    helix_lsp::Url::from_file_path(&path)
+        .map_err(|_| anyhow!("Invalid workspace folder '{}'", path.display()))
+}
+
+fn lsp_workspace_add(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let path = helix_stdx::path::canonicalize(&args[0]);
+    ensure!(path.is_dir(), "'{}' is not a directory", path.display());
+    let uri = workspace_folder_uri(&args[0])?;
+
+    let unsupported = cx.editor.language_servers.add_workspace_folder(uri);
+    if unsupported.is_empty() {
+        cx.editor
+            .set_status(format!("Added workspace folder '{}'", path.display()));
+    } else {
+        cx.editor.set_warning(format!(
+            "Added workspace folder '{}', not supported by: {}",
+            path.display(),
+            unsupported.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+fn lsp_workspace_remove(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let uri = workspace_folder_uri(&args[0])?;
+    ensure!(
+        cx.editor.language_servers.remove_workspace_folder(&uri),
+        "'{}' is not a workspace folder",
+        &args[0]
+    );
+    cx.editor
+        .set_status(format!("Removed workspace folder '{}'", &args[0]));
+
+    Ok(())
+}
+
+fn lsp_workspace_list(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    fn display(uri: &helix_lsp::Url) -> String {
+        uri.to_file_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| uri.to_string())
+    }
+
+    let mut contents = String::new();
+    let session_folders = cx.editor.language_servers.workspace_folders();
+    if !session_folders.is_empty() {
+        contents.push_str("**Added in this session**\n\n");
+        for uri in &session_folders {
+            writeln!(contents, "- `{}`", display(uri))?;
+        }
+        contents.push('\n');
+    }
+    for language_server in cx.editor.language_servers.iter_clients() {
+        writeln!(contents, "**{}**\n", language_server.name())?;
+        for workspace in language_server.workspace_folders().iter() {
+            writeln!(contents, "- `{}`", display(&workspace.uri))?;
+        }
+        contents.push('\n');
+    }
+    ensure!(!contents.is_empty(), "No language servers are running");
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                let contents = ui::Markdown::new(contents, editor.syn_loader.clone());
+                let popup = Popup::new("hover", contents).auto_close(true);
+                compositor.replace_or_push("hover", popup);
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn lsp_info(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let doc = doc!(cx.editor);
+    let language_servers: Vec<_> = match args.first() {
+        Some(name) => cx
+            .editor
+            .language_servers
+            .iter_clients()
+            .filter(|ls| ls.name() == name)
+            .map(|ls| &**ls)
+            .collect(),
+        None => doc.language_servers().collect(),
+    };
+    ensure!(
+        !language_servers.is_empty(),
+        match args.first() {
+            Some(name) => format!("Language server '{name}' is not running"),
+            None => "No language servers are attached to the current document".to_string(),
+        }
+    );
+
+    // The servers that are used for each feature in the current document, in order of
+    // precedence. Most features only use the first of them.
+    let feature_servers: Vec<_> = LanguageServerFeature::ALL
+        .into_iter()
+        .map(|feature| {
+            let ids: Vec<_> = doc
+                .language_servers_with_feature(feature)
+                .map(|ls| ls.id())
+                .collect();
+            (feature, ids)
+        })
+        .collect();
+
+    let mut contents = String::new();
+    for language_server in language_servers {
+        let id = language_server.id();
+        writeln!(contents, "# {}\n", language_server.name())?;
+        writeln!(
+            contents,
+            "- Status: {}",
+            if language_server.is_initialized() {
+                "initialized"
+            } else {
+                "initializing"
+            }
+        )?;
+        writeln!(
+            contents,
+            "- Root: `{}`",
+            language_server.root_path().display()
+        )?;
+        writeln!(contents, "- Trace: {}", language_server.trace())?;
+        if !language_server.is_initialized() {
+            contents.push('\n');
+            continue;
+        }
+        writeln!(
+            contents,
+            "- Offset encoding: {}\n",
+            language_server.offset_encoding()
+        )?;
+
+        contents.push_str("## Features\n\n");
+        let mut used = false;
+        for (feature, ids) in &feature_servers {
+            match ids.iter().position(|ls_id| *ls_id == id) {
+                Some(0) => writeln!(contents, "- {feature}")?,
+                Some(_) => {
+                    let first = cx
+                        .editor
+                        .language_server_by_id(ids[0])
+                        .map_or("", |ls| ls.name());
+                    writeln!(contents, "- {feature} (after `{first}`)")?
+                }
+                None => continue,
+            }
+            used = true;
+        }
+        if !used {
+            contents.push_str("Not used for the current document.\n");
+        }
+
+        contents.push_str("\n## Workspace folders\n\n");
+        for workspace in language_server.workspace_folders().iter() {
+            writeln!(contents, "- `{}`", workspace.uri)?;
+        }
+
+        contents.push_str("\n## Pending requests\n\n");
+        let pending_requests = language_server.pending_requests();
+        let mut pending_requests: Vec<_> = pending_requests
+            .iter()
+            .filter(|(_, request)| request.is_awaited())
+            .collect();
+        pending_requests.sort_by_key(|(_, request)| request.sent_at);
+        if pending_requests.is_empty() {
+            contents.push_str("None\n");
+        }
+        for (id, request) in pending_requests {
+            writeln!(
+                contents,
+                "- `{}` (id {}) for {}ms",
+                request.method,
+                id,
+                request.sent_at.elapsed().as_millis()
+            )?;
+        }
+
+        contents.push_str("\n## Progress\n\n");
+        match cx.editor.lsp_progress.progress_map(id) {
+            Some(progress) if !progress.is_empty() => {
+                for (token, status) in progress {
+                    let token = match token {
+                        helix_lsp::lsp::NumberOrString::Number(n) => n.to_string(),
+                        helix_lsp::lsp::NumberOrString::String(s) => s.clone(),
+                    };
+                    match status {
+                        helix_lsp::ProgressStatus::Created => {
+                            writeln!(contents, "- `{token}`: created")?
+                        }
+                        helix_lsp::ProgressStatus::Started { title, .. } => {
+                            writeln!(contents, "- `{token}`: {title}")?
+                        }
+                    }
+                }
+            }
+            _ => contents.push_str("None\n"),
+        }
+
+        if let Some(config) = language_server.config() {
+            writeln!(
+                contents,
+                "\n## Configuration\n\n```json\n{}\n```",
+                serde_json::to_string_pretty(config)?
+            )?;
+        }
+        writeln!(
+            contents,
+            "\n## Capabilities\n\n```json\n{}\n```\n",
+            serde_json::to_string_pretty(language_server.capabilities())?
+        )?;
+    }
+
+    // The capabilities are too long to be read comfortably in a popup.
+    let doc_id = cx
+        .editor
+        .new_file_from_text(Action::VerticalSplit, &contents);
+    let loader = cx.editor.syn_loader.load();
+    let doc = doc_mut!(cx.editor, &doc_id);
+    doc.readonly = true;
+    if let Err(err) = doc.set_language_by_language_id("markdown", &loader) {
+        log::debug!("failed to highlight :lsp-info buffer: {err}");
+    }
+
+    Ok(())
+}
+
+fn lsp_trace(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    use helix_core::syntax::config::LanguageServerTrace;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+    use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let name = &args[0];
+    let language_server = cx
+        .editor
+        .language_servers
+        .iter_clients()
+        .find(|ls| ls.name() == name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Language server '{name}' is not running"))?;
+
+    if let Some(level) = args.get(1) {
+        let trace: LanguageServerTrace = level.parse()?;
+        language_server.set_trace(trace);
+    }
+
+    let mut trace = language_server.subscribe_trace();
+    let doc_id = cx.editor.new_file_from_text(
+        Action::VerticalSplit,
+        &format!("Tracing messages of language server '{name}'\n"),
+    );
+    doc_mut!(cx.editor, &doc_id).readonly = true;
+
+    // Set once the trace buffer has been closed so that the subscription can be dropped.
+    let closed = Arc::new(AtomicBool::new(false));
+    tokio::spawn(async move {
+        // how often an idle server is checked for whether its trace buffer was closed
+        const CLOSED_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        while !closed.load(Ordering::Relaxed) {
+            let closed_ = closed.clone();
+            let mut text = match tokio::time::timeout(CLOSED_CHECK_INTERVAL, trace.recv()).await {
+                Ok(Ok(entry)) => format!("{entry}\n"),
+                Ok(Err(RecvError::Lagged(skipped))) => format!("... {skipped} messages skipped\n"),
+                Ok(Err(RecvError::Closed)) => break,
+                Err(_) => {
+                    job::dispatch(move |editor, _| {
+                        if !editor.documents.contains_key(&doc_id) {
+                            closed_.store(true, Ordering::Relaxed);
+                        }
+                    })
+                    .await;
+                    continue;
+                }
+            };
+            // Batch up everything that is already queued to avoid a redraw per message.
+            loop {
+                match trace.try_recv() {
+                    Ok(entry) => writeln!(text, "{entry}").unwrap(),
+                    Err(TryRecvError::Lagged(skipped)) => {
+                        writeln!(text, "... {skipped} messages skipped").unwrap()
+                    }
+                    Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+                }
+            }
+
+            job::dispatch(move |editor, _| {
+                if !editor.append_to_document(doc_id, &text) {
+                    closed_.store(true, Ordering::Relaxed);
+                }
+            })
+            .await;
+        }
+    });
+
+    Ok(())
+}
+
 fn tree_sitter_scopes(
     cx: &mut compositor::Context,
     _args: Args,
@@ -1874,26 +2230,54 @@ fn debug_eval(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> a
         // TODO: support no frame_id
 
         let frame_id = debugger.stack_frames[&thread_id][frame].id;
-        let response = helix_lsp::block_on(debugger.eval(args.join(" "), Some(frame_id)))?;
+        let expression = args.join(" ");
+        let response = helix_lsp::block_on(debugger.eval(expression.clone(), Some(frame_id)))?;
+        cx.editor
+            .debug_console_push(ConsoleCategory::Input, expression);
+        cx.editor
+            .debug_console_push(ConsoleCategory::Result, response.result.clone());
         cx.editor.set_status(response.result);
     }
     Ok(())
 }
 
-fn debug_start(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+fn debug_watch_add(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    let mut args: Vec<_> = args.into_iter().collect();
-    let name = match args.len() {
-        0 => None,
-        _ => Some(args.remove(0)),
-    };
-    dap_start_impl(cx, name.as_deref(), None, Some(args))
+    let expression = args[0].trim();
+    ensure!(!expression.is_empty(), "Expected an expression to watch");
+    if !cx
+        .editor
+        .debug_watches
+        .iter()
+        .any(|watch| watch.expression == expression)
+    {
+        cx.editor
+            .debug_watches
+            .push(DebugWatch::new(expression.to_string()));
+    }
+    crate::handlers::dap::evaluate_watches(cx.editor);
+
+    let panel = ui::DebugWatchPanel::new(cx.editor);
+    let popup = Popup::new(ui::DebugWatchPanel::ID, panel);
+    cx.jobs.callback(async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                compositor.replace_or_push(ui::DebugWatchPanel::ID, popup);
+            },
+        ));
+        Ok(call)
+    });
+    Ok(())
 }
 
-fn debug_remote(
+fn debug_watch_remove(
     cx: &mut compositor::Context,
     args: Args,
     event: PromptEvent,
@@ -1902,48 +2286,200 @@ fn debug_remote(
         return Ok(());
     }
 
-    let mut args: Vec<_> = args.into_iter().collect();
-    let address = match args.len() {
-        0 => None,
-        _ => Some(args.remove(0).parse()?),
-    };
-    let name = match args.len() {
-        0 => None,
-        _ => Some(args.remove(0)),
-    };
-    dap_start_impl(cx, name.as_deref(), address, Some(args))
+    match args.first().map(|expression| expression.trim()) {
+        Some(expression) => {
+            let len = cx.editor.debug_watches.len();
+            cx.editor
+                .debug_watches
+                .retain(|watch| watch.expression != expression);
+            ensure!(
+                cx.editor.debug_watches.len() != len,
+                "'{expression}' is not watched"
+            );
+        }
+        None => cx.editor.debug_watches.clear(),
+    }
+    Ok(())
 }
 
-fn tutor(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow::Result<()> {
+fn debug_function_breakpoint(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    let path = helix_loader::runtime_file(Path::new("tutor"));
-    cx.editor.open(&path, Action::Replace)?;
-    // Unset path to prevent accidentally saving to the original tutor file.
-    doc_mut!(cx.editor).set_path(None);
+    let name = &args[0];
+    let breakpoints = &mut cx.editor.function_breakpoints;
+    let status = match breakpoints
+        .iter()
+        .position(|breakpoint| breakpoint.name == *name)
+    {
+        Some(pos) => {
+            breakpoints.remove(pos);
+            format!("Function breakpoint on {name} removed")
+        }
+        None => {
+            breakpoints.push(FunctionBreakpoint::new(name.to_string()));
+            format!("Function breakpoint on {name} set")
+        }
+    };
+
+    if let Some(debugger) = cx.editor.debug_adapters.get_active_client_mut() {
+        helix_view::handlers::dap::function_breakpoints_changed(
+            debugger,
+            &mut cx.editor.function_breakpoints,
+        )?;
+    }
+    cx.editor.set_status(status);
     Ok(())
 }
 
-fn abort_goto_line_number_preview(cx: &mut compositor::Context) {
-    if let Some(last_selection) = cx.editor.last_selection.take() {
-        let scrolloff = cx.editor.config().scrolloff;
+fn debug_instruction_breakpoint(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
 
-        let (view, doc) = current!(cx.editor);
-        doc.set_selection(view.id, last_selection);
-        view.ensure_cursor_in_view(doc, scrolloff);
+    let reference = &args[0];
+    let offset = args
+        .get(1)
+        .map(|offset| offset.parse::<i64>())
+        .transpose()
+        .map_err(|_| anyhow!("invalid instruction offset"))?;
+
+    let breakpoints = &mut cx.editor.instruction_breakpoints;
+    let status = match breakpoints.iter().position(|breakpoint| {
+        breakpoint.instruction_reference == *reference && breakpoint.offset == offset
+    }) {
+        Some(pos) => {
+            breakpoints.remove(pos);
+            format!("Instruction breakpoint at {reference} removed")
+        }
+        None => {
+            breakpoints.push(InstructionBreakpoint::new(reference.to_string(), offset));
+            format!("Instruction breakpoint at {reference} set")
+        }
+    };
+
+    if let Some(debugger) = cx.editor.debug_adapters.get_active_client_mut() {
+        helix_view::handlers::dap::instruction_breakpoints_changed(
+            debugger,
+            &mut cx.editor.instruction_breakpoints,
+        )?;
     }
+    cx.editor.set_status(status);
+    Ok(())
 }
 
-fn update_goto_line_number_preview(cx: &mut compositor::Context, args: Args) -> anyhow::Result<()> {
-    cx.editor.last_selection.get_or_insert_with(|| {
-        let (view, doc) = current!(cx.editor);
-        doc.selection(view.id).clone()
-    });
-
-    let scrolloff = cx.editor.config().scrolloff;
-    let line = args[0].parse::<usize>()?;
+fn debug_console(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let filter = args.first().map(|category| category.parse()).transpose()?;
+    cx.editor.open_debug_console(Action::VerticalSplit, filter);
+    Ok(())
+}
+
+fn debug_memory(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let Some(debugger) = cx.editor.debug_adapters.get_active_client() else {
+        bail!("No active debug session");
+    };
+    let frame_id = debugger.current_stack_frame().map(|frame| frame.id);
+    let expression = &args[0];
+    let response = helix_lsp::block_on(debugger.eval(expression.to_string(), frame_id))?;
+    let Some(memory_reference) = response.memory_reference else {
+        bail!("{expression} has no memory reference");
+    };
+
+    cx.editor
+        .open_memory_view(Action::VerticalSplit, memory_reference);
+    crate::handlers::dap::refresh_memory_view(cx.editor);
+    Ok(())
+}
+
+fn debug_start(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut args: Vec<_> = args.into_iter().collect();
+    let name = match args.len() {
+        0 => None,
+        _ => Some(args.remove(0)),
+    };
+    dap_start_impl(cx, name.as_deref(), None, Some(args))
+}
+
+fn debug_remote(
+    cx: &mut compositor::Context,
+    args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut args: Vec<_> = args.into_iter().collect();
+    let address = match args.len() {
+        0 => None,
+        _ => Some(args.remove(0).parse()?),
+    };
+    let name = match args.len() {
+        0 => None,
+        _ => Some(args.remove(0)),
+    };
+    dap_start_impl(cx, name.as_deref(), address, Some(args))
+}
+
+fn tutor(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let path = helix_loader::runtime_file(Path::new("tutor"));
+    cx.editor.open(&path, Action::Replace)?;
+    // Unset path to prevent accidentally saving to the original tutor file.
+    doc_mut!(cx.editor).set_path(None);
+    Ok(())
+}
+
+fn abort_goto_line_number_preview(cx: &mut compositor::Context) {
+    if let Some(last_selection) = cx.editor.last_selection.take() {
+        let scrolloff = cx.editor.config().scrolloff;
+
+        let (view, doc) = current!(cx.editor);
+        doc.set_selection(view.id, last_selection);
+        view.ensure_cursor_in_view(doc, scrolloff);
+    }
+}
+
+fn update_goto_line_number_preview(cx: &mut compositor::Context, args: Args) -> anyhow::Result<()> {
+    cx.editor.last_selection.get_or_insert_with(|| {
+        let (view, doc) = current!(cx.editor);
+        doc.selection(view.id).clone()
+    });
+
+    let scrolloff = cx.editor.config().scrolloff;
+    let line = args[0].parse::<usize>()?;
     goto_line_without_jumplist(
         cx.editor,
         NonZeroUsize::new(line),
@@ -2473,6 +3009,368 @@ fn reset_diff_change(
     Ok(())
 }
 
+fn diff_base(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let doc = doc_mut!(cx.editor);
+    if doc.path().is_none() {
+        bail!("Scratch buffers have no diff base");
+    }
+    doc.set_diff_base_revision(args.first().map(|revision| revision.to_string()));
+    doc.update_diff_bases(&cx.editor.diff_providers);
+
+    let revision = doc.diff_base_revision();
+    if doc.diff_handle().is_none() {
+        bail!("The current buffer does not exist at {revision}");
+    }
+    cx.editor
+        .set_status(format!("Showing changes against {revision}"));
+    Ok(())
+}
+
+fn open_rev(cx: &mut compositor::Context, args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let revision = &args[0];
+    let path = match args.get(1) {
+        Some(path) => {
+            helix_stdx::path::canonicalize(helix_stdx::path::expand_tilde(Path::new(path)))
+        }
+        None => doc!(cx.editor)
+            .path()
+            .cloned()
+            .context("Scratch buffers have no history, pass a path")?,
+    };
+    open_revision(cx.editor, &path, revision, Action::Replace)
+}
+
+fn diff_split(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let (view, doc) = current_ref!(cx.editor);
+    let Some(handle) = doc.diff_handle() else {
+        bail!("Diff is not available in the current buffer")
+    };
+    let diff_base = String::from(handle.load().diff_base());
+    let (doc_id, view_id) = (doc.id(), view.id);
+    let language = doc.language_name().map(ToOwned::to_owned);
+    let revision = doc.diff_base_revision();
+
+    // only one document can be compared at a time
+    if let Some(split) = cx.editor.diff_split {
+        cx.editor.end_diff_split();
+        let _ = cx.editor.close_document(split.base_doc, true);
+    }
+
+    let base_doc = cx
+        .editor
+        .new_file_from_text(Action::VerticalSplit, &diff_base);
+    let (base_view, doc) = current!(cx.editor);
+    doc.readonly = true;
+    if let Some(language) = language {
+        let loader = cx.editor.syn_loader.load();
+        let _ = doc.set_language_by_language_id(&language, &loader);
+    }
+    cx.editor.diff_split = Some(DiffSplit::new(doc_id, view_id, base_doc, base_view.id));
+    cx.editor.focus(view_id);
+    cx.editor.sync_diff_split();
+    cx.editor
+        .set_status(format!("Showing changes against {revision}"));
+    Ok(())
+}
+
+fn hunk_stage(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let (view, doc) = current!(cx.editor);
+    let Some(handle) = doc.index_diff_handle() else {
+        bail!("The current buffer is not tracked in the index")
+    };
+
+    let diff = handle.load();
+    let doc_text = doc.text().slice(..);
+    let index = diff.diff_base();
+    let mut changes = 0;
+
+    let transaction = Transaction::change(
+        index,
+        diff.hunks_intersecting_line_ranges(doc.selection(view.id).line_ranges(doc_text))
+            .map(|hunk| {
+                changes += 1;
+                let start = doc_text.line_to_char(hunk.after.start as usize);
+                let end = doc_text.line_to_char(hunk.after.end as usize);
+                let text: Tendril = doc_text.slice(start..end).chunks().collect();
+                (
+                    index.line_to_char(hunk.before.start as usize),
+                    index.line_to_char(hunk.before.end as usize),
+                    (!text.is_empty()).then_some(text),
+                )
+            }),
+    );
+    if changes == 0 {
+        bail!("There are no unstaged changes under any selection");
+    }
+    let mut index = index.clone();
+    transaction.apply(&mut index);
+
+    drop(diff); // make borrow check happy
+    doc.set_index_text(index, &cx.editor.diff_providers)?;
+    cx.editor.set_status(format!(
+        "Staged {changes} change{}",
+        if changes == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+fn hunk_unstage(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let (view, doc) = current!(cx.editor);
+    let Some(index_handle) = doc.index_diff_handle() else {
+        bail!("The current buffer is not tracked in the index")
+    };
+    // the gutter may be diffed against a different revision, staged changes are always
+    // relative to HEAD
+    let head = doc
+        .path()
+        .and_then(|path| cx.editor.diff_providers.get_diff_base(path))
+        .unwrap_or_default();
+    let (head, ..) = from_reader(&mut head.as_slice(), Some(doc.encoding()))?;
+
+    let unstaged = index_handle.load();
+    let index = unstaged.diff_base();
+    let doc_text = doc.text().slice(..);
+    // the selected lines of the index, the staged changes are the differences to HEAD
+    let ranges: Vec<_> = doc
+        .selection(view.id)
+        .line_ranges(doc_text)
+        .map(|(start, end)| unstaged.base_line_range(start as u32..end as u32 + 1))
+        .collect();
+    let mut changes = 0;
+
+    let staged = helix_vcs::compute_hunks(&head, index);
+    let transaction = Transaction::change(
+        index,
+        staged
+            .iter()
+            .filter(|hunk| {
+                ranges.iter().any(|range| {
+                    if hunk.after.is_empty() {
+                        range.contains(&hunk.after.start) || range.end == hunk.after.start
+                    } else {
+                        hunk.after.start < range.end && range.start < hunk.after.end
+                    }
+                })
+            })
+            .map(|hunk| {
+                changes += 1;
+                let start = head.line_to_char(hunk.before.start as usize);
+                let end = head.line_to_char(hunk.before.end as usize);
+                let text: Tendril = head.slice(start..end).chunks().collect();
+                (
+                    index.line_to_char(hunk.after.start as usize),
+                    index.line_to_char(hunk.after.end as usize),
+                    (!text.is_empty()).then_some(text),
+                )
+            }),
+    );
+    if changes == 0 {
+        bail!("There are no staged changes under any selection");
+    }
+    let mut index = index.clone();
+    transaction.apply(&mut index);
+
+    drop(unstaged);
+    doc.set_index_text(index, &cx.editor.diff_providers)?;
+    cx.editor.set_status(format!(
+        "Unstaged {changes} change{}",
+        if changes == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+fn conflict_ours(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    resolve_conflicts(cx, Resolution::Ours)
+}
+
+fn conflict_theirs(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    resolve_conflicts(cx, Resolution::Theirs)
+}
+
+fn conflict_both(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    resolve_conflicts(cx, Resolution::Both)
+}
+
+fn conflict_base(
+    cx: &mut compositor::Context,
+    _args: Args,
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    resolve_conflicts(cx, Resolution::Base)
+}
+
+/// Replaces the merge conflicts under any selection with the versions picked by `resolution`.
+fn resolve_conflicts(cx: &mut compositor::Context, resolution: Resolution) -> anyhow::Result<()> {
+    let scrolloff = cx.editor.config().scrolloff;
+    let (view, doc) = current!(cx.editor);
+    let doc_text = doc.text().slice(..);
+    let line_ranges: Vec<_> = doc.selection(view.id).line_ranges(doc_text).collect();
+
+    let mut changes = Vec::new();
+    for conflict in doc.conflicts().iter().filter(|conflict| {
+        line_ranges
+            .iter()
+            .any(|&(start, end)| conflict.lines.start <= end && start < conflict.lines.end)
+    }) {
+        let Some(text) = conflict.resolve(doc_text, resolution) else {
+            bail!(
+                "The conflict at line {} has no base version, the diff3 conflict style is required",
+                conflict.lines.start + 1
+            );
+        };
+        changes.push((
+            doc_text.line_to_char(conflict.lines.start),
+            doc_text.line_to_char(conflict.lines.end),
+            (!text.is_empty()).then(|| text.into()),
+        ));
+    }
+    if changes.is_empty() {
+        bail!("There are no conflicts under any selection");
+    }
+
+    let resolved = changes.len();
+    let transaction = Transaction::change(doc.text(), changes.into_iter());
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+    view.ensure_cursor_in_view(doc, scrolloff);
+    cx.editor.set_status(format!(
+        "Resolved {resolved} conflict{}",
+        if resolved == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+fn blame(cx: &mut compositor::Context, _args: Args, event: PromptEvent) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let (view, doc) = current_ref!(cx.editor);
+    let doc_id = doc.id();
+    let cursor_line = doc
+        .selection(view.id)
+        .primary()
+        .cursor_line(doc.text().slice(..));
+    let Some(path) = doc.path() else {
+        bail!("Blame is not available for scratch buffers")
+    };
+    let blame = cx.editor.diff_providers.blame(path.clone());
+
+    let callback = async move {
+        let blame = std::sync::Arc::new(blame.await?);
+        let call: job::Callback = Callback::Editor(Box::new(move |editor: &mut Editor| {
+            let Some(doc) = editor.document_mut(doc_id) else {
+                return;
+            };
+            doc.set_blame(Some(blame));
+            let contents = blame_view_text(doc);
+
+            editor.new_file_from_text(Action::VerticalSplit, &contents);
+            let scrolloff = editor.config().scrolloff;
+            let (view, doc) = current!(editor);
+            doc.readonly = true;
+            let text = doc.text().slice(..);
+            let pos = text.line_to_char(cursor_line.min(text.len_lines() - 1));
+            doc.set_selection(view.id, Selection::point(pos));
+            view.ensure_cursor_in_view(doc, scrolloff);
+        }));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+/// Lists the commit that last changed each line of the document, one line per document line.
+fn blame_view_text(doc: &Document) -> String {
+    let text = doc.text();
+    // A text ending with a line ending has an empty last line, which has no row in the view.
+    let len_lines = if text.line(text.len_lines() - 1).len_chars() == 0 {
+        text.len_lines().saturating_sub(1).max(1)
+    } else {
+        text.len_lines()
+    };
+    let lines: Vec<_> = (0..len_lines)
+        .map(|line| match doc.line_blame(line) {
+            Some(LineBlame::Committed(commit)) => (
+                &*commit.id,
+                &*commit.author,
+                commit.relative_time(),
+                &*commit.summary,
+            ),
+            _ => ("", "", String::new(), "Not committed yet"),
+        })
+        .collect();
+    let id_width = lines.iter().map(|(id, ..)| id.len()).max().unwrap_or(0);
+    let author_width = lines
+        .iter()
+        .map(|(_, author, ..)| author.chars().count())
+        .max()
+        .unwrap_or(0);
+    let time_width = lines
+        .iter()
+        .map(|(_, _, time, _)| time.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut contents = String::new();
+    for (id, author, time, summary) in lines {
+        let _ = writeln!(
+            contents,
+            "{id:id_width$}  {author:author_width$}  {time:time_width$}  {summary}"
+        );
+    }
+    contents
+}
+
 fn clear_register(
     cx: &mut compositor::Context,
     args: Args,
@@ -2671,7 +3569,7 @@ fn noop(_cx: &mut compositor::Context, _args: Args, _event: PromptEvent) -> anyh
 
 const WRITE_NO_FORMAT_FLAG: Flag = Flag {
     name: "no-format",
-    doc: "skip auto-formatting",
+    doc: "skip auto-formatting and code actions on save",
     ..Flag::DEFAULT
 };
 
@@ -3279,6 +4177,64 @@ fn noop(_cx: &mut compositor::Context, _args: Args, _event: PromptEvent) -> anyh
             ..Signature::DEFAULT
         },
     },
+    TypableCommand {
+        name: "lsp-workspace-add",
+        aliases: &[],
+        doc: "Adds a workspace folder to every running language server that supports workspace folders, and to language servers started later in this session.",
+        fun: lsp_workspace_add,
+        completer: CommandCompleter::positional(&[completers::directory]),
+        signature: Signature {
+            positionals: (1, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "lsp-workspace-remove",
+        aliases: &[],
+        doc: "Removes a workspace folder from every running language server.",
+        fun: lsp_workspace_remove,
+        completer: CommandCompleter::positional(&[completers::lsp_workspace_folder]),
+        signature: Signature {
+            positionals: (1, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "lsp-workspace-list",
+        aliases: &[],
+        doc: "Lists the workspace folders of every running language server.",
+        fun: lsp_workspace_list,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "lsp-info",
+        aliases: &[],
+        doc: "Opens a scratch buffer describing the language servers of the current document, or the given language server: their capabilities, offset encoding, workspace folders, pending requests, progress and the features they are used for.",
+        fun: lsp_info,
+        completer: CommandCompleter::positional(&[completers::active_language_servers]),
+        signature: Signature {
+            positionals: (0, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "lsp-trace",
+        aliases: &[],
+        doc: "Opens a scratch buffer that streams the messages exchanged with the given language server. An optional trace level (off, messages or verbose) is sent to the server with '$/setTrace'.",
+        fun: lsp_trace,
+        completer: CommandCompleter::positional(&[
+            completers::active_language_servers,
+            completers::language_server_trace,
+        ]),
+        signature: Signature {
+            positionals: (1, Some(2)),
+            ..Signature::DEFAULT
+        },
+    },
     TypableCommand {
         name: "tree-sitter-scopes",
         aliases: &[],
@@ -3334,6 +4290,75 @@ fn noop(_cx: &mut compositor::Context, _args: Args, _event: PromptEvent) -> anyh
             ..Signature::DEFAULT
         },
     },
+    TypableCommand {
+        name: "debug-watch-add",
+        aliases: &[],
+        doc: "Add an expression to the watch expressions, which are evaluated whenever the debuggee stops.",
+        fun: debug_watch_add,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (1, Some(1)),
+            raw_after: Some(0),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "debug-watch-remove",
+        aliases: &[],
+        doc: "Remove the given watch expression, or all of them if none is given.",
+        fun: debug_watch_remove,
+        completer: CommandCompleter::all(completers::debug_watch),
+        signature: Signature {
+            positionals: (0, Some(1)),
+            raw_after: Some(0),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "debug-function-breakpoint",
+        aliases: &[],
+        doc: "Toggle a breakpoint on entering the function with the given name.",
+        fun: debug_function_breakpoint,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (1, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "debug-instruction-breakpoint",
+        aliases: &[],
+        doc: "Toggle a breakpoint on the instruction at the given memory reference, with an optional offset in bytes.",
+        fun: debug_instruction_breakpoint,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (1, Some(2)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "debug-memory",
+        aliases: &[],
+        doc: "Show the memory referenced by the value of the given expression, for example a pointer.",
+        fun: debug_memory,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (1, Some(1)),
+            raw_after: Some(0),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "debug-console",
+        aliases: &[],
+        doc: "Open the debug console, which collects the output of debugging sessions and evaluated expressions. An optional output category (console, important, stdout or stderr) limits the displayed output.",
+        fun: debug_console,
+        completer: CommandCompleter::positional(&[completers::debug_output_category]),
+        signature: Signature {
+            positionals: (0, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
     TypableCommand {
         name: "vsplit",
         aliases: &["vs"],
@@ -3589,6 +4614,116 @@ fn noop(_cx: &mut compositor::Context, _args: Args, _event: PromptEvent) -> anyh
             ..Signature::DEFAULT
         },
     },
+    TypableCommand {
+        name: "diff-base",
+        aliases: &[],
+        doc: "Compare the current buffer against the given revision, for example `HEAD`, `index`, `main` or `main...HEAD`. Without a revision the configured `diff-base` is used.",
+        fun: diff_base,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(1)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "open-rev",
+        aliases: &[],
+        doc: "Open a file as it was at the given revision in a read-only buffer, for example `:open-rev HEAD~2 src/main.rs`. Without a path the current file is opened.",
+        fun: open_rev,
+        completer: CommandCompleter::positional(&[completers::none, completers::filename]),
+        signature: Signature {
+            positionals: (1, Some(2)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "diff-split",
+        aliases: &[],
+        doc: "Open the diff base of the current buffer in a read-only vertical split that scrolls along with the buffer and highlights the changes in both.",
+        fun: diff_split,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "hunk-stage",
+        aliases: &[],
+        doc: "Stage the unstaged changes under the selections in the git index.",
+        fun: hunk_stage,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "hunk-unstage",
+        aliases: &[],
+        doc: "Remove the staged changes under the selections from the git index.",
+        fun: hunk_unstage,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "conflict-ours",
+        aliases: &[],
+        doc: "Resolve the merge conflicts under the selections to our version.",
+        fun: conflict_ours,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "conflict-theirs",
+        aliases: &[],
+        doc: "Resolve the merge conflicts under the selections to their version.",
+        fun: conflict_theirs,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "conflict-both",
+        aliases: &[],
+        doc: "Resolve the merge conflicts under the selections to our version followed by their version.",
+        fun: conflict_both,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "conflict-base",
+        aliases: &[],
+        doc: "Resolve the merge conflicts under the selections to the version of the common ancestor. Requires the diff3 or zdiff3 conflict style.",
+        fun: conflict_base,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
+    TypableCommand {
+        name: "blame",
+        aliases: &[],
+        doc: "Open a view listing the commit that last changed each line of the current buffer.",
+        fun: blame,
+        completer: CommandCompleter::none(),
+        signature: Signature {
+            positionals: (0, Some(0)),
+            ..Signature::DEFAULT
+        },
+    },
     TypableCommand {
         name: "clear-register",
         aliases: &[],
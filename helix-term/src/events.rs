@@ -1,8 +1,9 @@
 use helix_event::{events, register_event};
 use helix_view::document::Mode;
 use helix_view::events::{
-    ConfigDidChange, DiagnosticsDidChange, DocumentDidChange, DocumentDidClose, DocumentDidOpen,
-    DocumentFocusLost, LanguageServerExited, LanguageServerInitialized, SelectionDidChange,
+    ConfigDidChange, DebugFrameDidChange, DiagnosticsDidChange, DiffBasesDidChange,
+    DocumentDidChange, DocumentDidClose, DocumentDidOpen, DocumentFocusLost, LanguageServerExited,
+    LanguageServerInitialized, SelectionDidChange,
 };
 
 use crate::commands;
@@ -23,8 +24,10 @@ pub fn register() {
     register_event::<DocumentDidClose>();
     register_event::<DocumentFocusLost>();
     register_event::<SelectionDidChange>();
+    register_event::<DiffBasesDidChange>();
     register_event::<DiagnosticsDidChange>();
     register_event::<LanguageServerInitialized>();
     register_event::<LanguageServerExited>();
     register_event::<ConfigDidChange>();
+    register_event::<DebugFrameDidChange>();
 }
@@ -13,11 +13,16 @@
 pub use helix_view::handlers::{word_index, Handlers};
 
 use self::document_colors::DocumentColorsHandler;
+use self::document_highlight::DocumentHighlightHandler;
 
 mod auto_save;
+mod blame;
+mod commit_message;
 pub mod completion;
+pub mod dap;
 pub mod diagnostics;
 mod document_colors;
+mod document_highlight;
 mod prompt;
 mod signature_help;
 mod snippet;
@@ -25,10 +30,11 @@
 pub fn setup(config: Arc<ArcSwap<Config>>) -> Handlers {
     events::register();
 
-    let event_tx = completion::CompletionHandler::new(config).spawn();
+    let event_tx = completion::CompletionHandler::new(config.clone()).spawn();
     let signature_hints = SignatureHelpHandler::new().spawn();
     let auto_save = AutoSaveHandler::new().spawn();
     let document_colors = DocumentColorsHandler::default().spawn();
+    let document_highlight = DocumentHighlightHandler::new(config).spawn();
     let word_index = word_index::Handler::spawn();
     let pull_diagnostics = PullDiagnosticsHandler::default().spawn();
     let pull_all_documents_diagnostics = PullAllDocumentsDiagnosticHandler::default().spawn();
@@ -38,6 +44,7 @@ pub fn setup(config: Arc<ArcSwap<Config>>) -> Handlers {
         signature_hints,
         auto_save,
         document_colors,
+        document_highlight,
         word_index,
         pull_diagnostics,
         pull_all_documents_diagnostics,
@@ -47,9 +54,13 @@ pub fn setup(config: Arc<ArcSwap<Config>>) -> Handlers {
     completion::register_hooks(&handlers);
     signature_help::register_hooks(&handlers);
     auto_save::register_hooks(&handlers);
+    blame::register_hooks(&handlers);
+    commit_message::register_hooks(&handlers);
+    dap::register_hooks(&handlers);
     diagnostics::register_hooks(&handlers);
     snippet::register_hooks(&handlers);
     document_colors::register_hooks(&handlers);
+    document_highlight::register_hooks(&handlers);
     prompt::register_hooks(&handlers);
     handlers
 }
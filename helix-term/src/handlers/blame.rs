@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use helix_event::register_hook;
+use helix_view::{
+    events::{ConfigDidChange, DiffBasesDidChange},
+    handlers::Handlers,
+    DocumentId, Editor,
+};
+
+use crate::job;
+
+/// Computes the blame of the document in the background and attaches it once it is done.
+fn request_blame(editor: &mut Editor, doc_id: DocumentId) {
+    let Some(path) = editor.document(doc_id).and_then(|doc| doc.path()).cloned() else {
+        return;
+    };
+    let blame = editor.diff_providers.blame(path);
+    tokio::spawn(async move {
+        let blame = match blame.await {
+            Ok(blame) => Some(Arc::new(blame)),
+            Err(err) => {
+                log::debug!("failed to blame document: {err:#}");
+                None
+            }
+        };
+        job::dispatch(move |editor, _| {
+            if let Some(doc) = editor.document_mut(doc_id) {
+                doc.set_blame(blame);
+            }
+        })
+        .await;
+    });
+}
+
+pub(super) fn register_hooks(_handlers: &Handlers) {
+    // The diff bases are loaded when a document is opened and again whenever `HEAD` may have
+    // moved, the blame of `HEAD` follows. Documents being opened aren't added to the editor yet
+    // so they are looked up by path once the editor is available.
+    register_hook!(move |event: &mut DiffBasesDidChange<'_>| {
+        let Some(path) = event.doc.path().cloned() else {
+            return Ok(());
+        };
+        tokio::spawn(async move {
+            job::dispatch(move |editor, _| {
+                if !editor.config().inline_blame {
+                    return;
+                }
+                if let Some(doc_id) = editor.document_by_path(&path).map(|doc| doc.id()) {
+                    request_blame(editor, doc_id);
+                }
+            })
+            .await;
+        });
+        Ok(())
+    });
+
+    register_hook!(move |event: &mut ConfigDidChange<'_>| {
+        if event.new.inline_blame && !event.old.inline_blame {
+            let doc_ids: Vec<_> = event.editor.documents().map(|doc| doc.id()).collect();
+            for doc_id in doc_ids {
+                request_blame(event.editor, doc_id);
+            }
+        }
+        Ok(())
+    });
+}
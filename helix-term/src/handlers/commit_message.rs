@@ -0,0 +1,121 @@
+use helix_core::{
+    chars::char_is_word,
+    diagnostic::{DiagnosticProvider, Range, Severity},
+    Diagnostic,
+};
+use helix_event::register_hook;
+use helix_vcs::check_commit_message;
+use helix_view::{
+    editor::{Action, CommitDiff},
+    events::{DocumentDidChange, DocumentDidOpen},
+    handlers::Handlers,
+    Document, DocumentId, Editor,
+};
+
+use crate::job;
+
+/// The language of the message file that git opens in `$GIT_EDITOR`.
+const LANGUAGE: &str = "git-commit";
+
+/// Replaces the warnings about the width of the subject and body lines of a commit message.
+fn check(doc: &mut Document) {
+    let text = doc.text();
+    let diagnostics: Vec<_> = check_commit_message(text.slice(..), doc.text_width())
+        .into_iter()
+        .map(|warning| {
+            let line_start = text.line_to_char(warning.line);
+            let (start, end) = (
+                line_start + warning.columns.start,
+                line_start + warning.columns.end,
+            );
+            Diagnostic {
+                range: Range { start, end },
+                ends_at_word: start != end && end != 0 && char_is_word(text.char(end - 1)),
+                starts_at_word: start != end && char_is_word(text.char(start)),
+                zero_width: start == end,
+                line: warning.line,
+                message: warning.message,
+                severity: Some(Severity::Warning),
+                code: None,
+                provider: DiagnosticProvider::CommitMessage,
+                tags: Vec::new(),
+                source: None,
+                data: None,
+            }
+        })
+        .collect();
+    doc.replace_diagnostics(diagnostics, &[], Some(&DiagnosticProvider::CommitMessage));
+}
+
+/// Computes the changes staged for the commit in the background and shows them in a split next
+/// to the commit message.
+fn open_staged_diff(editor: &mut Editor, doc_id: DocumentId) {
+    // git passes the message file inside of the git directory
+    let Some(cwd) = editor
+        .document(doc_id)
+        .and_then(|doc| doc.path())
+        .and_then(|path| path.parent())
+        .map(ToOwned::to_owned)
+    else {
+        return;
+    };
+    let diff_providers = editor.diff_providers.clone();
+    tokio::spawn(async move {
+        let diff = tokio::task::spawn_blocking(move || diff_providers.staged_diff(&cwd)).await;
+        let Ok(Some(diff)) = diff else {
+            return;
+        };
+        if diff.is_empty() {
+            return;
+        }
+        job::dispatch(move |editor, _| {
+            let Some(view_id) = editor
+                .tree
+                .views()
+                .find(|(view, _)| view.doc == doc_id)
+                .map(|(view, _)| view.id)
+            else {
+                return;
+            };
+            if editor.commit_diff.is_some() {
+                return;
+            }
+            let focus = editor.tree.focus;
+            editor.focus(view_id);
+            let diff_doc = editor.new_file_from_text(Action::VerticalSplit, &diff);
+            let doc = doc_mut!(editor, &diff_doc);
+            doc.readonly = true;
+            let loader = editor.syn_loader.load();
+            let _ = doc.set_language_by_language_id("diff", &loader);
+            editor.commit_diff = Some(CommitDiff {
+                view: view_id,
+                diff_doc,
+            });
+            editor.focus(focus);
+        })
+        .await;
+    });
+}
+
+pub(super) fn register_hooks(_handlers: &Handlers) {
+    register_hook!(move |event: &mut DocumentDidOpen<'_>| {
+        let Some(doc) = event.editor.document_mut(event.doc) else {
+            return Ok(());
+        };
+        if doc.language_name() != Some(LANGUAGE) {
+            return Ok(());
+        }
+        check(doc);
+        if event.editor.config().commit_message_diff {
+            open_staged_diff(event.editor, event.doc);
+        }
+        Ok(())
+    });
+
+    register_hook!(move |event: &mut DocumentDidChange<'_>| {
+        if event.doc.language_name() == Some(LANGUAGE) {
+            check(event.doc);
+        }
+        Ok(())
+    });
+}
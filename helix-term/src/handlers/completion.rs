@@ -26,6 +26,7 @@
 pub use request::CompletionHandler;
 pub use resolve::ResolveHandler;
 
+mod commit_message;
 mod item;
 mod path;
 mod request;
@@ -0,0 +1,122 @@
+use std::{borrow::Cow, path::Path, sync::Arc};
+
+use helix_core::{self as core, completion::CompletionProvider, Transaction};
+use helix_event::TaskHandle;
+use helix_vcs::DiffProviderRegistry;
+use helix_view::{document::SavePoint, handlers::completion::ResponseContext, Editor};
+
+use super::{request::TriggerKind, CompletionItem, CompletionItems, CompletionResponse, Trigger};
+
+/// The number of recent commits whose subjects are offered on the subject line.
+const RECENT_COMMITS: usize = 200;
+// TODO: handle properly in the future
+const PRIORITY: i8 = 1;
+
+fn recent_subjects(diff_providers: &DiffProviderRegistry, cwd: &Path) -> Arc<[String]> {
+    let mut subjects: Vec<_> = diff_providers
+        .recent_commits(cwd, RECENT_COMMITS)
+        .into_iter()
+        .map(|commit| commit.summary.into())
+        .collect();
+    subjects.sort_unstable();
+    subjects.dedup();
+    subjects.into()
+}
+
+/// Completes the subjects of recent commits on the subject line of a commit message and the
+/// paths of the staged files everywhere else.
+pub(super) fn completion(
+    editor: &Editor,
+    trigger: Trigger,
+    handle: TaskHandle,
+    savepoint: Arc<SavePoint>,
+) -> Option<impl FnOnce() -> CompletionResponse> {
+    let (view, doc) = current_ref!(editor);
+    if doc.language_name() != Some("git-commit") {
+        return None;
+    }
+    // git passes the message file inside of the git directory
+    let cwd = doc.path()?.parent()?.to_owned();
+    let diff_providers = editor.diff_providers.clone();
+    let subjects = doc.commit_subjects.clone();
+    let rope = doc.text().clone();
+    let text = rope.slice(..);
+    let selection = doc.selection(view.id).clone();
+    let pos = selection.primary().cursor(text);
+    let line = text.char_to_line(pos);
+
+    // the subject is the first line that is neither blank nor a comment
+    let on_subject = (0..line).all(|line| {
+        text.line(line)
+            .chars()
+            .find(|ch| !ch.is_whitespace())
+            .is_none_or(|ch| ch == '#')
+    });
+    let typed_len = if on_subject {
+        pos - text.line_to_char(line)
+    } else {
+        text.chars_at(pos)
+            .reversed()
+            .take_while(|ch| !ch.is_whitespace())
+            .count()
+    };
+    if typed_len == 0 && trigger.kind != TriggerKind::Manual {
+        return None;
+    }
+    let typed: String = text.slice(pos - typed_len..pos).into();
+
+    if handle.is_canceled() {
+        return None;
+    }
+
+    let future = move || {
+        let items: Vec<(String, &'static str)> = if on_subject {
+            // listed on the blocking task on the first completion in the document
+            subjects
+                .get_or_init(|| recent_subjects(&diff_providers, &cwd))
+                .iter()
+                .map(|subject| (subject.clone(), "commit"))
+                .collect()
+        } else {
+            diff_providers
+                .staged_files(&cwd)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|path| (path.to_string_lossy().replace('\\', "/"), "file"))
+                .collect()
+        };
+        let items = items
+            .into_iter()
+            .filter(|(label, _)| *label != typed)
+            .map(|(label, kind)| {
+                let transaction = Transaction::change_by_selection(&rope, &selection, |range| {
+                    let cursor = range.cursor(rope.slice(..));
+                    (
+                        cursor.saturating_sub(typed_len),
+                        cursor,
+                        Some(label.as_str().into()),
+                    )
+                });
+                CompletionItem::Other(core::CompletionItem {
+                    transaction,
+                    label: label.into(),
+                    kind: Cow::Borrowed(kind),
+                    documentation: None,
+                    provider: CompletionProvider::CommitMessage,
+                })
+            })
+            .collect();
+
+        CompletionResponse {
+            items: CompletionItems::Other(items),
+            provider: CompletionProvider::CommitMessage,
+            context: ResponseContext {
+                is_incomplete: false,
+                priority: PRIORITY,
+                savepoint,
+            },
+        }
+    };
+
+    Some(future)
+}
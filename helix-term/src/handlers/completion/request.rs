@@ -28,7 +28,7 @@
 use crate::ui;
 use crate::ui::editor::InsertEvent;
 
-use super::word;
+use super::{commit_message, word};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(super) enum TriggerKind {
@@ -248,6 +248,11 @@ fn request_completions(
     ) {
         requests.spawn_blocking(path_completion_request);
     }
+    if let Some(commit_message_completion_request) =
+        commit_message::completion(editor, trigger, handle.clone(), savepoint.clone())
+    {
+        requests.spawn_blocking(commit_message_completion_request);
+    }
     if let Some(word_completion_request) =
         word::completion(editor, trigger, handle.clone(), savepoint)
     {
@@ -0,0 +1,275 @@
+use futures_util::future;
+use helix_core::{ChangeSet, Rope};
+use helix_event::register_hook;
+use helix_view::{
+    editor::{DebugWatch, WatchValue},
+    events::{DebugFrameDidChange, DocumentDidChange},
+    handlers::{
+        dap::{
+            breakpoints_changed, disassemble_current_frame, evaluate_inline_values,
+            move_breakpoints, read_memory_view, request_inline_values, set_disassembly,
+            set_inline_values, set_memory_view,
+        },
+        Handlers,
+    },
+    Editor,
+};
+use std::{
+    future::Future,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use crate::job;
+
+/// A document change whose breakpoints are not moved yet: the path of the document, its text
+/// before the change, the change and its text after the change.
+type PendingChange = (PathBuf, Rope, ChangeSet, Rope);
+
+/// A watch value: the expression of the watch followed by the names of the children leading to
+/// the value.
+type WatchPath = (String, Vec<String>);
+
+fn watch_value_mut<'a>(
+    watches: &'a mut [DebugWatch],
+    (expression, names): &WatchPath,
+) -> Option<&'a mut WatchValue> {
+    let watch = watches
+        .iter_mut()
+        .find(|watch| watch.expression == *expression)?;
+    let mut value = watch.value.as_mut()?.as_mut().ok()?;
+    for name in names {
+        value = value
+            .children
+            .iter_mut()
+            .find_map(|(child_name, child)| (child_name == name).then_some(child))?;
+    }
+    Some(value)
+}
+
+fn current_frame_id(editor: &Editor) -> Option<usize> {
+    editor.current_stack_frame().map(|frame| frame.id)
+}
+
+/// Evaluates every watch expression in the current stack frame.
+///
+/// The expressions are evaluated at once in the background. Values that were expanded before
+/// are expanded again once they arrived.
+pub fn evaluate_watches(editor: &mut Editor) {
+    let Some(debugger) = editor.debug_adapters.get_active_client() else {
+        return;
+    };
+    let frame_id = current_frame_id(editor);
+    let requests: Vec<_> = editor
+        .debug_watches
+        .iter()
+        .map(|watch| {
+            let expression = watch.expression.clone();
+            let request = debugger.eval_in_context(expression.clone(), frame_id, Some("watch"));
+            async move { (expression, request.await) }
+        })
+        .collect();
+    if requests.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let responses = future::join_all(requests).await;
+        job::dispatch(move |editor, _| {
+            // another stack frame was selected in the meantime
+            if current_frame_id(editor) != frame_id {
+                return;
+            }
+            let mut expansions = Vec::new();
+            for (expression, response) in responses {
+                let Some(watch) = editor
+                    .debug_watches
+                    .iter_mut()
+                    .find(|watch| watch.expression == expression)
+                else {
+                    continue;
+                };
+                let previous = watch.value.take().and_then(Result::ok);
+                let response = match response {
+                    Ok(response) => response,
+                    Err(err) => {
+                        watch.value = Some(Err(err.to_string()));
+                        continue;
+                    }
+                };
+                let value = WatchValue {
+                    value: response.result,
+                    ty: response.ty,
+                    variables_reference: response.variables_reference,
+                    ..Default::default()
+                };
+                if let Some(previous) = previous.filter(|previous| previous.expanded) {
+                    if value.has_children() {
+                        expansions.push(((expression, Vec::new()), previous));
+                    }
+                }
+                watch.value = Some(Ok(value));
+            }
+            restore_watch_expansion(editor, expansions);
+        })
+        .await;
+    });
+}
+
+/// Fetches the children of the watch values that were expanded before they were evaluated
+/// again, `expansions` holds the previous values. The children are fetched at once and those
+/// that were expanded as well are restored in turn.
+fn restore_watch_expansion(editor: &mut Editor, expansions: Vec<(WatchPath, WatchValue)>) {
+    let Some(debugger) = editor.debug_adapters.get_active_client() else {
+        return;
+    };
+    let requests: Vec<_> = expansions
+        .into_iter()
+        .filter_map(|(path, previous)| {
+            let variables_reference =
+                watch_value_mut(&mut editor.debug_watches, &path)?.variables_reference;
+            let request = debugger.variables(variables_reference);
+            Some(async move { (path, previous, variables_reference, request.await) })
+        })
+        .collect();
+    if requests.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let responses = future::join_all(requests).await;
+        job::dispatch(move |editor, _| {
+            let mut expansions = Vec::new();
+            for (path, previous, variables_reference, response) in responses {
+                let Ok(variables) = response else {
+                    continue;
+                };
+                let Some(value) = watch_value_mut(&mut editor.debug_watches, &path) else {
+                    continue;
+                };
+                // the value was evaluated again in the meantime
+                if value.variables_reference != variables_reference {
+                    continue;
+                }
+                value.set_children(variables);
+                value.expanded = true;
+                for (name, previous) in previous.children {
+                    let expandable = value
+                        .children
+                        .iter()
+                        .any(|(child_name, child)| *child_name == name && child.has_children());
+                    if previous.expanded && expandable {
+                        let mut path = path.clone();
+                        path.1.push(name);
+                        expansions.push((path, previous));
+                    }
+                }
+            }
+            restore_watch_expansion(editor, expansions);
+        })
+        .await;
+    });
+}
+
+/// Refreshes the disassembly and the memory view, those that are open. Their contents are
+/// requested at once in the background.
+pub fn refresh_debug_views(editor: &mut Editor) {
+    refresh_disassembly(editor);
+    refresh_memory_view(editor);
+}
+
+/// Shows the values of the variables in scope of the current stack frame inline. They are
+/// requested in two round trips in the background, see [`request_inline_values`].
+pub fn update_inline_values(editor: &mut Editor) {
+    if let Some(request) = request_inline_values(editor) {
+        refresh_in_background(editor, request, |editor, response| {
+            if let Some(request) = evaluate_inline_values(editor, response) {
+                refresh_in_background(editor, request, set_inline_values);
+            }
+        });
+    }
+}
+
+pub fn refresh_disassembly(editor: &mut Editor) {
+    if let Some(request) = disassemble_current_frame(editor) {
+        refresh_in_background(editor, request, set_disassembly);
+    }
+}
+
+pub fn refresh_memory_view(editor: &mut Editor) {
+    if let Some(request) = read_memory_view(editor) {
+        refresh_in_background(editor, request, |editor, (memory_reference, text)| {
+            set_memory_view(editor, memory_reference, text)
+        });
+    }
+}
+
+/// Awaits `request` and shows its response with `show`, unless another stack frame was selected
+/// in the meantime.
+fn refresh_in_background<T: Send + 'static>(
+    editor: &Editor,
+    request: impl Future<Output = T> + Send + 'static,
+    show: impl FnOnce(&mut Editor, T) + Send + 'static,
+) {
+    let frame_id = current_frame_id(editor);
+    tokio::spawn(async move {
+        let response = request.await;
+        job::dispatch(move |editor, _| {
+            if current_frame_id(editor) == frame_id {
+                show(editor, response);
+            }
+        })
+        .await;
+    });
+}
+
+pub(super) fn register_hooks(_handlers: &Handlers) {
+    register_hook!(move |event: &mut DebugFrameDidChange<'_>| {
+        evaluate_watches(event.editor);
+        update_inline_values(event.editor);
+        refresh_debug_views(event.editor);
+        Ok(())
+    });
+
+    // Breakpoints follow the lines they were set on. They live on the editor so they are moved
+    // once the change is applied. The changes are queued in order and a single job moves the
+    // breakpoints for all of them, dispatching a job per change could fill up the job queue
+    // while the editor is busy applying changes.
+    let pending: Arc<Mutex<Vec<PendingChange>>> = Arc::default();
+    register_hook!(move |event: &mut DocumentDidChange<'_>| {
+        let Some(path) = event.doc.path().cloned() else {
+            return Ok(());
+        };
+        let mut queue = pending.lock().unwrap();
+        queue.push((
+            path,
+            event.old_text.clone(),
+            event.changes.clone(),
+            event.doc.text().clone(),
+        ));
+        if queue.len() > 1 {
+            // the job moving the breakpoints of the earlier changes is still queued
+            return Ok(());
+        }
+        drop(queue);
+        let pending = pending.clone();
+        job::dispatch_blocking(move |editor, _| {
+            let queue = std::mem::take(&mut *pending.lock().unwrap());
+            for (path, old_text, changes, text) in queue {
+                let Some(breakpoints) = editor.breakpoints.get_mut(&path) else {
+                    continue;
+                };
+                if !move_breakpoints(breakpoints, &old_text, &changes, &text) {
+                    continue;
+                }
+                let Some(debugger) = editor.debug_adapters.get_active_client_mut() else {
+                    continue;
+                };
+                if let Err(err) = breakpoints_changed(debugger, path, breakpoints) {
+                    editor.set_error(format!("Failed to set breakpoints: {err}"));
+                }
+            }
+        });
+        Ok(())
+    });
+}
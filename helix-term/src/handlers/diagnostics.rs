@@ -200,7 +200,7 @@ fn request_document_diagnostics_for_language_severs(
             Some(async move {
                 let result = future.await;
 
-                (result, provider, uri)
+                (result, language_server_id, provider, uri)
             })
         })
         .collect();
@@ -213,13 +213,13 @@ fn request_document_diagnostics_for_language_severs(
         let mut retry_language_servers = HashSet::new();
         loop {
             match cancelable_future(futures.next(), &cancel).await {
-                Some(Some((Ok(result), provider, uri))) => {
+                Some(Some((Ok(result), _, provider, uri))) => {
                     job::dispatch(move |editor, _| {
                         handle_pull_diagnostics_response(editor, result, provider, uri, doc_id);
                     })
                     .await;
                 }
-                Some(Some((Err(err), DiagnosticProvider::Lsp { server_id, .. }, _))) => {
+                Some(Some((Err(err), server_id, _, _))) => {
                     let parsed_cancellation_data = if let helix_lsp::Error::Rpc(error) = err {
                         error.data.and_then(|data| {
                             serde_json::from_value::<lsp::DiagnosticServerCancellationData>(data)
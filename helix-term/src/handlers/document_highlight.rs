@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use helix_core::syntax::config::LanguageServerFeature;
+use helix_event::{cancelable_future, register_hook, send_blocking};
+use helix_lsp::{lsp, util::lsp_range_to_range, OffsetEncoding};
+use helix_view::{
+    document::DocumentHighlights,
+    events::{DocumentDidChange, LanguageServerExited, SelectionDidChange},
+    handlers::{lsp::DocumentHighlightEvent, Handlers},
+    DocumentId, Editor, ViewId,
+};
+use tokio::time::Instant;
+
+use crate::{config::Config, job};
+
+pub(super) struct DocumentHighlightHandler {
+    config: Arc<ArcSwap<Config>>,
+    trigger: Option<DocumentHighlightEvent>,
+}
+
+impl DocumentHighlightHandler {
+    pub fn new(config: Arc<ArcSwap<Config>>) -> Self {
+        Self {
+            config,
+            trigger: None,
+        }
+    }
+}
+
+impl helix_event::AsyncHook for DocumentHighlightHandler {
+    type Event = DocumentHighlightEvent;
+
+    fn handle_event(&mut self, event: Self::Event, _timeout: Option<Instant>) -> Option<Instant> {
+        // Only the latest cursor position matters, older events are superseded.
+        self.trigger = Some(event);
+        Some(Instant::now() + self.config.load().editor.idle_timeout)
+    }
+
+    fn finish_debounce(&mut self) {
+        let Some(DocumentHighlightEvent { doc, view }) = self.trigger.take() else {
+            return;
+        };
+
+        job::dispatch_blocking(move |editor, _compositor| {
+            request_document_highlights(editor, doc, view);
+        });
+    }
+}
+
+fn request_document_highlights(editor: &mut Editor, doc_id: DocumentId, view_id: ViewId) {
+    if !editor.config().lsp.auto_document_highlight {
+        return;
+    }
+
+    let Some(doc) = editor.documents.get_mut(&doc_id) else {
+        return;
+    };
+    if !doc.selections().contains_key(&view_id) {
+        return;
+    }
+
+    let Some(language_server) = doc
+        .language_servers_with_feature(LanguageServerFeature::DocumentHighlight)
+        .next()
+    else {
+        return;
+    };
+
+    let offset_encoding = language_server.offset_encoding();
+    let pos = doc.position(view_id, offset_encoding);
+    let Some(future) =
+        language_server.text_document_document_highlight(doc.identifier(), pos, None)
+    else {
+        return;
+    };
+
+    let version = doc.version();
+    let cancel = doc.document_highlight_controller.restart();
+
+    tokio::spawn(async move {
+        let highlights = match cancelable_future(future, &cancel).await {
+            Some(Ok(highlights)) => highlights.unwrap_or_default(),
+            Some(Err(err)) => {
+                log::error!("document highlight request failed: {err}");
+                return;
+            }
+            // The request was cancelled.
+            None => return,
+        };
+
+        job::dispatch(move |editor, _| {
+            attach_document_highlights(
+                editor,
+                doc_id,
+                view_id,
+                version,
+                offset_encoding,
+                highlights,
+            )
+        })
+        .await;
+    });
+}
+
+fn attach_document_highlights(
+    editor: &mut Editor,
+    doc_id: DocumentId,
+    view_id: ViewId,
+    version: i32,
+    offset_encoding: OffsetEncoding,
+    highlights: Vec<lsp::DocumentHighlight>,
+) {
+    if !editor.config().lsp.auto_document_highlight {
+        return;
+    }
+
+    let Some(doc) = editor.documents.get_mut(&doc_id) else {
+        return;
+    };
+    // The ranges are only valid for the text the request was made against.
+    if doc.version() != version || !doc.selections().contains_key(&view_id) {
+        return;
+    }
+
+    let text = doc.text();
+    let mut document_highlights = DocumentHighlights::default();
+    for highlight in highlights {
+        let Some(range) = lsp_range_to_range(text, highlight.range, offset_encoding) else {
+            continue;
+        };
+        let ranges = match highlight.kind {
+            Some(lsp::DocumentHighlightKind::READ) => &mut document_highlights.read,
+            Some(lsp::DocumentHighlightKind::WRITE) => &mut document_highlights.write,
+            _ => &mut document_highlights.text,
+        };
+        ranges.push(range.from()..range.to());
+    }
+
+    for ranges in [
+        &mut document_highlights.text,
+        &mut document_highlights.read,
+        &mut document_highlights.write,
+    ] {
+        ranges.sort_unstable_by_key(|range| range.start);
+    }
+
+    doc.set_document_highlights(view_id, document_highlights);
+}
+
+pub(super) fn register_hooks(handlers: &Handlers) {
+    let tx = handlers.document_highlight.clone();
+    register_hook!(move |event: &mut SelectionDidChange<'_>| {
+        if !event.doc.config.load().lsp.auto_document_highlight {
+            return Ok(());
+        }
+
+        // Keep the current highlights while the cursor moves within one of the occurrences
+        // to avoid flickering, otherwise drop them right away as they are stale.
+        let cursor = event
+            .doc
+            .selection(event.view)
+            .primary()
+            .cursor(event.doc.text().slice(..));
+        if !event
+            .doc
+            .document_highlights(event.view)
+            .is_some_and(|highlights| highlights.contains(cursor))
+        {
+            event.doc.clear_document_highlights(event.view);
+        }
+
+        event.doc.document_highlight_controller.cancel();
+        send_blocking(
+            &tx,
+            DocumentHighlightEvent {
+                doc: event.doc.id(),
+                view: event.view,
+            },
+        );
+        Ok(())
+    });
+
+    register_hook!(move |event: &mut DocumentDidChange<'_>| {
+        // Edits invalidate the occurrences, they are requested again once the cursor settles.
+        event.doc.document_highlight_controller.cancel();
+        event.doc.reset_all_document_highlights();
+        Ok(())
+    });
+
+    register_hook!(move |event: &mut LanguageServerExited<'_>| {
+        for doc in event.editor.documents_mut() {
+            if doc.supports_language_server(event.server_id) {
+                doc.document_highlight_controller.cancel();
+                doc.reset_all_document_highlights();
+            }
+        }
+        Ok(())
+    });
+}
@@ -11,6 +11,9 @@
 
 pub type EditorCompositorCallback = Box<dyn FnOnce(&mut Editor, &mut Compositor) + Send>;
 pub type EditorCallback = Box<dyn FnOnce(&mut Editor) + Send>;
+/// A callback that may add further jobs, for example to continue a write once an earlier job
+/// finished. Jobs that need to be waited on are still waited on when exiting.
+pub type EditorJobsCallback = Box<dyn FnOnce(&mut Editor, &Jobs) + Send>;
 
 runtime_local! {
     static JOB_QUEUE: OnceCell<Sender<Callback>> = OnceCell::new();
@@ -35,6 +38,7 @@ pub fn dispatch_blocking(job: impl FnOnce(&mut Editor, &mut Compositor) + Send +
 pub enum Callback {
     EditorCompositor(EditorCompositorCallback),
     Editor(EditorCallback),
+    EditorJobs(EditorJobsCallback),
 }
 
 pub type JobFuture = BoxFuture<'static, anyhow::Result<Option<Callback>>>;
@@ -110,6 +114,7 @@ pub fn handle_callback(
             Ok(Some(call)) => match call {
                 Callback::EditorCompositor(call) => call(editor, compositor),
                 Callback::Editor(call) => call(editor),
+                Callback::EditorJobs(call) => call(editor, self),
             },
             Err(e) => {
                 editor.set_error(format!("Async job failed: {}", e));
@@ -153,6 +158,11 @@ pub async fn finish(
                                 call(editor, compositor.as_deref_mut().unwrap())
                             }
                             Callback::Editor(call) => call(editor),
+                            Callback::EditorJobs(call) => {
+                                call(editor, self);
+                                // wait on the jobs added by the callback as well
+                                wait_futures.extend(std::mem::take(&mut self.wait_futures));
+                            }
 
                             // skip callbacks for which we don't have the necessary references
                             _ => (),
@@ -120,7 +120,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "e" => goto_prev_entry,
             "T" => goto_prev_test,
             "p" => goto_prev_paragraph,
-            "x" => goto_prev_xml_element,
+            "x" => goto_prev_conflict,
             "space" => add_newline_above,
         },
         "]" => { "Right bracket"
@@ -135,7 +135,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "e" => goto_next_entry,
             "T" => goto_next_test,
             "p" => goto_next_paragraph,
-            "x" => goto_next_xml_element,
+            "x" => goto_next_conflict,
             "space" => add_newline_below,
         },
 
@@ -234,24 +234,34 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "d" => diagnostics_picker,
             "D" => workspace_diagnostics_picker,
             "g" => changed_file_picker,
+            "l" => file_history_picker,
+            "L" => log_picker,
             "a" => code_action,
             "'" => last_picker,
             "G" => { "Debug (experimental)" sticky=true
                 "l" => dap_launch,
                 "r" => dap_restart,
                 "b" => dap_toggle_breakpoint,
+                "B" => dap_breakpoints,
                 "c" => dap_continue,
                 "h" => dap_pause,
                 "i" => dap_step_in,
                 "o" => dap_step_out,
                 "n" => dap_next,
+                "I" => dap_step_in_instruction,
+                "N" => dap_next_instruction,
+                "d" => dap_disassemble,
                 "v" => dap_variables,
+                "x" => dap_repl,
+                "w" => dap_watches,
                 "t" => dap_terminate,
                 "C-c" => dap_edit_condition,
                 "C-l" => dap_edit_log,
+                "C-h" => dap_edit_hit_condition,
                 "s" => { "Switch"
                     "t" => dap_switch_thread,
                     "f" => dap_switch_stack_frame,
+                    "s" => dap_switch_session,
                     // sl, sb
                 },
                 "e" => dap_enable_exceptions,
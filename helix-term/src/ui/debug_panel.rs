@@ -0,0 +1,75 @@
+use crate::{ctrl, key};
+use helix_view::{graphics::Rect, input::KeyEvent, theme::Style};
+use tui::{buffer::Buffer as Surface, text::Span};
+
+/// The selected row of a debug panel listing one row per line and the row at its top.
+pub(super) struct PanelCursor {
+    pub cursor: usize,
+    scroll: usize,
+}
+
+impl PanelCursor {
+    pub fn new(cursor: usize) -> Self {
+        Self { cursor, scroll: 0 }
+    }
+
+    /// Moves the cursor within `len` rows, returning whether `key` moves the cursor.
+    pub fn handle_key(&mut self, key: KeyEvent, len: usize) -> bool {
+        match key {
+            key!('j') | key!(Down) | ctrl!('n') => {
+                if self.cursor + 1 < len {
+                    self.cursor += 1;
+                }
+            }
+            key!('k') | key!(Up) | ctrl!('p') => self.cursor = self.cursor.saturating_sub(1),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Renders the rows visible around the cursor, `spans` builds the line of a row.
+    pub fn render<T>(
+        &mut self,
+        rows: &[T],
+        area: Rect,
+        surface: &mut Surface,
+        selected_style: Style,
+        spans: impl Fn(&T) -> Vec<Span<'_>>,
+    ) {
+        let height = area.height as usize;
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+
+        for (line, (idx, row)) in rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(height)
+            .enumerate()
+        {
+            let y = area.y + line as u16;
+            surface.set_spans(area.x, y, &spans(row).into(), area.width);
+            if idx == self.cursor {
+                surface.set_style(Rect::new(area.x, y, area.width, 1), selected_style);
+            }
+        }
+    }
+}
+
+/// The size of a debug panel showing `rows`, `empty_width` is the width without any rows.
+pub(super) fn required_size<T>(
+    rows: &[T],
+    width: impl Fn(&T) -> usize,
+    empty_width: usize,
+    viewport: (u16, u16),
+) -> Option<(u16, u16)> {
+    let width = rows.iter().map(width).max().unwrap_or(empty_width);
+    let height = rows.len().max(1);
+    Some((
+        (width as u16).min(viewport.0),
+        (height as u16).min(viewport.1),
+    ))
+}
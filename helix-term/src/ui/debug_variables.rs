@@ -0,0 +1,172 @@
+use super::debug_panel::{self, PanelCursor};
+use crate::{
+    compositor::{Component, Compositor, Context, EventResult},
+    key,
+};
+use helix_dap::{Scope, Variable};
+use helix_view::{editor::Action, graphics::Rect, input::Event};
+use tui::{buffer::Buffer as Surface, text::Span};
+
+/// A single line of the variables view: the header of a scope or one of its variables.
+enum Row {
+    Scope(String),
+    Variable {
+        /// Reference of the scope containing the variable.
+        container: usize,
+        name: String,
+        ty: Option<String>,
+        value: String,
+        memory_reference: Option<String>,
+    },
+}
+
+impl Row {
+    fn width(&self) -> usize {
+        match self {
+            Row::Scope(name) => name.chars().count() + 2,
+            Row::Variable {
+                name, ty, value, ..
+            } => {
+                let ty_width = ty.as_ref().map_or(0, |ty| ty.chars().count() + 2);
+                name.chars().count() + ty_width + 3 + value.chars().count()
+            }
+        }
+    }
+}
+
+/// Variables of the scopes of a stack frame. A data breakpoint on the selected variable is
+/// toggled with `b` and its memory is shown with `m`.
+pub struct DebugVariablesPanel {
+    frame_id: usize,
+    rows: Vec<Row>,
+    cursor: PanelCursor,
+}
+
+impl DebugVariablesPanel {
+    pub const ID: &'static str = "dap-variables";
+
+    pub fn new(frame_id: usize, scopes: Vec<(Scope, Vec<Variable>)>) -> Self {
+        let mut rows = Vec::new();
+        for (scope, variables) in scopes {
+            rows.push(Row::Scope(scope.name));
+            rows.extend(variables.into_iter().map(|variable| Row::Variable {
+                container: scope.variables_reference,
+                name: variable.name,
+                ty: variable.ty,
+                value: variable.value,
+                memory_reference: variable.memory_reference,
+            }));
+        }
+        // Start on the first variable rather than on a scope header.
+        let cursor = rows
+            .iter()
+            .position(|row| matches!(row, Row::Variable { .. }))
+            .unwrap_or(0);
+        Self {
+            frame_id,
+            rows,
+            cursor: PanelCursor::new(cursor),
+        }
+    }
+
+    fn toggle_data_breakpoint(&self, cx: &mut Context) {
+        let Some(Row::Variable {
+            container, name, ..
+        }) = self.rows.get(self.cursor.cursor)
+        else {
+            return;
+        };
+        match helix_view::handlers::dap::toggle_data_breakpoint(
+            cx.editor,
+            *container,
+            name.clone(),
+            Some(self.frame_id),
+        ) {
+            Ok(true) => cx
+                .editor
+                .set_status(format!("Data breakpoint set on {name}")),
+            Ok(false) => cx
+                .editor
+                .set_status(format!("Data breakpoint removed from {name}")),
+            Err(err) => cx
+                .editor
+                .set_error(format!("Failed to toggle data breakpoint: {err}")),
+        }
+    }
+
+    /// Opens the memory view for the selected variable, returning whether it has been opened.
+    fn open_memory_view(&self, cx: &mut Context) -> bool {
+        let Some(Row::Variable {
+            name,
+            memory_reference,
+            ..
+        }) = self.rows.get(self.cursor.cursor)
+        else {
+            return false;
+        };
+        let Some(memory_reference) = memory_reference else {
+            cx.editor
+                .set_error(format!("{name} has no memory reference"));
+            return false;
+        };
+        cx.editor
+            .open_memory_view(Action::VerticalSplit, memory_reference.clone());
+        crate::handlers::dap::refresh_memory_view(cx.editor);
+        true
+    }
+}
+
+impl Component for DebugVariablesPanel {
+    fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored(None);
+        };
+
+        if self.cursor.handle_key(*key, self.rows.len()) {
+            return EventResult::Consumed(None);
+        }
+        match *key {
+            key!('b') => self.toggle_data_breakpoint(cx),
+            key!('m') => {
+                if !self.open_memory_view(cx) {
+                    return EventResult::Consumed(None);
+                }
+                // Close the popup so that the memory view is visible.
+                return EventResult::Consumed(Some(Box::new(|compositor: &mut Compositor, _| {
+                    compositor.remove(DebugVariablesPanel::ID);
+                })));
+            }
+            _ => return EventResult::Ignored(None),
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
+        let theme = &cx.editor.theme;
+        let scope_style = theme.get("ui.linenr.selected");
+        let type_style = theme.get("ui.text");
+        let text_style = theme.get("ui.text.focus");
+        let selected_style = theme.get("ui.menu.selected");
+
+        self.cursor
+            .render(&self.rows, area, surface, selected_style, |row| match row {
+                Row::Scope(name) => vec![Span::styled(format!("▸ {name}"), scope_style)],
+                Row::Variable {
+                    name, ty, value, ..
+                } => {
+                    let mut spans = vec![Span::styled(name.as_str(), text_style)];
+                    if let Some(ty) = ty {
+                        spans.push(Span::raw(": "));
+                        spans.push(Span::styled(ty.as_str(), type_style));
+                    }
+                    spans.push(Span::raw(" = "));
+                    spans.push(Span::styled(value.as_str(), text_style));
+                    spans
+                }
+            });
+    }
+
+    fn required_size(&mut self, viewport: (u16, u16)) -> Option<(u16, u16)> {
+        debug_panel::required_size(&self.rows, Row::width, 0, viewport)
+    }
+}
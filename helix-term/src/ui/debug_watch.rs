@@ -0,0 +1,254 @@
+use super::debug_panel::{self, PanelCursor};
+use crate::{
+    compositor::{Component, Context, EventResult},
+    key,
+};
+use helix_lsp::block_on;
+use helix_view::{
+    editor::{DebugWatch, WatchValue},
+    graphics::Rect,
+    input::Event,
+    Editor,
+};
+use tui::{buffer::Buffer as Surface, text::Span};
+
+/// A single line of the watch panel: a watch expression or one of the children of its value.
+struct Row {
+    /// Index of the watch followed by the indices of the children leading to this row.
+    path: Vec<usize>,
+    label: String,
+    value: Result<String, String>,
+    ty: Option<String>,
+    expandable: bool,
+    expanded: bool,
+}
+
+impl Row {
+    fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    fn width(&self) -> usize {
+        let value_width = match &self.value {
+            Ok(value) | Err(value) => value.chars().count(),
+        };
+        let ty_width = self.ty.as_ref().map_or(0, |ty| ty.chars().count() + 2);
+        self.depth() * 2 + 2 + self.label.chars().count() + ty_width + 3 + value_width
+    }
+}
+
+/// Tree of the watch expressions of the editor and their (structured) values.
+///
+/// The values are re-evaluated by the editor whenever the debuggee stops, the panel only
+/// displays them and expands or collapses children.
+pub struct DebugWatchPanel {
+    rows: Vec<Row>,
+    cursor: PanelCursor,
+}
+
+impl DebugWatchPanel {
+    pub const ID: &'static str = "dap-watches";
+
+    pub fn new(editor: &Editor) -> Self {
+        let mut panel = Self {
+            rows: Vec::new(),
+            cursor: PanelCursor::new(0),
+        };
+        panel.update_rows(editor);
+        panel
+    }
+
+    fn update_rows(&mut self, editor: &Editor) {
+        fn push_children(rows: &mut Vec<Row>, path: &[usize], value: &WatchValue) {
+            if !value.expanded {
+                return;
+            }
+            for (idx, (name, child)) in value.children.iter().enumerate() {
+                let mut path = path.to_vec();
+                path.push(idx);
+                rows.push(Row {
+                    path: path.clone(),
+                    label: name.clone(),
+                    value: Ok(child.value.clone()),
+                    ty: child.ty.clone(),
+                    expandable: child.has_children(),
+                    expanded: child.expanded,
+                });
+                push_children(rows, &path, child);
+            }
+        }
+
+        self.rows.clear();
+        for (idx, watch) in editor.debug_watches.iter().enumerate() {
+            let (value, ty, expandable, expanded) = match &watch.value {
+                Some(Ok(value)) => (
+                    Ok(value.value.clone()),
+                    value.ty.clone(),
+                    value.has_children(),
+                    value.expanded,
+                ),
+                Some(Err(err)) => (Err(err.clone()), None, false, false),
+                None => (Ok("<not evaluated>".to_string()), None, false, false),
+            };
+            self.rows.push(Row {
+                path: vec![idx],
+                label: watch.expression.clone(),
+                value,
+                ty,
+                expandable,
+                expanded,
+            });
+            if let Some(Ok(value)) = &watch.value {
+                push_children(&mut self.rows, &[idx], value);
+            }
+        }
+        self.cursor.cursor = self.cursor.cursor.min(self.rows.len().saturating_sub(1));
+    }
+
+    fn value_mut<'a>(watches: &'a mut [DebugWatch], path: &[usize]) -> Option<&'a mut WatchValue> {
+        let (first, rest) = path.split_first()?;
+        let mut value = watches.get_mut(*first)?.value.as_mut()?.as_mut().ok()?;
+        for idx in rest {
+            value = &mut value.children.get_mut(*idx)?.1;
+        }
+        Some(value)
+    }
+
+    fn set_expanded(&mut self, editor: &mut Editor, expanded: bool) {
+        let Some(row) = self.rows.get(self.cursor.cursor) else {
+            return;
+        };
+        if !row.expandable {
+            return;
+        }
+        let path = row.path.clone();
+        let Some(value) = Self::value_mut(&mut editor.debug_watches, &path) else {
+            return;
+        };
+        if expanded && value.children.is_empty() {
+            let Some(debugger) = editor.debug_adapters.get_active_client() else {
+                return;
+            };
+            if let Err(err) = block_on(helix_view::handlers::dap::fetch_watch_children(
+                debugger, value,
+            )) {
+                editor.set_error(format!("Failed to get variables: {err}"));
+                return;
+            }
+        }
+        value.expanded = expanded;
+        self.update_rows(editor);
+    }
+
+    fn remove_watch(&mut self, editor: &mut Editor) {
+        let Some(row) = self.rows.get(self.cursor.cursor) else {
+            return;
+        };
+        if row.depth() == 0 {
+            editor.debug_watches.remove(row.path[0]);
+            self.update_rows(editor);
+        }
+    }
+
+    fn move_to_parent(&mut self) {
+        let Some(row) = self.rows.get(self.cursor.cursor) else {
+            return;
+        };
+        if let Some((_, parent)) = row.path.split_last() {
+            if let Some(idx) = self.rows.iter().position(|row| row.path == parent) {
+                self.cursor.cursor = idx;
+            }
+        }
+    }
+}
+
+impl Component for DebugWatchPanel {
+    fn handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored(None);
+        };
+
+        if self.cursor.handle_key(*key, self.rows.len()) {
+            return EventResult::Consumed(None);
+        }
+        match *key {
+            key!('l') | key!(Right) | key!(Enter) | key!(Tab) => {
+                let expanded = self
+                    .rows
+                    .get(self.cursor.cursor)
+                    .is_some_and(|row| row.expanded);
+                self.set_expanded(cx.editor, !expanded);
+            }
+            key!('h') | key!(Left) => {
+                if self
+                    .rows
+                    .get(self.cursor.cursor)
+                    .is_some_and(|row| row.expanded)
+                {
+                    self.set_expanded(cx.editor, false);
+                } else {
+                    self.move_to_parent();
+                }
+            }
+            key!('d') => self.remove_watch(cx.editor),
+            _ => return EventResult::Ignored(None),
+        }
+        EventResult::Consumed(None)
+    }
+
+    fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
+        // The values change whenever the debuggee stops.
+        self.update_rows(cx.editor);
+
+        let theme = &cx.editor.theme;
+        let text_style = theme.get("ui.text");
+        let label_style = theme.get("variable");
+        let type_style = theme.get("type");
+        let error_style = theme.get("error");
+        let selected_style = theme.get("ui.menu.selected");
+
+        if self.rows.is_empty() {
+            surface.set_stringn(
+                area.x,
+                area.y,
+                "No watch expressions",
+                area.width as usize,
+                text_style,
+            );
+            return;
+        }
+
+        self.cursor
+            .render(&self.rows, area, surface, selected_style, |row| {
+                let marker = match (row.expandable, row.expanded) {
+                    (true, true) => "▾ ",
+                    (true, false) => "▸ ",
+                    (false, _) => "  ",
+                };
+                let mut spans = vec![
+                    Span::raw("  ".repeat(row.depth())),
+                    Span::styled(marker, text_style),
+                    Span::styled(row.label.as_str(), label_style),
+                ];
+                if let Some(ty) = &row.ty {
+                    spans.push(Span::styled(": ", text_style));
+                    spans.push(Span::styled(ty.as_str(), type_style));
+                }
+                spans.push(Span::styled(" = ", text_style));
+                match &row.value {
+                    Ok(value) => spans.push(Span::styled(value.as_str(), text_style)),
+                    Err(err) => spans.push(Span::styled(err.as_str(), error_style)),
+                }
+                spans
+            });
+    }
+
+    fn required_size(&mut self, viewport: (u16, u16)) -> Option<(u16, u16)> {
+        debug_panel::required_size(
+            &self.rows,
+            Row::width,
+            "No watch expressions".len(),
+            viewport,
+        )
+    }
+}
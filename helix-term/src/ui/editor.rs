@@ -8,7 +8,7 @@
     ui::{
         document::{render_document, LinePos, TextRenderer},
         statusline,
-        text_decorations::{self, Decoration, DecorationManager, InlineDiagnostics},
+        text_decorations::{self, Decoration, DecorationManager, InlineBlame, InlineDiagnostics},
         Completion, ProgressSpinners,
     },
 };
@@ -23,15 +23,15 @@
     visual_offset_from_block, Change, Position, Range, Selection, Transaction,
 };
 use helix_view::{
-    annotations::diagnostics::DiagnosticFilter,
-    document::{Mode, SCRATCH_BUFFER_NAME},
+    annotations::{diagnostics::DiagnosticFilter, diff_split::LineChange},
+    document::Mode,
     editor::{CompleteAction, CursorShapeConfig},
     graphics::{Color, CursorKind, Modifier, Rect, Style},
     input::{KeyEvent, MouseButton, MouseEvent, MouseEventKind},
     keyboard::{KeyCode, KeyModifiers},
     Document, Editor, Theme, View,
 };
-use std::{mem::take, num::NonZeroUsize, ops, path::PathBuf, rc::Rc};
+use std::{mem::take, num::NonZeroUsize, ops, rc::Rc};
 
 use tui::{buffer::Buffer as Surface, text::Span};
 
@@ -102,14 +102,75 @@ pub fn render_view(
             Self::highlight_cursorcolumn(doc, view, surface, theme, inner, &text_annotations);
         }
 
-        // Set DAP highlights, if needed.
-        if let Some(frame) = editor.current_stack_frame() {
-            let dap_line = frame.line.saturating_sub(1);
+        // Set DAP highlights for the frames of the current stack, if needed.
+        let frame_lines = doc
+            .path()
+            .map(|path| editor.stack_frame_lines(path))
+            .unwrap_or_default();
+        if !frame_lines.is_empty() {
             let style = theme.get("ui.highlight.frameline");
+            let caller_style = theme.get("ui.highlight.frameline.caller");
             let line_decoration = move |renderer: &mut TextRenderer, pos: LinePos| {
-                if pos.doc_line != dap_line {
+                let Some(active) = frame_lines
+                    .iter()
+                    .filter(|(line, _)| *line == pos.doc_line)
+                    .map(|(_, active)| *active)
+                    .reduce(|a, b| a || b)
+                else {
                     return;
-                }
+                };
+                let style = if active { style } else { caller_style };
+                renderer.set_style(Rect::new(inner.x, pos.visual_line, inner.width, 1), style);
+            };
+
+            decorations.add_decoration(line_decoration);
+        }
+
+        if let Some(lines) = doc.diff_split_lines(view.id) {
+            let plus = theme.get("ui.highlight.diff.plus");
+            let minus = theme.get("ui.highlight.diff.minus");
+            let delta = theme.get("ui.highlight.diff.delta");
+            let line_decoration = move |renderer: &mut TextRenderer, pos: LinePos| {
+                let style = match lines.line_change(pos.doc_line) {
+                    Some(LineChange::Added) => plus,
+                    Some(LineChange::Removed) => minus,
+                    Some(LineChange::Modified) => delta,
+                    None => return,
+                };
+                renderer.set_style(Rect::new(inner.x, pos.visual_line, inner.width, 1), style);
+            };
+
+            decorations.add_decoration(line_decoration);
+        }
+
+        let conflicts = doc.conflicts();
+        if !conflicts.is_empty() {
+            let marker = theme.get("ui.highlight.conflict.marker");
+            let ours = theme.get("ui.highlight.conflict.ours");
+            let base = theme.get("ui.highlight.conflict.base");
+            let theirs = theme.get("ui.highlight.conflict.theirs");
+            let line_decoration = move |renderer: &mut TextRenderer, pos: LinePos| {
+                let line = pos.doc_line;
+                let idx = conflicts.partition_point(|conflict| conflict.lines.end <= line);
+                let Some(conflict) = conflicts
+                    .get(idx)
+                    .filter(|conflict| conflict.lines.contains(&line))
+                else {
+                    return;
+                };
+                let style = if conflict.ours.contains(&line) {
+                    ours
+                } else if conflict.theirs.contains(&line) {
+                    theirs
+                } else if conflict
+                    .base
+                    .as_ref()
+                    .is_some_and(|lines| lines.contains(&line))
+                {
+                    base
+                } else {
+                    marker
+                };
                 renderer.set_style(Rect::new(inner.x, pos.visual_line, inner.width, 1), style);
             };
 
@@ -141,6 +202,10 @@ pub fn render_view(
 
         Self::doc_diagnostics_highlights_into(doc, theme, &mut overlays);
 
+        if config.lsp.auto_document_highlight {
+            Self::doc_document_highlights_into(doc, view, theme, &mut overlays);
+        }
+
         if is_focused {
             if let Some(tabstops) = Self::tabstop_highlights(doc, theme) {
                 overlays.push(tabstops);
@@ -196,6 +261,12 @@ pub fn render_view(
             inline_diagnostic_config,
             config.end_of_line_diagnostics,
         ));
+        if is_focused && config.inline_blame {
+            let cursor_line = doc.text().char_to_line(primary_cursor);
+            if let Some(blame) = InlineBlame::new(doc, theme, cursor_line) {
+                decorations.add_decoration(blame);
+            }
+        }
         render_document(
             surface,
             inner,
@@ -459,6 +530,34 @@ pub fn doc_diagnostics_highlights_into(
         ]);
     }
 
+    /// Get highlights for the occurrences of the symbol under the cursor.
+    pub fn doc_document_highlights_into(
+        doc: &Document,
+        view: &View,
+        theme: &Theme,
+        overlay_highlights: &mut Vec<OverlayHighlights>,
+    ) {
+        let Some(highlights) = doc.document_highlights(view.id) else {
+            return;
+        };
+
+        for (scope, ranges) in [
+            ("ui.highlight.text", &highlights.text),
+            ("ui.highlight.read", &highlights.read),
+            ("ui.highlight.write", &highlights.write),
+        ] {
+            if ranges.is_empty() {
+                continue;
+            }
+            if let Some(highlight) = theme.find_highlight(scope) {
+                overlay_highlights.push(OverlayHighlights::Homogeneous {
+                    highlight,
+                    ranges: ranges.clone(),
+                });
+            }
+        }
+    }
+
     /// Get highlight spans for selections in a document view.
     pub fn doc_selection_highlights(
         mode: Mode,
@@ -593,7 +692,6 @@ pub fn tabstop_highlights(doc: &Document, theme: &Theme) -> Option<OverlayHighli
 
     /// Render bufferline at the top
     pub fn render_bufferline(editor: &Editor, viewport: Rect, surface: &mut Surface) {
-        let scratch = PathBuf::from(SCRATCH_BUFFER_NAME); // default filename to use for scratch buffer
         surface.clear_with(
             viewport,
             editor
@@ -616,13 +714,14 @@ pub fn render_bufferline(editor: &Editor, viewport: Rect, surface: &mut Surface)
         let current_doc = view!(editor).doc;
 
         for doc in editor.documents() {
-            let fname = doc
-                .path()
-                .unwrap_or(&scratch)
-                .file_name()
-                .unwrap_or_default()
-                .to_str()
-                .unwrap_or_default();
+            let fname = match doc.path() {
+                Some(path) => path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_str()
+                    .unwrap_or_default(),
+                None => doc.scratch_name(),
+            };
 
             let style = if current_doc == doc.id() {
                 bufferline_active
@@ -1544,6 +1643,8 @@ fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
         // if the terminal size suddenly changed, we need to trigger a resize
         cx.editor.resize(editor_area);
 
+        cx.editor.sync_diff_split();
+
         if use_bufferline {
             Self::render_bufferline(cx.editor, area.with_height(1), surface);
         }
@@ -1,4 +1,7 @@
 mod completion;
+mod debug_panel;
+mod debug_variables;
+mod debug_watch;
 mod document;
 pub(crate) mod editor;
 mod info;
@@ -17,9 +20,13 @@
 use crate::compositor::Compositor;
 use crate::filter_picker_entry;
 use crate::job::{self, Callback};
+use arc_swap::ArcSwap;
 pub use completion::Completion;
+pub use debug_variables::DebugVariablesPanel;
+pub use debug_watch::DebugWatchPanel;
 pub use editor::EditorView;
 use helix_stdx::rope;
+use helix_vcs::{FileStatus, StatusSnapshot};
 use helix_view::theme::Style;
 pub use markdown::Markdown;
 pub use menu::Menu;
@@ -33,6 +40,7 @@
 use tui::text::{Span, Spans};
 
 use std::path::Path;
+use std::sync::Arc;
 use std::{error::Error, path::PathBuf};
 
 struct Utf8PathBuf {
@@ -185,10 +193,52 @@ pub fn raw_regex_prompt(
     cx.push_layer(Box::new(prompt));
 }
 
-#[derive(Debug)]
+/// The cached version control status of files, displayed in the `status` column of the file
+/// and buffer pickers.
+pub struct VcsStatus {
+    snapshot: Arc<ArcSwap<StatusSnapshot>>,
+    untracked: Style,
+    modified: Style,
+    conflict: Style,
+    deleted: Style,
+    renamed: Style,
+}
+
+impl VcsStatus {
+    /// Reads the cached status, which is replaced by
+    /// [`DiffProviderRegistry::refresh_status`](helix_vcs::DiffProviderRegistry::refresh_status).
+    /// Pickers inject their items again once a refresh finishes so that they are filtered by the
+    /// new status.
+    pub fn new(editor: &Editor) -> Self {
+        Self {
+            snapshot: editor.diff_providers.status(),
+            untracked: editor.theme.get("diff.plus"),
+            modified: editor.theme.get("diff.delta"),
+            conflict: editor.theme.get("diff.delta.conflict"),
+            deleted: editor.theme.get("diff.minus"),
+            renamed: editor.theme.get("diff.delta.moved"),
+        }
+    }
+
+    pub fn format(&self, path: &Path) -> Span<'static> {
+        let Some(status) = self.snapshot.load().get(path) else {
+            return Span::raw("");
+        };
+        let style = match status {
+            FileStatus::Untracked => self.untracked,
+            FileStatus::Modified => self.modified,
+            FileStatus::Conflict => self.conflict,
+            FileStatus::Deleted => self.deleted,
+            FileStatus::Renamed => self.renamed,
+        };
+        Span::styled(status.as_str(), style)
+    }
+}
+
 pub struct FilePickerData {
     root: PathBuf,
     directory_style: Style,
+    status: VcsStatus,
 }
 type FilePicker = Picker<PathBuf, FilePickerData>;
 
@@ -200,6 +250,7 @@ pub fn file_picker(editor: &Editor, root: PathBuf) -> FilePicker {
     let data = FilePickerData {
         root: root.clone(),
         directory_style: editor.theme.get("ui.text.directory"),
+        status: VcsStatus::new(editor),
     };
 
     let now = Instant::now();
@@ -245,9 +296,11 @@ pub fn file_picker(editor: &Editor, root: PathBuf) -> FilePicker {
     });
     log::debug!("file_picker init {:?}", Instant::now().duration_since(now));
 
-    let columns = [PickerColumn::new(
-        "path",
-        |item: &PathBuf, data: &FilePickerData| {
+    let columns = [
+        PickerColumn::new("status", |item: &PathBuf, data: &FilePickerData| {
+            data.status.format(item).into()
+        }),
+        PickerColumn::new("path", |item: &PathBuf, data: &FilePickerData| {
             let path = item.strip_prefix(&data.root).unwrap_or(item);
             let mut spans = Vec::with_capacity(3);
             if let Some(dirs) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
@@ -262,9 +315,9 @@ pub fn file_picker(editor: &Editor, root: PathBuf) -> FilePicker {
                 .to_string_lossy();
             spans.push(Span::raw(filename));
             Spans::from(spans).into()
-        },
-    )];
-    let picker = Picker::new(columns, 0, [], data, move |cx, path: &PathBuf, action| {
+        }),
+    ];
+    let picker = Picker::new(columns, 1, [], data, move |cx, path: &PathBuf, action| {
         if let Err(e) = cx.editor.open(path, action) {
             let err = if let Some(err) = e.source() {
                 format!("{}", err)
@@ -276,27 +329,52 @@ pub fn file_picker(editor: &Editor, root: PathBuf) -> FilePicker {
     })
     .with_preview(|_editor, path| Some((path.as_path().into(), None)));
     let injector = picker.injector();
+    let refresh_status = editor.diff_providers.refresh_status(root);
     let timeout = std::time::Instant::now() + std::time::Duration::from_millis(30);
 
+    // the injected files are kept to inject them again once the status is refreshed
+    let mut injected = Vec::new();
     let mut hit_timeout = false;
     for file in &mut files {
-        if injector.push(file).is_err() {
+        if injector.push(file.clone()).is_err() {
             break;
         }
+        injected.push(file);
         if std::time::Instant::now() >= timeout {
             hit_timeout = true;
             break;
         }
     }
+    let (injected_tx, injected_rx) = tokio::sync::oneshot::channel();
     if hit_timeout {
         std::thread::spawn(move || {
             for file in files {
-                if injector.push(file).is_err() {
-                    break;
+                if injector.push(file.clone()).is_err() {
+                    return;
                 }
+                injected.push(file);
             }
+            let _ = injected_tx.send(injected);
         });
+    } else {
+        let _ = injected_tx.send(injected);
     }
+    // the files are filtered by the status they were injected with
+    tokio::spawn(async move {
+        let (_, injected) = futures_util::future::join(refresh_status, injected_rx).await;
+        let Ok(files) = injected else {
+            return;
+        };
+        job::dispatch(move |_editor, compositor| {
+            if let Some(overlay::Overlay {
+                content: picker, ..
+            }) = compositor.find::<overlay::Overlay<FilePicker>>()
+            {
+                picker.set_items(files);
+            }
+        })
+        .await;
+    });
     picker
 }
 
@@ -374,7 +452,6 @@ pub mod completers {
     use helix_core::command_line::{self, Tokenizer};
     use helix_core::fuzzy::fuzzy_match;
     use helix_core::syntax::config::LanguageServerFeature;
-    use helix_view::document::SCRATCH_BUFFER_NAME;
     use helix_view::theme;
     use helix_view::{editor::Config, Editor};
     use once_cell::sync::Lazy;
@@ -392,7 +469,7 @@ pub fn buffer(editor: &Editor, input: &str) -> Vec<Completion> {
         let names = editor.documents.values().map(|doc| {
             doc.relative_path()
                 .map(|p| p.display().to_string().into())
-                .unwrap_or_else(|| Cow::from(SCRATCH_BUFFER_NAME))
+                .unwrap_or_else(|| Cow::from(doc.scratch_name().to_string()))
         });
 
         fuzzy_match(input, names, true)
@@ -458,6 +535,57 @@ pub fn configured_language_servers(editor: &Editor, input: &str) -> Vec<Completi
             .collect()
     }
 
+    /// Completes the workspace folders of all running language servers.
+    pub fn lsp_workspace_folder(editor: &Editor, input: &str) -> Vec<Completion> {
+        let mut folders: Vec<String> = editor
+            .language_servers
+            .workspace_folders()
+            .into_iter()
+            .chain(
+                editor
+                    .language_servers
+                    .iter_clients()
+                    .flat_map(|ls| ls.workspace_folders().clone())
+                    .map(|workspace| workspace.uri),
+            )
+            .filter_map(|uri| uri.to_file_path().ok())
+            .map(|path| path.display().to_string())
+            .collect();
+        folders.sort_unstable();
+        folders.dedup();
+
+        fuzzy_match(input, folders, false)
+            .into_iter()
+            .map(|(name, _)| ((0..), Span::raw(name)))
+            .collect()
+    }
+
+    pub fn language_server_trace(_editor: &Editor, input: &str) -> Vec<Completion> {
+        fuzzy_match(input, ["off", "messages", "verbose"], false)
+            .into_iter()
+            .map(|(name, _)| ((0..), Span::raw(name)))
+            .collect()
+    }
+
+    pub fn debug_watch(editor: &Editor, input: &str) -> Vec<Completion> {
+        let expressions = editor
+            .debug_watches
+            .iter()
+            .map(|watch| watch.expression.as_str());
+
+        fuzzy_match(input, expressions, false)
+            .into_iter()
+            .map(|(name, _)| ((0..), Span::raw(name.to_string())))
+            .collect()
+    }
+
+    pub fn debug_output_category(_editor: &Editor, input: &str) -> Vec<Completion> {
+        fuzzy_match(input, ["console", "important", "stdout", "stderr"], false)
+            .into_iter()
+            .map(|(name, _)| ((0..), Span::raw(name)))
+            .collect()
+    }
+
     pub fn setting(_editor: &Editor, input: &str) -> Vec<Completion> {
         static KEYS: Lazy<Vec<String>> = Lazy::new(|| {
             let mut keys = Vec::new();
@@ -47,12 +47,15 @@
 use helix_view::{
     editor::Action,
     graphics::{CursorKind, Margin, Modifier, Rect},
+    input::KeyEvent,
     theme::Style,
     view::ViewPosition,
     Document, DocumentId, Editor,
 };
 
-use self::handlers::{DynamicQueryChange, DynamicQueryHandler, PreviewHighlightHandler};
+use self::handlers::{
+    DynamicQueryChange, DynamicQueryHandler, PreviewHighlightHandler, PreviewTextHandler,
+};
 
 pub const ID: &str = "picker";
 
@@ -64,6 +67,9 @@
 pub enum PathOrId<'a> {
     Id(DocumentId),
     Path(&'a Path),
+    /// Text that is not stored in a file, identified by a name. The text is generated by the
+    /// function passed to [`Picker::with_text_preview`].
+    Text(&'a str),
 }
 
 impl<'a> From<&'a Path> for PathOrId<'a> {
@@ -79,6 +85,7 @@ fn from(v: DocumentId) -> Self {
 }
 
 type FileCallback<T> = Box<dyn for<'a> Fn(&'a Editor, &'a T) -> Option<FileLocation<'a>>>;
+type TextCallback = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
 
 /// File path and range of lines (used to align and highlight lines)
 pub type FileLocation<'a> = (PathOrId<'a>, Option<(usize, usize)>);
@@ -89,6 +96,8 @@ pub enum CachedPreview {
     Binary,
     LargeFile,
     NotFound,
+    /// The text of a [`PathOrId::Text`] preview that is still being generated.
+    Loading,
 }
 
 // We don't store this enum in the cache so as to avoid lifetime constraints
@@ -124,6 +133,7 @@ fn placeholder(&self) -> &str {
                 CachedPreview::Binary => "<Binary file>",
                 CachedPreview::LargeFile => "<File too large to preview>",
                 CachedPreview::NotFound => "<File not found>",
+                CachedPreview::Loading => "<Loading preview>",
             },
         }
     }
@@ -266,6 +276,14 @@ pub struct Picker<T: 'static + Send + Sync, D: 'static> {
     read_buffer: Vec<u8>,
     /// Given an item in the picker, return the file path and line number to display.
     file_fn: Option<FileCallback<T>>,
+    /// Caches the previews of [`PathOrId::Text`] locations by name
+    text_preview_cache: HashMap<Arc<str>, CachedPreview>,
+    /// Generates the text of [`PathOrId::Text`] previews and the language to highlight it as.
+    text_fn: Option<(&'static str, TextCallback)>,
+    /// An event handler for generating the text of the currently previewed name.
+    preview_text_handler: Option<Sender<Arc<str>>>,
+    /// Additional actions run on the selected item when their key is pressed.
+    key_actions: Vec<(KeyEvent, KeyActionCallback<T>)>,
     /// An event handler for syntax highlighting the currently previewed file.
     preview_highlight_handler: Sender<Arc<Path>>,
     dynamic_query_handler: Option<Sender<DynamicQueryChange>>,
@@ -392,6 +410,10 @@ fn with(
             preview_cache: HashMap::new(),
             read_buffer: Vec::with_capacity(1024),
             file_fn: None,
+            text_preview_cache: HashMap::new(),
+            text_fn: None,
+            preview_text_handler: None,
+            key_actions: Vec::new(),
             preview_highlight_handler: PreviewHighlightHandler::<T, D>::default().spawn(),
             dynamic_query_handler: None,
         }
@@ -408,6 +430,20 @@ pub fn injector(&self) -> Injector<T, D> {
         }
     }
 
+    /// Replaces the items of the picker, for example to format their columns again once the
+    /// data they are formatted from changed.
+    pub fn set_items(&mut self, items: impl IntoIterator<Item = T>) {
+        // stop streaming the old items in the background
+        self.version.fetch_add(1, atomic::Ordering::Relaxed);
+        self.matcher.restart(false);
+        let injector = self.injector();
+        for item in items {
+            if injector.push(item).is_err() {
+                break;
+            }
+        }
+    }
+
     pub fn truncate_start(mut self, truncate_start: bool) -> Self {
         self.truncate_start = truncate_start;
         self
@@ -424,6 +460,28 @@ pub fn with_preview(
         self
     }
 
+    /// Generates the previews of [`PathOrId::Text`] locations, highlighted as `language`. The
+    /// text is generated in a background thread once per name and cached.
+    pub fn with_text_preview(
+        mut self,
+        language: &'static str,
+        text_fn: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.text_fn = Some((language, Arc::new(text_fn)));
+        self.preview_text_handler = Some(PreviewTextHandler::<T, D>::default().spawn());
+        self
+    }
+
+    /// Runs `action` on the selected item and closes the picker when `key` is pressed.
+    pub fn with_key_action(
+        mut self,
+        key: KeyEvent,
+        action: impl Fn(&mut Context, &T) + 'static,
+    ) -> Self {
+        self.key_actions.push((key, Box::new(action)));
+        self
+    }
+
     pub fn with_history_register(mut self, history_register: Option<char>) -> Self {
         self.prompt.with_history_register(history_register);
         self
@@ -674,6 +732,18 @@ fn get_preview<'picker, 'editor>(
                 let doc = editor.documents.get(&id).unwrap();
                 Some((Preview::EditorDocument(doc), range))
             }
+            PathOrId::Text(name) => {
+                let handler = self.preview_text_handler.as_ref()?;
+                let name: Arc<str> = name.into();
+                let preview = self
+                    .text_preview_cache
+                    .entry(name.clone())
+                    .or_insert(CachedPreview::Loading);
+                if matches!(preview, CachedPreview::Loading) {
+                    helix_event::send_blocking(handler, name);
+                }
+                Some((Preview::Cached(preview), range))
+            }
         }
     }
 
@@ -1064,6 +1134,13 @@ fn handle_event(&mut self, event: &Event, ctx: &mut Context) -> EventResult {
             EventResult::Consumed(Some(callback))
         };
 
+        if let Some((_, action)) = self.key_actions.iter().find(|(key, _)| *key == key_event) {
+            if let Some(option) = self.selection() {
+                action(ctx, option);
+            }
+            return close_fn(self);
+        }
+
         match key_event {
             shift!(Tab) | key!(Up) | ctrl!('p') => {
                 self.move_by(1, Direction::Backward);
@@ -1183,3 +1260,4 @@ fn drop(&mut self) {
 }
 
 type PickerCallback<T> = Box<dyn Fn(&mut Context, &T, Action)>;
+type KeyActionCallback<T> = Box<dyn Fn(&mut Context, &T)>;
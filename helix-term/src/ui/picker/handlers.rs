@@ -5,6 +5,7 @@
 };
 
 use helix_event::AsyncHook;
+use helix_view::Document;
 use tokio::time::Instant;
 
 use crate::{job, ui::overlay::Overlay};
@@ -112,6 +113,110 @@ fn finish_debounce(&mut self) {
     }
 }
 
+/// Generates the text of [`super::PathOrId::Text`] previews and highlights it in a background
+/// thread once the previewed name settles.
+pub(super) struct PreviewTextHandler<T: 'static + Send + Sync, D: 'static + Send + Sync> {
+    trigger: Option<Arc<str>>,
+    phantom_data: std::marker::PhantomData<(T, D)>,
+}
+
+impl<T: 'static + Send + Sync, D: 'static + Send + Sync> Default for PreviewTextHandler<T, D> {
+    fn default() -> Self {
+        Self {
+            trigger: None,
+            phantom_data: Default::default(),
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync, D: 'static + Send + Sync> AsyncHook for PreviewTextHandler<T, D> {
+    type Event = Arc<str>;
+
+    fn handle_event(&mut self, name: Self::Event, timeout: Option<Instant>) -> Option<Instant> {
+        if self
+            .trigger
+            .as_ref()
+            .is_some_and(|trigger| trigger == &name)
+        {
+            // If the name hasn't changed, don't reset the debounce
+            timeout
+        } else {
+            self.trigger = Some(name);
+            Some(Instant::now() + Duration::from_millis(150))
+        }
+    }
+
+    fn finish_debounce(&mut self) {
+        let Some(name) = self.trigger.take() else {
+            return;
+        };
+
+        job::dispatch_blocking(move |editor, compositor| {
+            let Some(Overlay {
+                content: picker, ..
+            }) = compositor.find::<Overlay<Picker<T, D>>>()
+            else {
+                return;
+            };
+
+            if !matches!(
+                picker.text_preview_cache.get(&name),
+                Some(CachedPreview::Loading)
+            ) {
+                return;
+            }
+
+            let Some((language, text_fn)) = picker.text_fn.clone() else {
+                return;
+            };
+            let loader = editor.syn_loader.load_full();
+
+            tokio::task::spawn_blocking(move || {
+                let text = text_fn(&name).map(|text| {
+                    let text = helix_core::Rope::from(text);
+                    let syntax = loader.language_for_name(language).and_then(|language| {
+                        helix_core::Syntax::new(text.slice(..), language, &loader)
+                            .inspect_err(|err| {
+                                log::info!("highlighting picker preview failed: {err}")
+                            })
+                            .ok()
+                            .map(|syntax| (language, syntax))
+                    });
+                    (text, syntax)
+                });
+
+                job::dispatch_blocking(move |editor, compositor| {
+                    let Some(Overlay {
+                        content: picker, ..
+                    }) = compositor.find::<Overlay<Picker<T, D>>>()
+                    else {
+                        log::info!("picker closed before the preview was generated");
+                        return;
+                    };
+                    let Some(preview) = picker.text_preview_cache.get_mut(&name) else {
+                        return;
+                    };
+                    let Some((text, syntax)) = text else {
+                        *preview = CachedPreview::NotFound;
+                        return;
+                    };
+                    let mut doc = Document::from(
+                        text,
+                        None,
+                        editor.config.clone(),
+                        editor.syn_loader.clone(),
+                    );
+                    if let Some((language, syntax)) = syntax {
+                        doc.language = Some(loader.language(language).config().clone());
+                        doc.syntax = Some(syntax);
+                    }
+                    *preview = CachedPreview::Document(Box::new(doc));
+                });
+            });
+        });
+    }
+}
+
 pub(super) struct DynamicQueryChange {
     pub query: Arc<str>,
     pub is_paste: bool,
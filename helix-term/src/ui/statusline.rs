@@ -2,12 +2,7 @@
 use helix_core::{coords_at_pos, encoding, unicode::width::UnicodeWidthStr, Position};
 use helix_lsp::lsp::DiagnosticSeverity;
 use helix_view::document::DEFAULT_LANGUAGE_NAME;
-use helix_view::{
-    document::{Mode, SCRATCH_BUFFER_NAME},
-    graphics::Rect,
-    theme::Style,
-    Document, Editor, View,
-};
+use helix_view::{document::Mode, graphics::Rect, theme::Style, Document, Editor, View};
 
 use crate::ui::ProgressSpinners;
 
@@ -157,6 +152,7 @@ fn get_render_function<'a, F>(element_id: StatusLineElementID) -> impl Fn(&mut R
         helix_view::editor::StatusLineElement::VersionControl => render_version_control,
         helix_view::editor::StatusLineElement::Register => render_register,
         helix_view::editor::StatusLineElement::CurrentWorkingDirectory => render_cwd,
+        helix_view::editor::StatusLineElement::Debug => render_debug,
     }
 }
 
@@ -451,7 +447,7 @@ fn render_file_name<'a, F>(context: &mut RenderContext<'a>, write: F)
         let path = rel_path
             .as_ref()
             .map(|p| p.to_string_lossy())
-            .unwrap_or_else(|| SCRATCH_BUFFER_NAME.into());
+            .unwrap_or_else(|| context.doc.scratch_name().into());
         format!(" {} ", path)
     };
 
@@ -467,7 +463,7 @@ fn render_file_absolute_path<'a, F>(context: &mut RenderContext<'a>, write: F)
         let path = path
             .as_ref()
             .map(|p| p.to_string_lossy())
-            .unwrap_or_else(|| SCRATCH_BUFFER_NAME.into());
+            .unwrap_or_else(|| context.doc.scratch_name().into());
         format!(" {} ", path)
     };
 
@@ -508,7 +504,7 @@ fn render_file_base_name<'a, F>(context: &mut RenderContext<'a>, write: F)
         let path = rel_path
             .as_ref()
             .and_then(|p| p.file_name().map(|s| s.to_string_lossy()))
-            .unwrap_or_else(|| SCRATCH_BUFFER_NAME.into());
+            .unwrap_or_else(|| context.doc.scratch_name().into());
         format!(" {} ", path)
     };
 
@@ -554,6 +550,30 @@ fn render_register<'a, F>(context: &mut RenderContext<'a>, write: F)
     }
 }
 
+fn render_debug<'a, F>(context: &mut RenderContext<'a>, write: F)
+where
+    F: Fn(&mut RenderContext<'a>, Span<'a>) + Copy,
+{
+    let Some(debugger) = context.editor.debug_adapters.get_active_client() else {
+        return;
+    };
+    let adapter = debugger
+        .config
+        .as_ref()
+        .map_or("debug", |config| config.name.as_str());
+
+    let content = match debugger.thread_id {
+        Some(thread_id) => match debugger.thread_states.get(&thread_id) {
+            Some(state) if state != "running" => {
+                format!(" {adapter}: thread {thread_id} stopped ({state}) ")
+            }
+            _ => format!(" {adapter}: thread {thread_id} "),
+        },
+        None => format!(" {adapter}: running "),
+    };
+    write(context, content.into());
+}
+
 fn render_file_indent_style<'a, F>(context: &mut RenderContext<'a>, write: F)
 where
     F: Fn(&mut RenderContext<'a>, Span<'a>) + Copy,
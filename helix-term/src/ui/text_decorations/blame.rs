@@ -0,0 +1,79 @@
+use helix_core::doc_formatter::FormattedGrapheme;
+use helix_core::Position;
+use helix_view::graphics::Style;
+use helix_view::{Document, Theme};
+
+use crate::ui::document::{LinePos, TextRenderer};
+use crate::ui::text_decorations::Decoration;
+
+/// Renders the commit that last changed the cursor line at the end of that line.
+pub struct InlineBlame {
+    /// The char idx of the end of the cursor line, the blame is drawn after it.
+    line_end: usize,
+    text: String,
+    style: Style,
+    at_line_end: bool,
+}
+
+impl InlineBlame {
+    pub fn new(doc: &Document, theme: &Theme, cursor_line: usize) -> Option<Self> {
+        let text = doc.line_blame(cursor_line)?.to_string();
+        Some(InlineBlame {
+            line_end: helix_core::line_ending::line_end_char_index(
+                &doc.text().slice(..),
+                cursor_line,
+            ),
+            text,
+            style: theme.get("ui.virtual.inline-blame"),
+            at_line_end: false,
+        })
+    }
+}
+
+impl Decoration for InlineBlame {
+    fn reset_pos(&mut self, pos: usize) -> usize {
+        self.at_line_end = false;
+        if pos <= self.line_end {
+            self.line_end
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn decorate_grapheme(
+        &mut self,
+        _renderer: &mut TextRenderer,
+        _grapheme: &FormattedGrapheme,
+    ) -> usize {
+        self.at_line_end = true;
+        usize::MAX
+    }
+
+    fn render_virt_lines(
+        &mut self,
+        renderer: &mut TextRenderer,
+        pos: LinePos,
+        virt_off: Position,
+    ) -> Position {
+        // soft wrapped lines render the blame after their last visual line only
+        if !std::mem::take(&mut self.at_line_end) {
+            return Position::new(0, 0);
+        }
+        let col = virt_off.col + 2;
+        if !renderer.column_in_bounds(col, 1) {
+            return Position::new(0, 0);
+        }
+        let draw_col = (col - renderer.offset.col) as u16;
+        let (end_col, _) = renderer.set_string_truncated(
+            renderer.viewport.x + draw_col,
+            pos.visual_line,
+            &self.text,
+            renderer.viewport.width.saturating_sub(draw_col) as usize,
+            |_| self.style,
+            true,
+            false,
+        );
+        let drawn = end_col.saturating_sub(renderer.viewport.x + draw_col);
+        Position::new(0, drawn as usize + 2)
+    }
+}
@@ -844,3 +844,76 @@ async fn global_search_with_multibyte_chars() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resolve_conflicts() -> anyhow::Result<()> {
+    let file = helpers::temp_file_with_contents(indoc! {"\
+        a
+        <<<<<<< ours
+        b
+        =======
+        c
+        >>>>>>> theirs
+        d
+        <<<<<<< ours
+        e
+        =======
+        f
+        >>>>>>> theirs
+        "})?;
+
+    test_key_sequence(
+        &mut AppBuilder::new().with_file(file.path(), None).build()?,
+        Some("]x]x:conflict-theirs<ret>[x:conflict-both<ret>"),
+        Some(&|app| {
+            let doc = helix_view::doc!(app.editor);
+            assert_eq!("a\nb\nc\nd\nf\n", doc.text());
+            assert!(doc.conflicts().is_empty());
+        }),
+        false,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_commit_message_warnings() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("COMMIT_EDITMSG");
+    std::fs::write(&file, "\n# comment\n")?;
+
+    test_key_sequence(
+        &mut AppBuilder::new().with_file(&file, None).build()?,
+        Some(&format!("i{}<ret>body<esc>", "x".repeat(60))),
+        Some(&|app| {
+            let doc = helix_view::doc!(app.editor);
+            let warnings: Vec<_> = doc
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| {
+                    (
+                        diagnostic.line,
+                        diagnostic.range.start,
+                        &*diagnostic.message,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                warnings,
+                [
+                    (0, 50, "Subject is longer than 50 characters"),
+                    (
+                        1,
+                        61,
+                        "Separate the subject from the body with a blank line"
+                    ),
+                ]
+            );
+        }),
+        false,
+    )
+    .await?;
+
+    Ok(())
+}
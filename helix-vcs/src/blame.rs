@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use crate::CommitInfo;
+
+/// The commits that last changed each line of a file at `HEAD`.
+#[derive(Debug, Default)]
+pub struct FileBlame {
+    /// One entry per line of the file at `HEAD`.
+    pub lines: Vec<Arc<CommitInfo>>,
+}
+
+impl FileBlame {
+    /// Returns the commit that last changed the given (zero-based) line at `HEAD`.
+    pub fn line(&self, line: usize) -> Option<&Arc<CommitInfo>> {
+        self.lines.get(line)
+    }
+}
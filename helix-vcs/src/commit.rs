@@ -0,0 +1,55 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The author, time and summary of a commit.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CommitInfo {
+    /// Abbreviated commit hash.
+    pub id: Box<str>,
+    pub author: Box<str>,
+    /// Author time in seconds since the unix epoch.
+    pub time: i64,
+    /// First line of the commit message.
+    pub summary: Box<str>,
+}
+
+impl CommitInfo {
+    /// Formats the author time relative to now, for example `3 days ago`.
+    pub fn relative_time(&self) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs() as i64);
+        format_relative_time(now.saturating_sub(self.time))
+    }
+}
+
+fn format_relative_time(seconds: i64) -> String {
+    const UNITS: [(i64, &str); 6] = [
+        (365 * 24 * 60 * 60, "year"),
+        (30 * 24 * 60 * 60, "month"),
+        (7 * 24 * 60 * 60, "week"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+    UNITS.iter().find(|(unit, _)| seconds >= *unit).map_or_else(
+        || "just now".to_string(),
+        |(unit, name)| {
+            let count = seconds / unit;
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{count} {name}{plural} ago")
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_relative_time;
+
+    #[test]
+    fn relative_time() {
+        assert_eq!(format_relative_time(5), "just now");
+        assert_eq!(format_relative_time(60), "1 minute ago");
+        assert_eq!(format_relative_time(3 * 24 * 60 * 60 + 5), "3 days ago");
+        assert_eq!(format_relative_time(800 * 24 * 60 * 60), "2 years ago");
+    }
+}
@@ -0,0 +1,105 @@
+use std::ops::Range;
+
+use helix_core::RopeSlice;
+
+/// The width of the subject line that git tooling expects.
+const SUBJECT_WIDTH: usize = 50;
+
+/// Lines starting with this are comments and stripped from the commit message by git.
+const COMMENT: &str = "#";
+/// Everything below this line, including the line, is removed by `git commit --verbose`.
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+/// A line of a commit message that breaks a formatting convention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageWarning {
+    pub line: usize,
+    /// The offending characters of the line.
+    pub columns: Range<usize>,
+    pub message: String,
+}
+
+/// Checks that the subject of the commit message in `text` is at most 50 characters wide, that it
+/// is followed by a blank line and that the body lines are at most `body_width` characters
+/// wide. Comments are ignored.
+pub fn check_commit_message(text: RopeSlice, body_width: usize) -> Vec<MessageWarning> {
+    let mut warnings = Vec::new();
+    let mut subject_line = None;
+    for (i, line) in text.lines().enumerate() {
+        let line = String::from(line);
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line == SCISSORS {
+            break;
+        }
+        if line.starts_with(COMMENT) {
+            continue;
+        }
+        let width = line.chars().count();
+        let Some(subject) = subject_line else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            subject_line = Some(i);
+            if width > SUBJECT_WIDTH {
+                warnings.push(MessageWarning {
+                    line: i,
+                    columns: SUBJECT_WIDTH..width,
+                    message: format!("Subject is longer than {SUBJECT_WIDTH} characters"),
+                });
+            }
+            continue;
+        };
+        if i == subject + 1 && !line.trim().is_empty() {
+            warnings.push(MessageWarning {
+                line: i,
+                columns: 0..width,
+                message: "Separate the subject from the body with a blank line".to_string(),
+            });
+        } else if width > body_width {
+            warnings.push(MessageWarning {
+                line: i,
+                columns: body_width..width,
+                message: format!("Body line is longer than {body_width} characters"),
+            });
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod test {
+    use helix_core::Rope;
+
+    use super::*;
+
+    #[test]
+    fn check() {
+        let long = "x".repeat(60);
+        let text = Rope::from(format!(
+            "# comment\n\n{long}\nbody\n\n{long}\n# {long}\n{SCISSORS}\n{long}\n"
+        ));
+        let warnings = check_commit_message(text.slice(..), 50);
+        assert_eq!(
+            warnings,
+            [
+                MessageWarning {
+                    line: 2,
+                    columns: 50..60,
+                    message: "Subject is longer than 50 characters".to_string(),
+                },
+                MessageWarning {
+                    line: 3,
+                    columns: 0..4,
+                    message: "Separate the subject from the body with a blank line".to_string(),
+                },
+                MessageWarning {
+                    line: 5,
+                    columns: 50..60,
+                    message: "Body line is longer than 50 characters".to_string(),
+                },
+            ]
+        );
+        let text = Rope::from("short\n\nbody\n");
+        assert_eq!(check_commit_message(text.slice(..), 72), []);
+    }
+}
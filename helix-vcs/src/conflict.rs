@@ -0,0 +1,150 @@
+use std::ops::Range;
+
+use helix_core::RopeSlice;
+
+/// A conflict left in a file by a merge, delimited by conflict markers:
+///
+/// ```text
+/// <<<<<<< ours
+/// our version
+/// ||||||| base
+/// common ancestor, only present with the `diff3` and `zdiff3` conflict styles
+/// =======
+/// their version
+/// >>>>>>> theirs
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// All lines of the conflict, including the markers.
+    pub lines: Range<usize>,
+    /// The lines of our version.
+    pub ours: Range<usize>,
+    /// The lines of the common ancestor.
+    pub base: Option<Range<usize>>,
+    /// The lines of their version.
+    pub theirs: Range<usize>,
+}
+
+/// Which versions a [`Conflict`] is resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+    /// Our version followed by their version.
+    Both,
+    /// The common ancestor.
+    Base,
+}
+
+impl Conflict {
+    /// Returns the text the conflict is replaced with when resolving it to `resolution`, or
+    /// `None` if the common ancestor was requested but is not part of the conflict.
+    pub fn resolve(&self, text: RopeSlice, resolution: Resolution) -> Option<String> {
+        let lines = |lines: &Range<usize>| {
+            text.slice(text.line_to_char(lines.start)..text.line_to_char(lines.end))
+        };
+        let resolved = match resolution {
+            Resolution::Ours => lines(&self.ours).into(),
+            Resolution::Theirs => lines(&self.theirs).into(),
+            Resolution::Both => {
+                let mut both = String::from(lines(&self.ours));
+                both.extend(lines(&self.theirs).chunks());
+                both
+            }
+            Resolution::Base => lines(self.base.as_ref()?).into(),
+        };
+        Some(resolved)
+    }
+}
+
+/// Returns whether `line` starts with a conflict marker made of `marker`. Markers are seven
+/// marker characters followed by the end of the line or a space and a label.
+fn is_marker(line: RopeSlice, marker: char) -> bool {
+    let mut chars = line.chars();
+    chars.by_ref().take(7).filter(|&c| c == marker).count() == 7
+        && matches!(chars.next(), None | Some(' ' | '\r' | '\n'))
+}
+
+/// Finds the conflicts in `text`, sorted by line. Unterminated conflicts are ignored.
+pub fn find_conflicts(text: RopeSlice) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    // the start of the current conflict and the start of the base and their version in it
+    let mut start = None;
+    let mut base = None;
+    let mut theirs = None;
+    for (i, line) in text.lines().enumerate() {
+        if is_marker(line, '<') {
+            (start, base, theirs) = (Some(i), None, None);
+            continue;
+        }
+        let Some(conflict_start) = start else {
+            continue;
+        };
+        match theirs {
+            None if base.is_none() && is_marker(line, '|') => base = Some(i + 1),
+            None if is_marker(line, '=') => theirs = Some(i + 1),
+            Some(theirs_start) if is_marker(line, '>') => {
+                conflicts.push(Conflict {
+                    lines: conflict_start..i + 1,
+                    ours: conflict_start + 1..base.unwrap_or(theirs_start) - 1,
+                    base: base.map(|base| base..theirs_start - 1),
+                    theirs: theirs_start..i,
+                });
+                (start, base, theirs) = (None, None, None);
+            }
+            _ => (),
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod test {
+    use helix_core::Rope;
+
+    use super::*;
+
+    #[test]
+    fn find() {
+        let text = Rope::from_str(
+            "a\n<<<<<<< HEAD\nb\n=======\nc\nd\n>>>>>>> branch\ne\n\
+             <<<<<<< ours\n||||||| base\nf\n=======\n>>>>>>>\n",
+        );
+        assert_eq!(
+            find_conflicts(text.slice(..)),
+            [
+                Conflict {
+                    lines: 1..7,
+                    ours: 2..3,
+                    base: None,
+                    theirs: 4..6,
+                },
+                Conflict {
+                    lines: 8..13,
+                    ours: 9..9,
+                    base: Some(10..11),
+                    theirs: 12..12,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignore_incomplete_markers() {
+        let text = Rope::from_str("<<<<<<<<\n<<<<<<< a\nb\n=======\n>>>>>>>> c\n");
+        assert_eq!(find_conflicts(text.slice(..)), []);
+        let text = Rope::from_str("<<<<<<< a\n<<<<<<< b\nc\n=======\nd\n>>>>>>> e\n");
+        assert_eq!(find_conflicts(text.slice(..))[0].lines, 1..6);
+    }
+
+    #[test]
+    fn resolve() {
+        let text = Rope::from_str("<<<<<<< a\nb\n||||||| c\nd\n=======\ne\n>>>>>>> f\n");
+        let text = text.slice(..);
+        let conflict = &find_conflicts(text)[0];
+        assert_eq!(conflict.resolve(text, Resolution::Ours).unwrap(), "b\n");
+        assert_eq!(conflict.resolve(text, Resolution::Theirs).unwrap(), "e\n");
+        assert_eq!(conflict.resolve(text, Resolution::Both).unwrap(), "b\ne\n");
+        assert_eq!(conflict.resolve(text, Resolution::Base).unwrap(), "d\n");
+    }
+}
@@ -1,9 +1,11 @@
+use std::cmp::Ordering;
 use std::iter::Peekable;
+use std::ops::Range;
 use std::sync::Arc;
 
 use helix_core::Rope;
 use helix_event::RenderLockGuard;
-use imara_diff::Algorithm;
+use imara_diff::{Algorithm, IndentHeuristic, IndentLevel, InternedInput, Interner};
 use parking_lot::{RwLock, RwLockReadGuard};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio::task::JoinHandle;
@@ -33,6 +35,9 @@ struct DiffInner {
     diff_base: Rope,
     doc: Rope,
     hunks: Vec<Hunk>,
+    /// The versions at which the texts were last changed, see [`Diff::version`].
+    base_version: u64,
+    doc_version: u64,
 }
 
 /// Representation of a diff that can be updated.
@@ -123,6 +128,35 @@ fn update_document_impl(
 // cap average line length to 128 for files with MAX_DIFF_LINES
 const MAX_DIFF_BYTES: usize = MAX_DIFF_LINES * 128;
 
+/// Computes the changes between two texts synchronously. Prefer a [DiffHandle] for texts that
+/// are edited, it diffs incrementally in the background.
+pub fn compute_hunks(before: &Rope, after: &Rope) -> Vec<Hunk> {
+    let mut input = InternedInput {
+        before: Vec::with_capacity(before.len_lines()),
+        after: Vec::with_capacity(after.len_lines()),
+        interner: Interner::new(before.len_lines() + after.len_lines()),
+    };
+    input.update_before(before.lines());
+    input.update_after(after.lines());
+    let mut diff = imara_diff::Diff::compute(ALGORITHM, &input);
+    diff.postprocess_with(
+        &input.before,
+        &input.after,
+        IndentHeuristic::new(|token| IndentLevel::for_ascii_line(input.interner[token].bytes(), 4)),
+    );
+    diff.hunks().collect()
+}
+
+/// Empty rows that are rendered after lines of the diff base and of the document,
+/// see [`Diff::filler_rows`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FillerRows {
+    /// `(line, rows)` pairs sorted by line, `rows` empty rows follow `line` of the diff base.
+    pub base: Vec<(u32, u32)>,
+    /// `(line, rows)` pairs sorted by line, `rows` empty rows follow `line` of the document.
+    pub doc: Vec<(u32, u32)>,
+}
+
 /// A list of changes in a file sorted in ascending
 /// non-overlapping order
 #[derive(Debug)]
@@ -154,6 +188,21 @@ pub fn is_inverted(&self) -> bool {
         self.inverted
     }
 
+    /// Identifies this state of the diff. The version changes whenever the diff is computed
+    /// again and is never shared with another diff.
+    pub fn version(&self) -> u64 {
+        self.diff.base_version.max(self.diff.doc_version)
+    }
+
+    /// The [`Self::version`] at which the text returned by [`Self::diff_base`] last changed.
+    pub fn diff_base_version(&self) -> u64 {
+        if self.inverted {
+            self.diff.doc_version
+        } else {
+            self.diff.base_version
+        }
+    }
+
     /// Returns the `Hunk` for the `n`th change in this file.
     /// if there is no `n`th change  `Hunk::NONE` is returned instead.
     pub fn nth_hunk(&self, n: u32) -> Hunk {
@@ -274,6 +323,75 @@ pub fn hunk_at(&self, line: u32, include_removal: bool) -> Option<u32> {
             }
         }
     }
+
+    /// Maps a line of the document to the corresponding line of the diff base.
+    /// Returns `None` if the line was changed or added.
+    pub fn base_line(&self, line: u32) -> Option<u32> {
+        let changed = self
+            .hunk_at(line, false)
+            .is_some_and(|hunk| self.nth_hunk(hunk).after.contains(&line));
+        if changed {
+            return None;
+        }
+        let doc_range = if self.inverted {
+            |hunk: &Hunk| hunk.before.clone()
+        } else {
+            |hunk: &Hunk| hunk.after.clone()
+        };
+        // all hunks that end before this line shift it by the same offset
+        let pos = self
+            .diff
+            .hunks
+            .partition_point(|hunk| doc_range(hunk).end <= line);
+        match pos.checked_sub(1).map(|pos| self.nth_hunk(pos as u32)) {
+            Some(hunk) => Some(hunk.before.end + line - hunk.after.end),
+            None => Some(line),
+        }
+    }
+
+    /// Maps a range of document lines to the range of diff base lines they correspond to.
+    /// Changes that intersect the range are included entirely.
+    pub fn base_line_range(&self, lines: Range<u32>) -> Range<u32> {
+        let changed_hunk = |line: u32| {
+            self.hunk_at(line, false)
+                .map(|hunk| self.nth_hunk(hunk))
+                .filter(|hunk| hunk.after.contains(&line))
+        };
+        let start = match changed_hunk(lines.start) {
+            Some(hunk) => hunk.before.start,
+            None => self.base_line(lines.start).unwrap_or_default(),
+        };
+        if lines.is_empty() {
+            return start..start;
+        }
+        let end = match changed_hunk(lines.end - 1) {
+            Some(hunk) => hunk.before.end,
+            None => self.base_line(lines.end - 1).unwrap_or_default() + 1,
+        };
+        start..end
+    }
+
+    /// Computes the empty rows that need to be rendered after lines of the diff base and of
+    /// the document so that unchanged lines are rendered at the same height when both are
+    /// shown side by side.
+    pub fn filler_rows(&self) -> FillerRows {
+        let mut fillers = FillerRows::default();
+        for hunk in (0..self.len()).map(|n| self.nth_hunk(n)) {
+            let (before, after) = (hunk.before.len() as u32, hunk.after.len() as u32);
+            // rows can only be inserted after a line, changes at the start of the file
+            // are padded after the first line instead
+            match before.cmp(&after) {
+                Ordering::Greater => fillers
+                    .doc
+                    .push((hunk.after.end.saturating_sub(1), before - after)),
+                Ordering::Less => fillers
+                    .base
+                    .push((hunk.before.end.saturating_sub(1), after - before)),
+                Ordering::Equal => (),
+            }
+        }
+        fillers
+    }
 }
 
 pub struct HunksInLineRangesIter<'a, I: Iterator<Item = (usize, usize)>> {
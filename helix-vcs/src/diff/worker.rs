@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use helix_core::{Rope, RopeSlice};
@@ -16,6 +17,9 @@
 #[cfg(test)]
 mod test;
 
+/// Versions are unique across all diffs so that a diff replaced by another one is noticed too.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
 pub(super) struct DiffWorker {
     pub channel: UnboundedReceiver<Event>,
     pub diff: Arc<RwLock<DiffInner>>,
@@ -38,9 +42,10 @@ pub async fn run(mut self, diff_base: Rope, doc: Rope) {
         if let Some(lines) = interner.interned_lines() {
             self.perform_diff(lines);
         }
-        self.apply_hunks(interner.diff_base(), interner.doc());
+        self.apply_hunks(interner.diff_base(), interner.doc(), true, true);
         while let Some(event) = self.channel.recv().await {
             let (doc, diff_base) = self.accumulate_events(event).await;
+            let (base_changed, doc_changed) = (diff_base.is_some(), doc.is_some());
 
             let process_accumulated_events = || {
                 if let Some(new_base) = diff_base {
@@ -62,17 +67,29 @@ pub async fn run(mut self, diff_base: Rope, doc: Rope) {
             #[cfg(not(test))]
             tokio::task::block_in_place(process_accumulated_events);
 
-            self.apply_hunks(interner.diff_base(), interner.doc());
+            self.apply_hunks(
+                interner.diff_base(),
+                interner.doc(),
+                base_changed,
+                doc_changed,
+            );
         }
     }
 
     /// update the hunks (used by the gutter) by replacing it with `self.new_hunks`.
     /// `self.new_hunks` is always empty after this function runs.
     /// To improve performance this function tries to reuse the allocation of the old diff previously stored in `self.line_diffs`
-    fn apply_hunks(&mut self, diff_base: Rope, doc: Rope) {
+    fn apply_hunks(&mut self, diff_base: Rope, doc: Rope, base_changed: bool, doc_changed: bool) {
+        let version = NEXT_VERSION.fetch_add(1, Ordering::Relaxed);
         let mut diff = self.diff.write();
         diff.diff_base = diff_base;
         diff.doc = doc;
+        if base_changed {
+            diff.base_version = version;
+        }
+        if doc_changed {
+            diff.doc_version = version;
+        }
         diff.hunks.clear();
         diff.hunks.extend(self.diff_alloc.hunks());
         drop(diff);
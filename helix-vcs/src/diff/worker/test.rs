@@ -1,7 +1,7 @@
 use helix_core::Rope;
 use tokio::task::JoinHandle;
 
-use crate::diff::{DiffHandle, Hunk};
+use crate::diff::{Diff, DiffHandle, FillerRows, Hunk};
 
 impl DiffHandle {
     fn new_test(diff_base: &str, doc: &str) -> (DiffHandle, JoinHandle<()>) {
@@ -143,3 +143,94 @@ async fn update_base() {
         ]
     )
 }
+
+#[tokio::test]
+async fn base_line() {
+    let (differ, handle) = DiffHandle::new_test("a\nb\nc\nd\n", "x\na\nc\nD\n");
+    let diff = differ.diff.clone();
+    differ.into_diff(handle).await;
+    let diff = Diff {
+        diff: diff.read(),
+        inverted: false,
+    };
+    assert_eq!(diff.base_line(0), None);
+    assert_eq!(diff.base_line(1), Some(0));
+    assert_eq!(diff.base_line(2), Some(2));
+    assert_eq!(diff.base_line(3), None);
+    assert_eq!(diff.base_line(4), Some(4));
+}
+
+#[tokio::test]
+async fn base_line_range() {
+    let (differ, handle) = DiffHandle::new_test("a\nb\nc\nd\n", "x\na\nc\nD\n");
+    let diff = differ.diff.clone();
+    differ.into_diff(handle).await;
+    let diff = Diff {
+        diff: diff.read(),
+        inverted: false,
+    };
+    assert_eq!(diff.base_line_range(0..1), 0..0);
+    assert_eq!(diff.base_line_range(1..3), 0..3);
+    assert_eq!(diff.base_line_range(3..4), 3..4);
+    assert_eq!(diff.base_line_range(2..2), 2..2);
+}
+
+#[test]
+fn compute_hunks() {
+    let hunks = crate::compute_hunks(&Rope::from_str("a\nb\nc\n"), &Rope::from_str("a\nc\nd\n"));
+    assert_eq!(
+        hunks,
+        [
+            Hunk {
+                before: 1..2,
+                after: 1..1
+            },
+            Hunk {
+                before: 3..3,
+                after: 2..3
+            },
+        ]
+    )
+}
+
+#[tokio::test]
+async fn filler_rows() {
+    let (differ, handle) = DiffHandle::new_test("a\nb\nc\nd\ne\n", "a\nx\ny\nz\nc\ne\n");
+    let diff = differ.diff.clone();
+    differ.into_diff(handle).await;
+    let diff = Diff {
+        diff: diff.read(),
+        inverted: false,
+    };
+    assert_eq!(
+        diff.filler_rows(),
+        FillerRows {
+            base: vec![(1, 2)],
+            doc: vec![(4, 1)]
+        }
+    );
+}
+
+#[tokio::test]
+async fn versions() {
+    async fn versions(base_changed: bool) -> (u64, u64) {
+        let (differ, handle) = DiffHandle::new_test("foo\n", "foo\n");
+        differ.update_document_impl(Rope::from_str("bar\n"), base_changed, None);
+        let diff = differ.diff.clone();
+        drop(differ);
+        handle.await.unwrap();
+        let diff = Diff {
+            diff: diff.read(),
+            inverted: false,
+        };
+        (diff.version(), diff.diff_base_version())
+    }
+
+    // the diff base keeps the version it was computed with first when only the document changes
+    let (doc_version, base_version) = versions(false).await;
+    assert!(base_version < doc_version);
+    let (version, base_version) = versions(true).await;
+    assert_eq!(version, base_version);
+    // versions are not shared between diffs
+    assert_ne!(doc_version, version);
+}
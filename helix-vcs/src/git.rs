@@ -1,30 +1,42 @@
 use anyhow::{bail, Context, Result};
 use arc_swap::ArcSwap;
 use gix::filter::plumbing::driver::apply::Delay;
+use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use gix::bstr::ByteSlice;
+use gix::bstr::{BStr, ByteSlice};
+use gix::diff::index::ChangeRef;
 use gix::diff::Rewrites;
 use gix::dir::entry::Status;
+use gix::index::entry::{Mode, Stage};
+use gix::object::tree::diff::ChangeDetached;
 use gix::objs::tree::EntryKind;
+use gix::revision::walk::Sorting;
 use gix::sec::trust::DefaultForLevel;
+use gix::status::tree_index::TrackRenames;
 use gix::status::{
     index_worktree::Item,
     plumbing::index_as_worktree::{Change, EntryStatus},
     UntrackedFiles,
 };
+use gix::traverse::commit::simple::CommitTimeOrder;
 use gix::{Commit, ObjectId, Repository, ThreadSafeRepository};
 
-use crate::FileChange;
+use crate::{CommitInfo, FileBlame, FileChange};
 
 #[cfg(test)]
 mod test;
 
 #[inline]
 fn get_repo_dir(file: &Path) -> Result<&Path> {
-    file.parent().context("file has no parent directory")
+    // the file and even its directory may only exist in other revisions
+    file.ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .context("file has no parent directory")
 }
 
 pub fn get_diff_base(file: &Path) -> Result<Vec<u8>> {
@@ -42,9 +54,106 @@ pub fn get_diff_base(file: &Path) -> Result<Vec<u8>> {
     let file_oid = find_file_in_commit(&repo, &head, &file)?;
 
     let file_object = repo.find_object(file_oid)?;
-    let data = file_object.detach().data;
-    // Get the actual data that git would make out of the git object.
-    // This will apply the user's git config or attributes like crlf conversions.
+    convert_to_worktree(&repo, &file, file_object.detach().data)
+}
+
+/// Like [`get_diff_base`] but for the commit the given revision resolves to. `A..B` resolves
+/// to `A` and `A...B` to the merge base of `A` and `B`, like the arguments of `git diff`.
+pub fn get_diff_base_at(file: &Path, revision: &str) -> Result<Vec<u8>> {
+    debug_assert!(!file.exists() || file.is_file());
+    debug_assert!(file.is_absolute());
+    let file = gix::path::realpath(file).context("resolve symlinks")?;
+
+    let repo_dir = get_repo_dir(&file)?;
+    let repo = open_repo(repo_dir)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let commit_id = match repo.rev_parse(revision)?.detach() {
+        gix::revision::plumbing::Spec::Include(id)
+        | gix::revision::plumbing::Spec::Range { from: id, .. } => id,
+        gix::revision::plumbing::Spec::Merge { theirs, ours } => {
+            repo.merge_base(theirs, ours)?.detach()
+        }
+        _ => bail!("{revision} does not name a single commit"),
+    };
+    let commit = repo
+        .find_object(commit_id)?
+        .peel_to_commit()
+        .with_context(|| format!("{revision} is not a commit"))?;
+    let file_oid = find_file_in_commit(&repo, &commit, &file)?;
+
+    let file_object = repo.find_object(file_oid)?;
+    convert_to_worktree(&repo, &file, file_object.detach().data)
+}
+
+/// Returns the contents of `file` in the index (the staging area).
+pub fn get_index_base(file: &Path) -> Result<Vec<u8>> {
+    debug_assert!(!file.exists() || file.is_file());
+    debug_assert!(file.is_absolute());
+    let file = gix::path::realpath(file).context("resolve symlinks")?;
+
+    let repo_dir = get_repo_dir(&file)?;
+    let repo = open_repo(repo_dir)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let rela_path = index_path(&repo, &file)?;
+    let index = repo.index()?;
+    let entry = index
+        .entry_by_path(rela_path.as_ref())
+        .context("file is not in the index")?;
+    if !matches!(entry.mode, Mode::FILE | Mode::FILE_EXECUTABLE) {
+        bail!("entry at {} is not a file", file.display());
+    }
+    let data = repo.find_object(entry.id)?.detach().data;
+    convert_to_worktree(&repo, &file, data)
+}
+
+/// Replaces the contents of `file` in the index with `contents`, like `git add` does with
+/// the contents of the working tree.
+pub fn set_index_content(file: &Path, contents: &[u8]) -> Result<()> {
+    debug_assert!(file.is_absolute());
+    let file = gix::path::realpath(file).context("resolve symlinks")?;
+
+    let repo_dir = get_repo_dir(&file)?;
+    let repo = open_repo(repo_dir)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let rela_path = index_path(&repo, &file)?;
+    let mut index = repo.open_index()?;
+
+    let mut data = Vec::with_capacity(contents.len());
+    let (mut pipeline, _) = repo.filter_pipeline(None)?;
+    pipeline
+        .convert_to_git(contents, &gix::path::from_bstr(rela_path.as_bstr()), &index)?
+        .read_to_end(&mut data)?;
+    let blob_id = repo.write_blob(&data)?.detach();
+
+    let entry = index
+        .entry_mut_by_path_and_stage(rela_path.as_ref(), Stage::Unconflicted)
+        .context("file is not in the index")?;
+    if !matches!(entry.mode, Mode::FILE | Mode::FILE_EXECUTABLE) {
+        bail!("entry at {} is not a file", file.display());
+    }
+    entry.id = blob_id;
+    // The stat information belongs to the file in the working tree which (usually) still has
+    // different contents. Clearing it forces git to compare the actual contents.
+    entry.stat = Default::default();
+    // the cached tree no longer matches the index
+    index.remove_tree();
+    index.write(Default::default())?;
+    Ok(())
+}
+
+/// Returns the path of `file` relative to the working tree, as used for index lookups.
+fn index_path(repo: &Repository, file: &Path) -> Result<gix::bstr::BString> {
+    let work_dir = repo.workdir().context("repo has no worktree")?;
+    let rela_path = file.strip_prefix(work_dir)?;
+    Ok(gix::path::to_unix_separators_on_windows(gix::path::try_into_bstr(rela_path)?).into_owned())
+}
+
+/// Gets the actual data that git would make out of the git object.
+/// This will apply the user's git config or attributes like crlf conversions.
+fn convert_to_worktree(repo: &Repository, file: &Path, data: Vec<u8>) -> Result<Vec<u8>> {
     if let Some(work_dir) = repo.workdir() {
         let rela_path = file.strip_prefix(work_dir)?;
         let rela_path = gix::path::try_into_bstr(rela_path)?;
@@ -83,6 +192,359 @@ pub fn for_each_changed_file(cwd: &Path, f: impl Fn(Result<FileChange>) -> bool)
     status(&open_repo(cwd)?.to_thread_local(), f)
 }
 
+/// Attributes every line of `file` at `HEAD` to the commit that last changed it.
+///
+/// History is followed along first parents only and renames are not tracked, so changes
+/// merged from other branches are attributed to the merge commit.
+pub fn blame(file: &Path) -> Result<FileBlame> {
+    debug_assert!(file.is_absolute());
+    let file = gix::path::realpath(file).context("resolve symlinks")?;
+
+    let repo_dir = get_repo_dir(&file)?;
+    let repo = open_repo(repo_dir)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let mut commit = repo.head_commit()?;
+    let mut blob_id = find_file_in_commit(&repo, &commit, &file)?;
+    let mut data = repo.find_object(blob_id)?.detach().data;
+
+    let line_count = imara_diff::sources::byte_lines(&data).count();
+    let mut lines: Vec<Option<Arc<CommitInfo>>> = vec![None; line_count];
+    // (line at HEAD, line in the blob of `commit`) for all lines that are not yet attributed
+    let mut pending: Vec<(usize, u32)> = (0..line_count).map(|line| (line, line as u32)).collect();
+    let mut commits = HashMap::new();
+
+    while !pending.is_empty() {
+        let parent = match commit.parent_ids().next() {
+            Some(id) => Some(id.object()?.try_into_commit()?),
+            None => None,
+        };
+        let parent_blob = parent
+            .as_ref()
+            .and_then(|parent| find_file_in_commit(&repo, parent, &file).ok());
+        let (Some(parent), Some(parent_blob_id)) = (parent, parent_blob) else {
+            // the file was added in this commit
+            let info = blame_commit(&mut commits, &commit)?;
+            for (line, _) in pending.drain(..) {
+                lines[line] = Some(info.clone());
+            }
+            break;
+        };
+        if parent_blob_id == blob_id {
+            commit = parent;
+            continue;
+        }
+
+        let parent_data = repo.find_object(parent_blob_id)?.detach().data;
+        let input = imara_diff::InternedInput::new(parent_data.as_slice(), data.as_slice());
+        let mut diff = imara_diff::Diff::compute(imara_diff::Algorithm::Histogram, &input);
+        diff.postprocess_lines(&input);
+        // maps lines of the current blob to the lines of the parent blob they are unchanged from
+        let mut parent_lines = vec![None; input.after.len()];
+        let (mut before, mut after) = (0, 0);
+        for hunk in diff.hunks().chain(std::iter::once(imara_diff::Hunk {
+            before: input.before.len() as u32..input.before.len() as u32,
+            after: input.after.len() as u32..input.after.len() as u32,
+        })) {
+            while after < hunk.after.start {
+                parent_lines[after as usize] = Some(before);
+                after += 1;
+                before += 1;
+            }
+            before = hunk.before.end;
+            after = hunk.after.end;
+        }
+
+        let info = blame_commit(&mut commits, &commit)?;
+        pending.retain_mut(|(line, current)| {
+            match parent_lines.get(*current as usize).copied().flatten() {
+                Some(parent_line) => {
+                    *current = parent_line;
+                    true
+                }
+                None => {
+                    lines[*line] = Some(info.clone());
+                    false
+                }
+            }
+        });
+
+        commit = parent;
+        blob_id = parent_blob_id;
+        data = parent_data;
+    }
+
+    Ok(FileBlame {
+        lines: lines.into_iter().map(Option::unwrap).collect(),
+    })
+}
+
+/// Returns the shared blame information of `commit`, decoding it at most once.
+fn blame_commit(
+    commits: &mut HashMap<ObjectId, Arc<CommitInfo>>,
+    commit: &Commit,
+) -> Result<Arc<CommitInfo>> {
+    if let Some(info) = commits.get(&commit.id) {
+        return Ok(info.clone());
+    }
+    let info = Arc::new(commit_info(commit)?);
+    commits.insert(commit.id, info.clone());
+    Ok(info)
+}
+
+fn commit_info(commit: &Commit) -> Result<CommitInfo> {
+    let author = commit.author()?;
+    Ok(CommitInfo {
+        id: commit.id().shorten_or_id().to_string().into(),
+        author: author.name.to_str_lossy().into(),
+        time: author.seconds(),
+        summary: commit.message()?.summary().to_str_lossy().into(),
+    })
+}
+
+/// Reports the commits reachable from `HEAD`, newest first. If `file` is given only the
+/// commits that changed it are reported.
+pub fn for_each_commit(
+    cwd: &Path,
+    file: Option<&Path>,
+    f: impl Fn(Result<CommitInfo>) -> bool,
+) -> Result<()> {
+    let file = file
+        .map(gix::path::realpath)
+        .transpose()
+        .context("resolve symlinks")?;
+    let repo_dir = match &file {
+        Some(file) => get_repo_dir(file)?,
+        None => cwd,
+    };
+    let repo = open_repo(repo_dir)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let walk = repo
+        .rev_walk([repo.head_id()?])
+        .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+        .all()?;
+
+    for info in walk {
+        let commit = info.map_err(anyhow::Error::from).and_then(|info| {
+            let commit = info.object()?;
+            match &file {
+                Some(file) if !changes_file(&repo, &commit, file)? => Ok(None),
+                _ => commit_info(&commit).map(Some),
+            }
+        });
+        if let Some(commit) = commit.transpose() {
+            if !f(commit) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `commit` changed `file`. Like `git log`, merge commits only count if the
+/// file differs from all of their parents.
+fn changes_file(repo: &Repository, commit: &Commit, file: &Path) -> Result<bool> {
+    let blob = find_file_in_commit(repo, commit, file).ok();
+    let mut parents = 0;
+    for parent in commit.parent_ids() {
+        parents += 1;
+        let parent = parent.object()?.try_into_commit()?;
+        if find_file_in_commit(repo, &parent, file).ok() == blob {
+            return Ok(false);
+        }
+    }
+    Ok(parents > 0 || blob.is_some())
+}
+
+/// Formats the commit `revision` and the changes it made to its first parent like `git show`.
+pub fn show_commit(cwd: &Path, revision: &str) -> Result<String> {
+    let repo = open_repo(cwd)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let commit = repo
+        .rev_parse_single(revision)?
+        .object()?
+        .peel_to_commit()
+        .with_context(|| format!("{revision} is not a commit"))?;
+    let author = commit.author()?;
+
+    let mut text = String::new();
+    writeln!(text, "commit {}", commit.id)?;
+    writeln!(text, "Author: {} <{}>", author.name, author.email)?;
+    writeln!(text, "Date:   {}", commit_info(&commit)?.relative_time())?;
+    writeln!(text)?;
+    for line in commit.message_raw()?.to_str_lossy().trim_end().lines() {
+        writeln!(text, "    {line}")?;
+    }
+    writeln!(text)?;
+
+    let parent = match commit.parent_ids().next() {
+        Some(id) => Some(id.object()?.try_into_commit()?.tree()?),
+        None => None,
+    };
+    let tree = commit.tree()?;
+    for change in repo.diff_tree_to_tree(parent.as_ref(), &tree, None)? {
+        // submodules and directories have no content to diff
+        if !matches!(
+            change.entry_mode().kind(),
+            EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link
+        ) {
+            continue;
+        }
+        let (before, after) = match &change {
+            ChangeDetached::Addition { id, .. } => (None, Some(*id)),
+            ChangeDetached::Deletion { id, .. } => (Some(*id), None),
+            ChangeDetached::Modification {
+                previous_id, id, ..
+            } => (Some(*previous_id), Some(*id)),
+            ChangeDetached::Rewrite { source_id, id, .. } => (Some(*source_id), Some(*id)),
+        };
+        let (from, to) = (change.source_location(), change.location());
+        write_file_diff(&mut text, &repo, (from, before), (to, after))?;
+    }
+    Ok(text)
+}
+
+/// Formats the changes staged for the next commit like `git diff --cached`.
+pub fn staged_diff(cwd: &Path) -> Result<String> {
+    let repo = open_repo(cwd)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let mut text = String::new();
+    for_each_staged_change(&repo, |change| {
+        let (from, before) = match &change {
+            ChangeRef::Addition { location, .. } => (location.as_ref(), None),
+            ChangeRef::Deletion { location, id, .. } => {
+                (location.as_ref(), Some(id.as_ref().to_owned()))
+            }
+            ChangeRef::Modification {
+                location,
+                previous_id,
+                ..
+            } => (location.as_ref(), Some(previous_id.as_ref().to_owned())),
+            ChangeRef::Rewrite {
+                source_location,
+                source_id,
+                ..
+            } => (
+                source_location.as_ref(),
+                Some(source_id.as_ref().to_owned()),
+            ),
+        };
+        let after = match &change {
+            ChangeRef::Deletion { .. } => None,
+            _ => Some(change.id().to_owned()),
+        };
+        write_file_diff(&mut text, &repo, (from, before), (change.location(), after))
+    })?;
+    Ok(text)
+}
+
+/// Returns the paths of the files with changes staged for the next commit, relative to the root
+/// of the repository.
+pub fn staged_files(cwd: &Path) -> Result<Vec<PathBuf>> {
+    let repo = open_repo(cwd)
+        .context("failed to open git repo")?
+        .to_thread_local();
+    let mut files = Vec::new();
+    for_each_staged_change(&repo, |change| {
+        files.push(change.location().to_path()?.to_owned());
+        Ok(())
+    })?;
+    Ok(files)
+}
+
+/// Calls `f` with the differences between `HEAD` and the index of the next commit, skipping
+/// submodules.
+fn for_each_staged_change(
+    repo: &Repository,
+    mut f: impl FnMut(ChangeRef<'_, '_>) -> Result<()>,
+) -> Result<()> {
+    // `git commit --all` and `git commit <paths>` prepare the index of the commit in a
+    // temporary file that is passed to the editor in `GIT_INDEX_FILE`
+    let (snapshot, temporary);
+    let index: &gix::index::State = match std::env::var_os("GIT_INDEX_FILE") {
+        Some(path) => {
+            temporary = gix::index::File::at(
+                std::path::absolute(path)?,
+                repo.object_hash(),
+                false,
+                Default::default(),
+            )?;
+            &temporary
+        }
+        None => {
+            snapshot = repo.index_or_empty()?;
+            &snapshot
+        }
+    };
+    repo.tree_index_status(
+        &repo.head_tree_id_or_empty()?,
+        index,
+        None,
+        TrackRenames::AsConfigured,
+        |change, _, _| {
+            if change.entry_mode() != Mode::COMMIT {
+                f(change)?;
+            }
+            Ok::<_, anyhow::Error>(gix::diff::index::Action::Continue)
+        },
+    )?;
+    Ok(())
+}
+
+/// Appends the diff of a file to `text`, given its path and blob before and after the change.
+/// A missing blob means that the file was added or deleted.
+fn write_file_diff(
+    text: &mut String,
+    repo: &Repository,
+    (from, before): (&BStr, Option<ObjectId>),
+    (to, after): (&BStr, Option<ObjectId>),
+) -> Result<()> {
+    writeln!(text, "diff --git a/{from} b/{to}")?;
+    match before {
+        Some(_) => writeln!(text, "--- a/{from}")?,
+        None => writeln!(text, "--- /dev/null")?,
+    }
+    match after {
+        Some(_) => writeln!(text, "+++ b/{to}")?,
+        None => writeln!(text, "+++ /dev/null")?,
+    }
+    write_blob_diff(text, repo, before, after)
+}
+
+/// Appends the unified diff between two blobs to `text`. A missing blob is treated as empty.
+fn write_blob_diff(
+    text: &mut String,
+    repo: &Repository,
+    before: Option<ObjectId>,
+    after: Option<ObjectId>,
+) -> Result<()> {
+    let data = |id: Option<ObjectId>| -> Result<Vec<u8>> {
+        match id {
+            Some(id) => Ok(repo.find_object(id)?.detach().data),
+            None => Ok(Vec::new()),
+        }
+    };
+    let (before, after) = (data(before)?, data(after)?);
+    if before.contains(&0) || after.contains(&0) {
+        writeln!(text, "Binary files differ")?;
+        return Ok(());
+    }
+    let (before, after) = (before.to_str_lossy(), after.to_str_lossy());
+    let input = imara_diff::InternedInput::new(&*before, &*after);
+    let mut diff = imara_diff::Diff::compute(imara_diff::Algorithm::Histogram, &input);
+    diff.postprocess_lines(&input);
+    let printer = imara_diff::BasicLineDiffPrinter(&input.interner);
+    write!(
+        text,
+        "{}",
+        diff.unified_diff(&printer, imara_diff::UnifiedDiffConfig::default(), &input)
+    )?;
+    Ok(())
+}
+
 fn open_repo(path: &Path) -> Result<ThreadSafeRepository> {
     // custom open options
     let mut git_open_opts_map = gix::sec::trust::Mapping::<gix::open::Options>::default();
@@ -2,7 +2,7 @@
 
 use tempfile::TempDir;
 
-use crate::git;
+use crate::{git, FileStatus, StatusSnapshot};
 
 fn exec_git_cmd(args: &str, git_dir: &Path) {
     let res = Command::new("git")
@@ -150,3 +150,217 @@ fn symlink_to_git_repo() {
     assert_eq!(git::get_diff_base(&file_link).unwrap(), contents);
     assert_eq!(git::get_diff_base(&file).unwrap(), contents);
 }
+
+#[test]
+fn blame_lines() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file)
+        .unwrap()
+        .write_all(b"a\nb\nc\n")
+        .unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file)
+        .unwrap()
+        .write_all(b"a\nB\nc\nd\n")
+        .unwrap();
+    create_commit(temp_git.path(), true);
+    // commits that do not touch the file are skipped
+    File::create(temp_git.path().join("other.txt")).unwrap();
+    create_commit(temp_git.path(), true);
+    // uncommitted changes are not blamed
+    File::create(&file).unwrap().write_all(b"x\n").unwrap();
+
+    let blame = git::blame(&file).unwrap();
+    assert_eq!(blame.lines.len(), 4);
+    let first = &blame.lines[0];
+    let second = &blame.lines[1];
+    assert_ne!(first.id, second.id);
+    assert_eq!(&blame.lines[2], first);
+    assert_eq!(&blame.lines[3], second);
+    assert_eq!(&*first.author, "author");
+    assert_eq!(&*first.summary, "message");
+    assert_eq!(first.time, 946684800);
+}
+
+#[test]
+fn blame_untracked_file() {
+    let temp_git = empty_git_repo();
+    File::create(temp_git.path().join("other.txt")).unwrap();
+    create_commit(temp_git.path(), true);
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"foo").unwrap();
+
+    assert!(git::blame(&file).is_err());
+}
+
+#[test]
+fn stage_content() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"a\nb\n").unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file)
+        .unwrap()
+        .write_all(b"a\nB\nc\n")
+        .unwrap();
+    assert_eq!(git::get_index_base(&file).unwrap(), b"a\nb\n");
+
+    git::set_index_content(&file, b"a\nB\n").unwrap();
+    assert_eq!(git::get_index_base(&file).unwrap(), b"a\nB\n");
+    assert_eq!(git::get_diff_base(&file).unwrap(), b"a\nb\n");
+    // committing the index only includes the staged content
+    create_commit(temp_git.path(), false);
+    assert_eq!(git::get_diff_base(&file).unwrap(), b"a\nB\n");
+}
+
+#[test]
+fn stage_untracked_file() {
+    let temp_git = empty_git_repo();
+    File::create(temp_git.path().join("other.txt")).unwrap();
+    create_commit(temp_git.path(), true);
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"foo").unwrap();
+
+    assert!(git::get_index_base(&file).is_err());
+    assert!(git::set_index_content(&file, b"foo").is_err());
+}
+
+#[test]
+fn diff_base_at_revision() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"a").unwrap();
+    create_commit(temp_git.path(), true);
+    exec_git_cmd("checkout -b feature", temp_git.path());
+    File::create(&file).unwrap().write_all(b"b").unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file).unwrap().write_all(b"c").unwrap();
+    create_commit(temp_git.path(), true);
+    exec_git_cmd("checkout main", temp_git.path());
+    File::create(&file).unwrap().write_all(b"d").unwrap();
+    create_commit(temp_git.path(), true);
+    exec_git_cmd("checkout feature", temp_git.path());
+
+    assert_eq!(git::get_diff_base_at(&file, "HEAD").unwrap(), b"c");
+    assert_eq!(git::get_diff_base_at(&file, "HEAD~1").unwrap(), b"b");
+    assert_eq!(git::get_diff_base_at(&file, "main").unwrap(), b"d");
+    assert_eq!(git::get_diff_base_at(&file, "main..HEAD").unwrap(), b"d");
+    assert_eq!(git::get_diff_base_at(&file, "main...HEAD").unwrap(), b"a");
+    assert!(git::get_diff_base_at(&file, "does-not-exist").is_err());
+}
+
+#[test]
+fn file_history() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"a\n").unwrap();
+    exec_git_cmd("add -A", temp_git.path());
+    exec_git_cmd("commit -m add", temp_git.path());
+    File::create(temp_git.path().join("other.txt")).unwrap();
+    exec_git_cmd("add -A", temp_git.path());
+    exec_git_cmd("commit -m other", temp_git.path());
+    File::create(&file).unwrap().write_all(b"b\n").unwrap();
+    exec_git_cmd("commit -am change", temp_git.path());
+
+    let summaries = |file: Option<&Path>| {
+        let summaries = std::sync::Mutex::new(Vec::new());
+        git::for_each_commit(temp_git.path(), file, |commit| {
+            summaries.lock().unwrap().push(commit.unwrap().summary);
+            true
+        })
+        .unwrap();
+        let mut summaries = summaries.into_inner().unwrap();
+        // all commits have the same commit time so their order is unspecified
+        summaries.sort();
+        summaries
+    };
+    assert_eq!(
+        summaries(None),
+        ["add".into(), "change".into(), "other".into()]
+    );
+    assert_eq!(summaries(Some(&file)), ["add".into(), "change".into()]);
+}
+
+#[test]
+fn show_commit() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"a\nb\n").unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file).unwrap().write_all(b"a\nc\n").unwrap();
+    File::create(temp_git.path().join("new.txt"))
+        .unwrap()
+        .write_all(b"new\n")
+        .unwrap();
+    create_commit(temp_git.path(), true);
+
+    let text = git::show_commit(temp_git.path(), "HEAD").unwrap();
+    assert!(text.starts_with("commit "));
+    assert!(text.contains("Author: author <author@example.com>\n"));
+    assert!(text.contains("\n    message\n"));
+    assert!(text.contains(
+        "diff --git a/file.txt b/file.txt\n--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+    ));
+    assert!(text.contains("--- /dev/null\n+++ b/new.txt\n@@ -1,0 +1,1 @@\n+new\n"));
+}
+
+#[test]
+fn diff_base_at_revision_of_deleted_file() {
+    let temp_git = empty_git_repo();
+    let dir = temp_git.path().join("dir");
+    std::fs::create_dir(&dir).unwrap();
+    let file = dir.join("file.txt");
+    File::create(&file).unwrap().write_all(b"a").unwrap();
+    create_commit(temp_git.path(), true);
+    std::fs::remove_dir_all(&dir).unwrap();
+    create_commit(temp_git.path(), true);
+
+    assert!(git::get_diff_base_at(&file, "HEAD").is_err());
+    assert_eq!(git::get_diff_base_at(&file, "HEAD~1").unwrap(), b"a");
+}
+
+#[test]
+fn staged_changes() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    File::create(&file).unwrap().write_all(b"a\nb\n").unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file).unwrap().write_all(b"a\nc\n").unwrap();
+    let unstaged = temp_git.path().join("unstaged.txt");
+    File::create(unstaged).unwrap().write_all(b"new\n").unwrap();
+    exec_git_cmd("add file.txt", temp_git.path());
+
+    // git opens the commit message in the git dir
+    let files = git::staged_files(&temp_git.path().join(".git")).unwrap();
+    assert_eq!(files, [Path::new("file.txt")]);
+    let text = git::staged_diff(temp_git.path()).unwrap();
+    assert_eq!(
+        text,
+        "diff --git a/file.txt b/file.txt\n--- a/file.txt\n+++ b/file.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+    );
+}
+
+#[test]
+fn status_snapshot() {
+    let temp_git = empty_git_repo();
+    let file = temp_git.path().join("file.txt");
+    let other = temp_git.path().join("other.txt");
+    File::create(&file).unwrap().write_all(b"a\n").unwrap();
+    File::create(&other).unwrap().write_all(b"a\n").unwrap();
+    create_commit(temp_git.path(), true);
+    File::create(&file).unwrap().write_all(b"b\n").unwrap();
+    let new = temp_git.path().join("new.txt");
+    File::create(&new).unwrap().write_all(b"new\n").unwrap();
+
+    let changes = std::cell::RefCell::new(Vec::new());
+    git::for_each_changed_file(temp_git.path(), |change| {
+        changes.borrow_mut().push(change.unwrap());
+        true
+    })
+    .unwrap();
+    let snapshot = StatusSnapshot::new(changes.into_inner());
+    assert_eq!(snapshot.get(&file), Some(FileStatus::Modified));
+    assert_eq!(snapshot.get(&new), Some(FileStatus::Untracked));
+    assert_eq!(snapshot.get(&other), None);
+}
@@ -0,0 +1,192 @@
+//! Diff provider for [Jujutsu](https://jj-vcs.github.io/jj/) workspaces, including workspaces
+//! colocated with git. The repository is queried with the `jj` command line tool.
+
+use anyhow::{bail, Context, Result};
+use arc_swap::ArcSwap;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use crate::FileChange;
+
+#[cfg(test)]
+mod test;
+
+/// Returns the root of the jj workspace containing `path`. Used to avoid running `jj` outside
+/// of workspaces.
+fn workspace_root(path: &Path) -> Result<&Path> {
+    path.ancestors()
+        .find(|dir| dir.join(".jj").is_dir())
+        .context("not in a jj workspace")
+}
+
+/// Runs `jj` in the workspace at `root` and returns its output. The working copy is not
+/// snapshotted so that looking up revisions does not create a new operation.
+fn jj(root: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    run_jj(root, &["--ignore-working-copy"], args)
+}
+
+/// Like [`jj`] but snapshots the working copy first, so that the output reflects the files on
+/// disk.
+fn jj_with_snapshot(root: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    run_jj(root, &[], args)
+}
+
+fn run_jj(root: &Path, global_args: &[&str], args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("jj")
+        .current_dir(root)
+        .args(["--color", "never", "--no-pager"])
+        .args(global_args)
+        .args(args)
+        .output()
+        .context("failed to run jj")?;
+    if !output.status.success() {
+        bail!(
+            "`jj {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Returns a fileset that matches exactly the file at `file`.
+fn fileset(root: &Path, file: &Path) -> Result<String> {
+    let path = file
+        .strip_prefix(root)
+        .context("file is not in the workspace")?
+        .components()
+        .map(|component| component.as_os_str().to_str().context("path is not UTF-8"))
+        .collect::<Result<Vec<_>>>()?
+        .join("/");
+    Ok(format!(
+        "root-file:\"{}\"",
+        path.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+/// Returns the contents of `file` in the parent of the working copy commit.
+pub fn get_diff_base(file: &Path) -> Result<Vec<u8>> {
+    get_diff_base_at(file, "@-")
+}
+
+/// Like [`get_diff_base`] but for the commit the given revset resolves to.
+pub fn get_diff_base_at(file: &Path, revision: &str) -> Result<Vec<u8>> {
+    debug_assert!(file.is_absolute());
+    let root = workspace_root(file)?;
+    jj(
+        root,
+        &[
+            "file",
+            "show",
+            "--revision",
+            revision,
+            &fileset(root, file)?,
+        ],
+    )
+}
+
+/// Returns the change id of the working copy commit followed by its bookmarks.
+pub fn get_current_head_name(file: &Path) -> Result<Arc<ArcSwap<Box<str>>>> {
+    debug_assert!(file.is_absolute());
+    let root = workspace_root(file)?;
+    let template = r#"separate(" ", change_id.shortest(8), bookmarks)"#;
+    let name = jj(
+        root,
+        &[
+            "log",
+            "--no-graph",
+            "--revisions",
+            "@",
+            "--template",
+            template,
+        ],
+    )?;
+    let name = String::from_utf8(name)?.trim().to_string();
+
+    Ok(Arc::new(ArcSwap::from_pointee(name.into_boxed_str())))
+}
+
+/// Reports the files changed in the working copy commit.
+pub fn for_each_changed_file(cwd: &Path, f: impl Fn(Result<FileChange>) -> bool) -> Result<()> {
+    let root = workspace_root(cwd)?;
+    let summary = jj_with_snapshot(root, &["diff", "--summary", "--revision", "@"])?;
+    // the working copy was just snapshotted, fails if there are no conflicts
+    let conflicts = jj(root, &["resolve", "--list", "--revision", "@"]).unwrap_or_default();
+
+    let conflicts: HashSet<_> = String::from_utf8_lossy(&conflicts)
+        .lines()
+        .filter_map(|line| parse_conflict_line(line, root))
+        .collect();
+    for path in &conflicts {
+        if !f(Ok(FileChange::Conflict { path: path.clone() })) {
+            return Ok(());
+        }
+    }
+    for change in String::from_utf8_lossy(&summary)
+        .lines()
+        .filter_map(|line| parse_summary_line(line, root))
+    {
+        if conflicts.contains(change.path()) {
+            continue;
+        }
+        if !f(Ok(change)) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Parses a line of `jj diff --summary`, for example `M src/lib.rs` or
+/// `R src/{old.rs => new.rs}`. Paths are relative to `root`.
+fn parse_summary_line(line: &str, root: &Path) -> Option<FileChange> {
+    let (status, path) = line.split_once(' ')?;
+    let change = match status {
+        "M" => FileChange::Modified {
+            path: root.join(path),
+        },
+        // jj tracks new files automatically, they are reported like untracked files in git
+        "A" => FileChange::Untracked {
+            path: root.join(path),
+        },
+        "D" => FileChange::Deleted {
+            path: root.join(path),
+        },
+        "R" => {
+            let (from, to) = parse_rename(path)?;
+            FileChange::Renamed {
+                from_path: root.join(from),
+                to_path: root.join(to),
+            }
+        }
+        "C" => FileChange::Untracked {
+            path: root.join(parse_rename(path)?.1),
+        },
+        _ => return None,
+    };
+    Some(change)
+}
+
+/// Splits `prefix/{from => to}/suffix` into the paths before and after the rename.
+fn parse_rename(path: &str) -> Option<(String, String)> {
+    let (prefix, rest) = path.split_once('{')?;
+    let (renamed, suffix) = rest.split_once('}')?;
+    let (from, to) = renamed.split_once(" => ")?;
+    // an empty side of the rename also removes one of the path separators around it
+    let join = |name: &str| {
+        if name.is_empty() {
+            format!("{prefix}{}", suffix.strip_prefix('/').unwrap_or(suffix))
+        } else {
+            format!("{prefix}{name}{suffix}")
+        }
+    };
+    Some((join(from), join(to)))
+}
+
+/// Parses a line of `jj resolve --list`: the path of a conflicted file followed by a
+/// description of the conflict, for example `src/lib.rs    2-sided conflict`.
+fn parse_conflict_line(line: &str, root: &Path) -> Option<PathBuf> {
+    let (path, _description) = line.rsplit_once("  ")?;
+    Some(root.join(path.trim_end()))
+}
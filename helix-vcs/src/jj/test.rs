@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use crate::jj::{fileset, parse_conflict_line, parse_rename, parse_summary_line};
+use crate::FileChange;
+
+#[test]
+fn summary() {
+    let root = Path::new("/repo");
+    let change = parse_summary_line("M src/lib.rs", root).unwrap();
+    assert!(matches!(change, FileChange::Modified { .. }));
+    assert_eq!(change.path(), Path::new("/repo/src/lib.rs"));
+
+    let change = parse_summary_line("A new file.txt", root).unwrap();
+    assert!(matches!(change, FileChange::Untracked { .. }));
+    assert_eq!(change.path(), Path::new("/repo/new file.txt"));
+
+    let change = parse_summary_line("D old.rs", root).unwrap();
+    assert!(matches!(change, FileChange::Deleted { .. }));
+
+    let Some(FileChange::Renamed { from_path, to_path }) =
+        parse_summary_line("R src/{a.rs => b.rs}", root)
+    else {
+        panic!("expected a rename");
+    };
+    assert_eq!(from_path, Path::new("/repo/src/a.rs"));
+    assert_eq!(to_path, Path::new("/repo/src/b.rs"));
+
+    assert!(parse_summary_line("Working copy changes:", root).is_none());
+}
+
+#[test]
+fn rename() {
+    assert_eq!(
+        parse_rename("{a => b}/c.rs"),
+        Some(("a/c.rs".to_string(), "b/c.rs".to_string()))
+    );
+    assert_eq!(
+        parse_rename("src/{ => nested}/lib.rs"),
+        Some(("src/lib.rs".to_string(), "src/nested/lib.rs".to_string()))
+    );
+    assert_eq!(parse_rename("src/lib.rs"), None);
+}
+
+#[test]
+fn conflict() {
+    assert_eq!(
+        parse_conflict_line("src/my file.rs    2-sided conflict", Path::new("/repo")),
+        Some(Path::new("/repo/src/my file.rs").to_path_buf())
+    );
+}
+
+#[test]
+fn file_pattern() {
+    assert_eq!(
+        fileset(Path::new("/repo"), Path::new("/repo/src/a \"b\".rs")).unwrap(),
+        r#"root-file:"src/a \"b\".rs""#
+    );
+    assert!(fileset(Path::new("/repo"), Path::new("/other/file.rs")).is_err());
+}
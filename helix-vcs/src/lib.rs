@@ -1,30 +1,46 @@
 //! `helix_vcs` provides types for working with diffs from a Version Control System (VCS).
-//! Currently `git` is the only supported provider for diffs, but this architecture allows
-//! for other providers to be added in the future.
+//! Currently `git` and `jj` are the supported providers for diffs, but this architecture
+//! allows for other providers to be added in the future.
 
 use anyhow::{anyhow, bail, Result};
 use arc_swap::ArcSwap;
 use std::{
+    cell::RefCell,
+    future::Future,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 #[cfg(feature = "git")]
 mod git;
+#[cfg(feature = "jj")]
+mod jj;
 
+mod blame;
+mod commit;
+mod commit_message;
+mod conflict;
 mod diff;
 
-pub use diff::{DiffHandle, Hunk};
+pub use blame::FileBlame;
+pub use commit::CommitInfo;
+pub use commit_message::{check_commit_message, MessageWarning};
+pub use conflict::{find_conflicts, Conflict, Resolution};
+
+pub use diff::{compute_hunks, DiffHandle, FillerRows, Hunk};
 
 mod status;
 
-pub use status::FileChange;
+pub use status::{FileChange, FileStatus, StatusSnapshot};
 
 /// Contains all active diff providers. Diff providers are compiled in via features. Currently
-/// only `git` is supported.
+/// `git` and `jj` are supported.
 #[derive(Clone)]
 pub struct DiffProviderRegistry {
     providers: Vec<DiffProvider>,
+    /// The changed files of the last [`DiffProviderRegistry::refresh_status`], shared by all
+    /// clones of the registry.
+    status: Arc<ArcSwap<StatusSnapshot>>,
 }
 
 impl DiffProviderRegistry {
@@ -43,6 +59,55 @@ pub fn get_diff_base(&self, file: &Path) -> Option<Vec<u8>> {
             })
     }
 
+    /// Get the given file at the given revision from the VCS, for example a branch or commit.
+    /// Like [`Self::get_diff_base`] this provides a "base" for a diff to be created.
+    pub fn get_diff_base_at(&self, file: &Path, revision: &str) -> Option<Vec<u8>> {
+        self.providers
+            .iter()
+            .find_map(|provider| match provider.get_diff_base_at(file, revision) {
+                Ok(res) => Some(res),
+                Err(err) => {
+                    log::debug!("{err:#?}");
+                    log::debug!(
+                        "failed to open diff base at {revision} for {}",
+                        file.display()
+                    );
+                    None
+                }
+            })
+    }
+
+    /// Get the contents of the given file in the index (the staging area). Differences to
+    /// the "base" returned by [`Self::get_diff_base`] have been staged.
+    pub fn get_index_base(&self, file: &Path) -> Option<Vec<u8>> {
+        self.providers
+            .iter()
+            .find_map(|provider| match provider.get_index_base(file) {
+                Ok(res) => Some(res),
+                Err(err) => {
+                    log::debug!("{err:#?}");
+                    log::debug!("failed to open index base for {}", file.display());
+                    None
+                }
+            })
+    }
+
+    /// Replaces the contents of the given file in the index, staging all differences to the
+    /// base returned by [`Self::get_diff_base`].
+    pub fn set_index_content(&self, file: &Path, contents: &[u8]) -> Result<()> {
+        // report the error of the last provider that is compiled in, the earlier ones usually
+        // fail because the file is not part of one of their repositories
+        let mut last_err = None;
+        for provider in &self.providers {
+            match provider.set_index_content(file, contents) {
+                Ok(()) => return Ok(()),
+                Err(err) if !matches!(provider, DiffProvider::None) => last_err = Some(err),
+                Err(_) => (),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("no diff provider returns success")))
+    }
+
     /// Get the current name of the current [HEAD](https://stackoverflow.com/questions/2304087/what-is-head-in-git).
     pub fn get_current_head_name(&self, file: &Path) -> Option<Arc<ArcSwap<Box<str>>>> {
         self.providers
@@ -75,18 +140,163 @@ pub fn for_each_changed_file(
             }
         });
     }
+
+    /// Returns the cached status of the changed files. The snapshot is replaced once a
+    /// [`DiffProviderRegistry::refresh_status`] finishes.
+    pub fn status(&self) -> Arc<ArcSwap<StatusSnapshot>> {
+        self.status.clone()
+    }
+
+    /// Collects the changed files below `cwd` in a background task and replaces the cached
+    /// status with them once done. The returned future resolves once the status is replaced,
+    /// the refresh runs whether it is awaited or not.
+    pub fn refresh_status(&self, cwd: PathBuf) -> impl Future<Output = ()> + Send + 'static {
+        let registry = self.clone();
+        let refresh = tokio::task::spawn_blocking(move || {
+            let changes = RefCell::new(Vec::new());
+            let found = registry.providers.iter().any(|provider| {
+                let res = provider.for_each_changed_file(&cwd, |change: Result<FileChange>| {
+                    changes.borrow_mut().extend(change.ok());
+                    true
+                });
+                if res.is_err() {
+                    changes.borrow_mut().clear();
+                }
+                res.is_ok()
+            });
+            if !found {
+                log::debug!("failed to obtain the status of {}", cwd.display());
+            }
+            let snapshot = StatusSnapshot::new(changes.into_inner());
+            registry.status.store(Arc::new(snapshot));
+            helix_event::request_redraw();
+        });
+        async move {
+            if let Err(err) = refresh.await {
+                log::error!("failed to refresh the status: {err}");
+            }
+        }
+    }
+
+    /// Fire-and-forget commit history iteration, newest first. Runs everything in a background
+    /// task. If `file` is given only the commits that changed it are reported. Keeps iterating
+    /// until `f` returns `false`.
+    pub fn for_each_commit(
+        self,
+        cwd: PathBuf,
+        file: Option<PathBuf>,
+        f: impl Fn(Result<CommitInfo>) -> bool + Send + 'static,
+    ) {
+        tokio::task::spawn_blocking(move || {
+            if self
+                .providers
+                .iter()
+                .find_map(|provider| provider.for_each_commit(&cwd, file.as_deref(), &f).ok())
+                .is_none()
+            {
+                f(Err(anyhow!("no diff provider returns success")));
+            }
+        });
+    }
+
+    /// Formats the given commit and the changes it made, like `git show`.
+    pub fn show_commit(&self, cwd: &Path, revision: &str) -> Option<String> {
+        self.providers
+            .iter()
+            .find_map(|provider| match provider.show_commit(cwd, revision) {
+                Ok(res) => Some(res),
+                Err(err) => {
+                    log::debug!("{err:#?}");
+                    log::debug!("failed to show commit {revision}");
+                    None
+                }
+            })
+    }
+
+    /// Returns up to `count` of the most recent commits, newest first.
+    pub fn recent_commits(&self, cwd: &Path, count: usize) -> Vec<CommitInfo> {
+        let commits = RefCell::new(Vec::new());
+        let found = self.providers.iter().any(|provider| {
+            provider
+                .for_each_commit(cwd, None, |commit| {
+                    let mut commits = commits.borrow_mut();
+                    commits.extend(commit.ok());
+                    commits.len() < count
+                })
+                .is_ok()
+        });
+        if !found {
+            log::debug!("failed to list the commits of {}", cwd.display());
+        }
+        commits.into_inner()
+    }
+
+    /// Formats the changes staged for the next commit, like `git diff --cached`.
+    pub fn staged_diff(&self, cwd: &Path) -> Option<String> {
+        self.providers
+            .iter()
+            .find_map(|provider| match provider.staged_diff(cwd) {
+                Ok(res) => Some(res),
+                Err(err) => {
+                    log::debug!("{err:#?}");
+                    log::debug!("failed to diff the staged changes in {}", cwd.display());
+                    None
+                }
+            })
+    }
+
+    /// Returns the paths of the files with changes staged for the next commit, relative to the
+    /// root of the repository.
+    pub fn staged_files(&self, cwd: &Path) -> Option<Vec<PathBuf>> {
+        self.providers
+            .iter()
+            .find_map(|provider| match provider.staged_files(cwd) {
+                Ok(res) => Some(res),
+                Err(err) => {
+                    log::debug!("{err:#?}");
+                    log::debug!("failed to list the staged files in {}", cwd.display());
+                    None
+                }
+            })
+    }
+
+    /// Computes which commit last changed each line of `file` at `HEAD`. Blaming walks the
+    /// file history so it runs in a background task.
+    pub fn blame(&self, file: PathBuf) -> impl Future<Output = Result<FileBlame>> + Send + 'static {
+        let providers = self.providers.clone();
+        async move {
+            tokio::task::spawn_blocking(move || {
+                // report the error of the last provider that is compiled in, see `set_index_content`
+                let mut last_err = None;
+                for provider in providers {
+                    match provider.blame(&file) {
+                        Ok(blame) => return Ok(blame),
+                        Err(err) if !matches!(provider, DiffProvider::None) => last_err = Some(err),
+                        Err(_) => (),
+                    }
+                }
+                Err(last_err.unwrap_or_else(|| anyhow!("no diff provider returns success")))
+            })
+            .await?
+        }
+    }
 }
 
 impl Default for DiffProviderRegistry {
     fn default() -> Self {
-        // currently only git is supported
         // TODO make this configurable when more providers are added
         let providers = vec![
+            // jj comes first as jj workspaces are often colocated with a git repository
+            #[cfg(feature = "jj")]
+            DiffProvider::Jj,
             #[cfg(feature = "git")]
             DiffProvider::Git,
             DiffProvider::None,
         ];
-        DiffProviderRegistry { providers }
+        DiffProviderRegistry {
+            providers,
+            status: Arc::default(),
+        }
     }
 }
 
@@ -98,6 +308,8 @@ fn default() -> Self {
 enum DiffProvider {
     #[cfg(feature = "git")]
     Git,
+    #[cfg(feature = "jj")]
+    Jj,
     None,
 }
 
@@ -106,6 +318,38 @@ fn get_diff_base(&self, file: &Path) -> Result<Vec<u8>> {
         match self {
             #[cfg(feature = "git")]
             Self::Git => git::get_diff_base(file),
+            #[cfg(feature = "jj")]
+            Self::Jj => jj::get_diff_base(file),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn get_diff_base_at(&self, file: &Path, revision: &str) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::get_diff_base_at(file, revision),
+            #[cfg(feature = "jj")]
+            Self::Jj => jj::get_diff_base_at(file, revision),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn get_index_base(&self, file: &Path) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::get_index_base(file),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("jj has no index"),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn set_index_content(&self, file: &Path, contents: &[u8]) -> Result<()> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::set_index_content(file, contents),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("jj has no index"),
             Self::None => bail!("No diff support compiled in"),
         }
     }
@@ -114,6 +358,8 @@ fn get_current_head_name(&self, file: &Path) -> Result<Arc<ArcSwap<Box<str>>>> {
         match self {
             #[cfg(feature = "git")]
             Self::Git => git::get_current_head_name(file),
+            #[cfg(feature = "jj")]
+            Self::Jj => jj::get_current_head_name(file),
             Self::None => bail!("No diff support compiled in"),
         }
     }
@@ -126,6 +372,63 @@ fn for_each_changed_file(
         match self {
             #[cfg(feature = "git")]
             Self::Git => git::for_each_changed_file(cwd, f),
+            #[cfg(feature = "jj")]
+            Self::Jj => jj::for_each_changed_file(cwd, f),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn for_each_commit(
+        &self,
+        cwd: &Path,
+        file: Option<&Path>,
+        f: impl Fn(Result<CommitInfo>) -> bool,
+    ) -> Result<()> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::for_each_commit(cwd, file, f),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("commit history is not supported for jj"),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn show_commit(&self, cwd: &Path, revision: &str) -> Result<String> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::show_commit(cwd, revision),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("commit history is not supported for jj"),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn staged_diff(&self, cwd: &Path) -> Result<String> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::staged_diff(cwd),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("jj has no index"),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn staged_files(&self, cwd: &Path) -> Result<Vec<PathBuf>> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::staged_files(cwd),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("jj has no index"),
+            Self::None => bail!("No diff support compiled in"),
+        }
+    }
+
+    fn blame(&self, file: &Path) -> Result<FileBlame> {
+        match self {
+            #[cfg(feature = "git")]
+            Self::Git => git::blame(file),
+            #[cfg(feature = "jj")]
+            Self::Jj => bail!("blame is not supported for jj"),
             Self::None => bail!("No diff support compiled in"),
         }
     }
@@ -1,4 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 /// States for a file having been changed.
 pub enum FileChange {
@@ -27,4 +30,58 @@ pub fn path(&self) -> &Path {
             Self::Renamed { to_path, .. } => to_path,
         }
     }
+
+    pub fn status(&self) -> FileStatus {
+        match self {
+            Self::Untracked { .. } => FileStatus::Untracked,
+            Self::Modified { .. } => FileStatus::Modified,
+            Self::Conflict { .. } => FileStatus::Conflict,
+            Self::Deleted { .. } => FileStatus::Deleted,
+            Self::Renamed { .. } => FileStatus::Renamed,
+        }
+    }
+}
+
+/// The kind of a [`FileChange`], without the paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Untracked,
+    Modified,
+    Conflict,
+    Deleted,
+    Renamed,
+}
+
+impl FileStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Untracked => "untracked",
+            Self::Modified => "modified",
+            Self::Conflict => "conflict",
+            Self::Deleted => "deleted",
+            Self::Renamed => "renamed",
+        }
+    }
+}
+
+/// The status of the changed files of a working directory at one point in time. Files that are
+/// not part of the snapshot are unchanged or were changed after the snapshot was taken.
+#[derive(Debug, Default)]
+pub struct StatusSnapshot {
+    files: HashMap<PathBuf, FileStatus>,
+}
+
+impl StatusSnapshot {
+    pub fn new(changes: impl IntoIterator<Item = FileChange>) -> Self {
+        let files = changes
+            .into_iter()
+            .map(|change| (change.path().to_owned(), change.status()))
+            .collect();
+        Self { files }
+    }
+
+    /// Returns the status of the file at the absolute `path`.
+    pub fn get(&self, path: &Path) -> Option<FileStatus> {
+        self.files.get(path).copied()
+    }
 }
@@ -0,0 +1,182 @@
+use std::ops::Range;
+
+use helix_core::text_annotations::LineAnnotation;
+use helix_core::{Position, RopeSlice};
+use helix_vcs::{FillerRows, Hunk};
+
+use crate::Document;
+
+/// How a line shown in a `:diff-split` pane differs from the other pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineChange {
+    /// The line only exists in the document.
+    Added,
+    /// The line only exists in the diff base.
+    Removed,
+    /// The line was changed, both panes show a version of it.
+    Modified,
+}
+
+/// The layout of one pane of a `:diff-split`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffSplitLines {
+    /// `(line, rows)` pairs sorted by line. `rows` empty rows are rendered after `line`
+    /// so that unchanged lines line up with the other pane.
+    pub fillers: Vec<(usize, usize)>,
+    /// The changed lines of this pane, sorted by line.
+    pub changes: Vec<(Range<usize>, LineChange)>,
+}
+
+impl DiffSplitLines {
+    /// Computes the layout of both panes, `(base, doc)`, from the hunks of a diff and the
+    /// filler rows returned by `Diff::filler_rows`.
+    pub fn new(hunks: impl Iterator<Item = Hunk>, fillers: FillerRows) -> (Self, Self) {
+        let to_usize = |fillers: Vec<(u32, u32)>| {
+            fillers
+                .into_iter()
+                .map(|(line, rows)| (line as usize, rows as usize))
+                .collect()
+        };
+        let mut base = DiffSplitLines {
+            fillers: to_usize(fillers.base),
+            changes: Vec::new(),
+        };
+        let mut doc = DiffSplitLines {
+            fillers: to_usize(fillers.doc),
+            changes: Vec::new(),
+        };
+        let to_range = |lines: Range<u32>| lines.start as usize..lines.end as usize;
+        for hunk in hunks {
+            let change = if hunk.is_pure_insertion() {
+                LineChange::Added
+            } else if hunk.is_pure_removal() {
+                LineChange::Removed
+            } else {
+                LineChange::Modified
+            };
+            if !hunk.before.is_empty() {
+                base.changes.push((to_range(hunk.before), change));
+            }
+            if !hunk.after.is_empty() {
+                doc.changes.push((to_range(hunk.after), change));
+            }
+        }
+        (base, doc)
+    }
+
+    /// Returns how `line` differs from the other pane, `None` if it is unchanged.
+    pub fn line_change(&self, line: usize) -> Option<LineChange> {
+        let i = self.changes.partition_point(|(lines, _)| lines.end <= line);
+        self.changes
+            .get(i)
+            .filter(|(lines, _)| lines.contains(&line))
+            .map(|&(_, change)| change)
+    }
+
+    /// Returns the row `line` is rendered at, counting filler rows but not soft wraps.
+    pub fn line_to_row(&self, line: usize) -> usize {
+        let fillers: usize = self
+            .fillers
+            .iter()
+            .take_while(|&&(filler_line, _)| filler_line < line)
+            .map(|&(_, rows)| rows)
+            .sum();
+        line + fillers
+    }
+
+    /// Returns the line rendered at `row` together with the number of rows the row is below
+    /// the start of the line. The rows below are filler rows.
+    pub fn row_to_line(&self, row: usize) -> (usize, usize) {
+        let mut fillers = 0;
+        for &(line, rows) in &self.fillers {
+            if line + fillers >= row {
+                break;
+            }
+            if row <= line + fillers + rows {
+                return (line, row - line - fillers);
+            }
+            fillers += rows;
+        }
+        (row - fillers, 0)
+    }
+}
+
+/// Inserts the filler rows of a `:diff-split` pane.
+pub(crate) struct DiffFiller<'a> {
+    text: RopeSlice<'a>,
+    fillers: &'a [(usize, usize)],
+    idx: usize,
+}
+
+impl<'a> DiffFiller<'a> {
+    #[allow(clippy::new_ret_no_self)]
+    pub(crate) fn new(
+        doc: &'a Document,
+        lines: &'a DiffSplitLines,
+    ) -> Box<dyn LineAnnotation + 'a> {
+        Box::new(DiffFiller {
+            text: doc.text().slice(..),
+            fillers: &lines.fillers,
+            idx: 0,
+        })
+    }
+}
+
+impl LineAnnotation for DiffFiller<'_> {
+    fn reset_pos(&mut self, char_idx: usize) -> usize {
+        let line = self.text.char_to_line(char_idx.min(self.text.len_chars()));
+        self.idx = self
+            .fillers
+            .partition_point(|&(filler_line, _)| filler_line < line);
+        usize::MAX
+    }
+
+    fn insert_virtual_lines(
+        &mut self,
+        line_end_char_idx: usize,
+        _line_end_visual_pos: Position,
+        doc_line: usize,
+    ) -> Position {
+        // only pad after the last visual line of soft wrapped lines
+        if doc_line + 1 >= self.text.len_lines()
+            || line_end_char_idx != self.text.line_to_char(doc_line + 1)
+        {
+            return Position::new(0, 0);
+        }
+        while self
+            .fillers
+            .get(self.idx)
+            .is_some_and(|&(line, _)| line < doc_line)
+        {
+            self.idx += 1;
+        }
+        match self.fillers.get(self.idx) {
+            Some(&(line, rows)) if line == doc_line => {
+                self.idx += 1;
+                Position::new(rows, 0)
+            }
+            _ => Position::new(0, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        let lines = DiffSplitLines {
+            fillers: vec![(1, 2), (4, 1)],
+            changes: Vec::new(),
+        };
+        assert_eq!(lines.line_to_row(1), 1);
+        assert_eq!(lines.line_to_row(2), 4);
+        assert_eq!(lines.line_to_row(5), 8);
+        assert_eq!(lines.row_to_line(1), (1, 0));
+        assert_eq!(lines.row_to_line(3), (1, 2));
+        assert_eq!(lines.row_to_line(4), (2, 0));
+        assert_eq!(lines.row_to_line(7), (4, 1));
+        assert_eq!(lines.row_to_line(8), (5, 0));
+    }
+}
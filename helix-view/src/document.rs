@@ -15,7 +15,9 @@
 use helix_event::TaskController;
 use helix_lsp::util::lsp_pos_to_pos;
 use helix_stdx::faccess::{copy_metadata, readonly};
-use helix_vcs::{DiffHandle, DiffProviderRegistry};
+use helix_vcs::{
+    find_conflicts, CommitInfo, Conflict, DiffHandle, DiffProviderRegistry, FileBlame,
+};
 use once_cell::sync::OnceCell;
 use thiserror;
 
@@ -44,8 +46,9 @@
 };
 
 use crate::{
+    annotations::diff_split::DiffSplitLines,
     editor::Config,
-    events::{DocumentDidChange, SelectionDidChange},
+    events::{DiffBasesDidChange, DocumentDidChange, SelectionDidChange},
     expansion,
     view::ViewPosition,
     DocumentId, Editor, Theme, View, ViewId,
@@ -61,6 +64,11 @@
 
 pub const SCRATCH_BUFFER_NAME: &str = "[scratch]";
 
+/// The [diff base revision](Document::diff_base_revision) of the last commit.
+pub const DIFF_BASE_HEAD: &str = "HEAD";
+/// The [diff base revision](Document::diff_base_revision) of the staged contents.
+pub const DIFF_BASE_INDEX: &str = "index";
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Mode {
     Normal = 0,
@@ -150,12 +158,16 @@ pub struct Document {
     /// To know if they're up-to-date, check the `id` field in `DocumentInlayHints`.
     pub(crate) inlay_hints: HashMap<ViewId, DocumentInlayHints>,
     pub(crate) jump_labels: HashMap<ViewId, Vec<Overlay>>,
+    /// The layout of the views showing the document in a `:diff-split`.
+    pub(crate) diff_split_lines: HashMap<ViewId, DiffSplitLines>,
     /// Set to `true` when the document is updated, reset to `false` on the next inlay hints
     /// update from the LSP
     pub inlay_hints_oudated: bool,
 
     path: Option<PathBuf>,
     relative_path: OnceCell<Option<PathBuf>>,
+    /// Shown instead of [`SCRATCH_BUFFER_NAME`] while the document has no path.
+    name: Option<String>,
     encoding: &'static encoding::Encoding,
     has_bom: bool,
 
@@ -197,7 +209,14 @@ pub struct Document {
     pub(crate) language_servers: HashMap<LanguageServerName, Arc<Client>>,
 
     diff_handle: Option<DiffHandle>,
+    index_diff_handle: Option<DiffHandle>,
+    /// Overrides [`Config::diff_base`](crate::editor::Config::diff_base) for this document.
+    diff_base_revision: Option<String>,
     version_control_head: Option<Arc<ArcSwap<Box<str>>>>,
+    /// The commits that last changed each line of the file at `HEAD`.
+    blame: Option<Arc<FileBlame>>,
+    /// The merge conflicts in the document, see [`Document::detect_conflicts`].
+    conflicts: Vec<Conflict>,
 
     // when document was used for most-recent-used buffer picker
     pub focused_at: std::time::Instant,
@@ -213,6 +232,17 @@ pub struct Document {
     pub color_swatch_controller: TaskController,
     pub pull_diagnostic_controller: TaskController,
 
+    /// Values of the variables in scope, shown while the debuggee is stopped in this document.
+    pub inline_values: Vec<InlineAnnotation>,
+
+    /// Sorted subjects of recent commits, completed on the subject line of a commit message.
+    /// Listing them walks the history so it is only done on the first completion.
+    pub commit_subjects: Arc<OnceCell<Arc<[String]>>>,
+
+    /// Occurrences of the symbol under the cursor, per view, as reported by the language server.
+    pub(crate) document_highlights: HashMap<ViewId, DocumentHighlights>,
+    pub document_highlight_controller: TaskController,
+
     // NOTE: this field should eventually go away - we should use the Editor's syn_loader instead
     // of storing a copy on every doc. Then we can remove the surrounding `Arc` and use the
     // `ArcSwap` directly.
@@ -226,6 +256,51 @@ pub struct DocumentColorSwatches {
     pub color_swatches_padding: Vec<InlineAnnotation>,
 }
 
+/// Char ranges of `textDocument/documentHighlight` results for a single `(Document, View)` combo,
+/// grouped by their kind.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentHighlights {
+    /// Occurrences with the `TEXT` kind or without a kind.
+    pub text: Vec<std::ops::Range<usize>>,
+    /// Occurrences where the symbol is read.
+    pub read: Vec<std::ops::Range<usize>>,
+    /// Occurrences where the symbol is written to.
+    pub write: Vec<std::ops::Range<usize>>,
+}
+
+impl DocumentHighlights {
+    /// Whether `pos` lies within one of the highlighted occurrences.
+    pub fn contains(&self, pos: usize) -> bool {
+        [&self.text, &self.read, &self.write]
+            .into_iter()
+            .flatten()
+            .any(|range| range.contains(&pos))
+    }
+}
+
+/// The blame information of a single line, see [`Document::line_blame`].
+#[derive(Debug, Clone, Copy)]
+pub enum LineBlame<'a> {
+    Committed(&'a CommitInfo),
+    /// The line has changes that are not committed yet.
+    Uncommitted,
+}
+
+impl Display for LineBlame<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Committed(commit) => write!(
+                f,
+                "{}, {} • {}",
+                commit.author,
+                commit.relative_time(),
+                commit.summary
+            ),
+            Self::Uncommitted => f.write_str("Not committed yet"),
+        }
+    }
+}
+
 /// Inlay hints for a single `(Document, View)` combo.
 ///
 /// There are `*_inlay_hints` field for each kind of hints an LSP can send since we offer the
@@ -693,12 +768,14 @@ pub fn from(
         let line_ending = config.load().default_line_ending.into();
         let changes = ChangeSet::new(text.slice(..));
         let old_state = None;
+        let conflicts = find_conflicts(text.slice(..));
 
         Self {
             id: DocumentId::default(),
             active_snippet: None,
             path: None,
             relative_path: OnceCell::new(),
+            name: None,
             encoding,
             has_bom,
             text,
@@ -723,16 +800,25 @@ pub fn from(
             modified_since_accessed: false,
             language_servers: HashMap::new(),
             diff_handle: None,
+            index_diff_handle: None,
+            diff_base_revision: None,
             config,
             version_control_head: None,
+            blame: None,
+            conflicts,
             focused_at: std::time::Instant::now(),
             readonly: false,
             jump_labels: HashMap::new(),
+            diff_split_lines: HashMap::new(),
             color_swatches: None,
             color_swatch_controller: TaskController::new(),
+            inline_values: Vec::new(),
+            commit_subjects: Arc::default(),
             syn_loader,
             previous_diagnostic_id: None,
             pull_diagnostic_controller: TaskController::new(),
+            document_highlights: HashMap::new(),
+            document_highlight_controller: TaskController::new(),
         }
     }
 
@@ -1244,15 +1330,51 @@ pub fn reload(
         self.reset_modified();
         self.pickup_last_saved_time();
         self.detect_indent_and_line_ending();
+        self.detect_conflicts();
+
+        self.update_diff_bases(provider_registry);
+
+        Ok(())
+    }
 
-        match provider_registry.get_diff_base(&path) {
+    /// Loads the bases of the diffs of this document from version control again.
+    pub fn update_diff_bases(&mut self, provider_registry: &DiffProviderRegistry) {
+        let Some(path) = self.path().cloned() else {
+            return;
+        };
+        let revision = self.diff_base_revision();
+        let diff_base = match revision.as_str() {
+            DIFF_BASE_HEAD => provider_registry.get_diff_base(&path),
+            DIFF_BASE_INDEX => provider_registry.get_index_base(&path),
+            revision => provider_registry.get_diff_base_at(&path, revision),
+        };
+        match diff_base {
             Some(diff_base) => self.set_diff_base(diff_base),
             None => self.diff_handle = None,
         }
+        match provider_registry.get_index_base(&path) {
+            Some(index_base) => self.set_index_diff_base(index_base),
+            None => self.index_diff_handle = None,
+        }
 
         self.version_control_head = provider_registry.get_current_head_name(&path);
 
-        Ok(())
+        helix_event::dispatch(DiffBasesDidChange { doc: self });
+    }
+
+    /// The revision the changes of this document are compared against, see
+    /// [`Config::diff_base`](crate::editor::Config::diff_base).
+    pub fn diff_base_revision(&self) -> String {
+        match &self.diff_base_revision {
+            Some(revision) => revision.clone(),
+            None => self.config.load().diff_base.clone(),
+        }
+    }
+
+    /// Overrides the revision the changes of this document are compared against. `None`
+    /// restores the configured default. Takes effect on [`Self::update_diff_bases`].
+    pub fn set_diff_base_revision(&mut self, revision: Option<String>) {
+        self.diff_base_revision = revision;
     }
 
     /// Sets the [`Document`]'s encoding with the encoding correspondent to `label`.
@@ -1374,6 +1496,8 @@ pub fn remove_view(&mut self, view_id: ViewId) {
         self.selections.remove(&view_id);
         self.inlay_hints.remove(&view_id);
         self.jump_labels.remove(&view_id);
+        self.diff_split_lines.remove(&view_id);
+        self.document_highlights.remove(&view_id);
     }
 
     /// Apply a [`Transaction`] to the [`Document`] to change its text.
@@ -1454,10 +1578,14 @@ fn apply_impl(
 
         // TODO: all of that should likely just be hooks
         // start computing the diff in parallel
-        if let Some(diff_handle) = &self.diff_handle {
+        for diff_handle in self.diff_handle.iter().chain(&self.index_diff_handle) {
             diff_handle.update_document(self.text.clone(), false);
         }
 
+        if !self.conflicts.is_empty() {
+            self.detect_conflicts();
+        }
+
         // map diagnostics over changes too
         changes.update_positions(self.diagnostics.iter_mut().map(|diagnostic| {
             let assoc = if diagnostic.starts_at_word {
@@ -1524,6 +1652,7 @@ fn apply_impl(
             apply_inlay_hint_changes(other_inlay_hints);
             apply_inlay_hint_changes(padding_after_inlay_hints);
         }
+        apply_inlay_hint_changes(&mut self.inline_values);
 
         helix_event::dispatch(DocumentDidChange {
             doc: self,
@@ -1578,6 +1707,17 @@ pub fn apply(&mut self, transaction: &Transaction, view_id: ViewId) -> bool {
         self.apply_inner(transaction, view_id, true)
     }
 
+    /// Apply a [`Transaction`] without recording it in the history. This is meant for scratch
+    /// buffers whose text is generated, like logs, so that their history doesn't grow with
+    /// every update. The history is cleared as its revisions don't apply to the new text.
+    pub fn apply_untracked(&mut self, transaction: &Transaction, view_id: ViewId) -> bool {
+        let success = self.apply_impl(transaction, view_id, true);
+        self.history = Cell::new(History::default());
+        self.changes = ChangeSet::new(self.text.slice(..));
+        self.old_state = None;
+        success
+    }
+
     /// Apply a [`Transaction`] to the [`Document`] to change its text
     /// without notifying the language servers. This is useful for temporary transactions
     /// that must not influence the server.
@@ -1868,21 +2008,73 @@ pub fn supports_language_server(&self, id: LanguageServerId) -> bool {
         self.language_servers().any(|l| l.id() == id)
     }
 
+    /// Returns the merge conflicts in the document, sorted by line.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// Searches the whole document for merge conflicts. This happens when the document is
+    /// loaded. Changes only search again while there are conflicts left, so that documents
+    /// without conflicts are not searched after every edit.
+    pub fn detect_conflicts(&mut self) {
+        self.conflicts = find_conflicts(self.text.slice(..));
+    }
+
     pub fn diff_handle(&self) -> Option<&DiffHandle> {
         self.diff_handle.as_ref()
     }
 
     /// Intialize/updates the differ for this document with a new base.
     pub fn set_diff_base(&mut self, diff_base: Vec<u8>) {
-        if let Ok((diff_base, ..)) = from_reader(&mut diff_base.as_slice(), Some(self.encoding)) {
-            if let Some(differ) = &self.diff_handle {
-                differ.update_diff_base(diff_base);
-                return;
+        let differ = self.diff_handle.take();
+        self.diff_handle = self.update_differ(differ, diff_base);
+    }
+
+    /// Returns the differ against the contents of the file in the index. Changes that are
+    /// in the [`Self::diff_handle`] but not in this diff have been staged.
+    pub fn index_diff_handle(&self) -> Option<&DiffHandle> {
+        self.index_diff_handle.as_ref()
+    }
+
+    /// Intialize/updates the differ against the contents of the file in the index.
+    pub fn set_index_diff_base(&mut self, index_base: Vec<u8>) {
+        let differ = self.index_diff_handle.take();
+        self.index_diff_handle = self.update_differ(differ, index_base);
+    }
+
+    /// Replaces the contents of the file in the index with `text`, staging the differences
+    /// to `HEAD`.
+    pub fn set_index_text(
+        &mut self,
+        text: Rope,
+        provider_registry: &DiffProviderRegistry,
+    ) -> Result<(), Error> {
+        let path = self
+            .path()
+            .ok_or_else(|| anyhow!("scratch buffers are not tracked in the index"))?;
+        let mut contents = Vec::with_capacity(text.len_bytes());
+        helix_lsp::block_on(to_writer(
+            &mut contents,
+            (self.encoding, self.has_bom),
+            &text,
+        ))?;
+        provider_registry.set_index_content(path, &contents)?;
+        match &self.index_diff_handle {
+            Some(differ) => {
+                differ.update_diff_base(text);
             }
-            self.diff_handle = Some(DiffHandle::new(diff_base, self.text.clone()))
-        } else {
-            self.diff_handle = None;
+            None => self.index_diff_handle = Some(DiffHandle::new(text, self.text.clone())),
         }
+        Ok(())
+    }
+
+    fn update_differ(&self, differ: Option<DiffHandle>, diff_base: Vec<u8>) -> Option<DiffHandle> {
+        let (diff_base, ..) = from_reader(&mut diff_base.as_slice(), Some(self.encoding)).ok()?;
+        if let Some(differ) = differ {
+            differ.update_diff_base(diff_base);
+            return Some(differ);
+        }
+        Some(DiffHandle::new(diff_base, self.text.clone()))
     }
 
     pub fn version_control_head(&self) -> Option<Arc<Box<str>>> {
@@ -1896,6 +2088,33 @@ pub fn set_version_control_head(
         self.version_control_head = version_control_head;
     }
 
+    pub fn set_blame(&mut self, blame: Option<Arc<FileBlame>>) {
+        self.blame = blame;
+    }
+
+    /// Returns the commit that last changed the given line of the document. Lines that were
+    /// edited since `HEAD` are reported as [`LineBlame::Uncommitted`].
+    pub fn line_blame(&self, line: usize) -> Option<LineBlame<'_>> {
+        let blame = self.blame.as_ref()?;
+        // The blame describes the lines at `HEAD`. When diffing against another revision the
+        // index is the closest match, it only differs from `HEAD` by the staged changes.
+        let differ = if self.diff_base_revision() == DIFF_BASE_HEAD {
+            &self.diff_handle
+        } else {
+            &self.index_diff_handle
+        };
+        let base_line = match differ {
+            Some(handle) => match handle.load().base_line(line as u32) {
+                Some(base_line) => base_line as usize,
+                None => return Some(LineBlame::Uncommitted),
+            },
+            None => line,
+        };
+        blame
+            .line(base_line)
+            .map(|commit| LineBlame::Committed(commit))
+    }
+
     #[inline]
     /// Tree-sitter AST tree
     pub fn syntax(&self) -> Option<&Syntax> {
@@ -2001,7 +2220,17 @@ pub fn relative_path(&self) -> Option<&Path> {
 
     pub fn display_name(&self) -> Cow<'_, str> {
         self.relative_path()
-            .map_or_else(|| SCRATCH_BUFFER_NAME.into(), |path| path.to_string_lossy())
+            .map_or_else(|| self.scratch_name().into(), |path| path.to_string_lossy())
+    }
+
+    /// The name of the document while it has no path, [`SCRATCH_BUFFER_NAME`] unless set with
+    /// [`Self::set_scratch_name`].
+    pub fn scratch_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(SCRATCH_BUFFER_NAME)
+    }
+
+    pub fn set_scratch_name(&mut self, name: Option<String>) {
+        self.name = name;
     }
 
     // transact(Fn) ?
@@ -2279,6 +2508,19 @@ pub fn remove_jump_labels(&mut self, view_id: ViewId) {
         self.jump_labels.remove(&view_id);
     }
 
+    /// Returns the layout of the `:diff-split` pane showing the document in `view_id`.
+    pub fn diff_split_lines(&self, view_id: ViewId) -> Option<&DiffSplitLines> {
+        self.diff_split_lines.get(&view_id)
+    }
+
+    pub fn set_diff_split_lines(&mut self, view_id: ViewId, lines: DiffSplitLines) {
+        self.diff_split_lines.insert(view_id, lines);
+    }
+
+    pub fn remove_diff_split_lines(&mut self, view_id: ViewId) {
+        self.diff_split_lines.remove(&view_id);
+    }
+
     /// Get the inlay hints for this document and `view_id`.
     pub fn inlay_hints(&self, view_id: ViewId) -> Option<&DocumentInlayHints> {
         self.inlay_hints.get(&view_id)
@@ -2290,6 +2532,24 @@ pub fn reset_all_inlay_hints(&mut self) {
         self.inlay_hints = Default::default();
     }
 
+    pub fn set_document_highlights(&mut self, view_id: ViewId, highlights: DocumentHighlights) {
+        self.document_highlights.insert(view_id, highlights);
+    }
+
+    pub fn document_highlights(&self, view_id: ViewId) -> Option<&DocumentHighlights> {
+        self.document_highlights.get(&view_id)
+    }
+
+    pub fn clear_document_highlights(&mut self, view_id: ViewId) {
+        self.document_highlights.remove(&view_id);
+    }
+
+    /// Removes the document highlights of all views, for example because the text changed and
+    /// they no longer line up with the symbol occurrences.
+    pub fn reset_all_document_highlights(&mut self) {
+        self.document_highlights = Default::default();
+    }
+
     pub fn has_language_server_with_feature(&self, feature: LanguageServerFeature) -> bool {
         self.language_servers_with_feature(feature).next().is_some()
     }
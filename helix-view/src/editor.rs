@@ -1,8 +1,12 @@
 use crate::{
-    annotations::diagnostics::{DiagnosticFilter, InlineDiagnosticsConfig},
+    annotations::{
+        diagnostics::{DiagnosticFilter, InlineDiagnosticsConfig},
+        diff_split::DiffSplitLines,
+    },
     clipboard::ClipboardProvider,
     document::{
         DocumentOpenError, DocumentSavedEventFuture, DocumentSavedEventResult, Mode, SavePoint,
+        DIFF_BASE_HEAD,
     },
     events::{DocumentDidClose, DocumentDidOpen, DocumentFocusLost},
     graphics::{CursorKind, Rect},
@@ -12,6 +16,7 @@
     register::Registers,
     theme::{self, Theme},
     tree::{self, Tree},
+    view::ViewPosition,
     Document, DocumentId, View, ViewId,
 };
 use helix_event::dispatch;
@@ -331,6 +336,8 @@ pub struct Config {
     pub search: SearchConfig,
     pub lsp: LspConfig,
     pub terminal: Option<TerminalConfig>,
+    /// Debugger configuration.
+    pub debugger: DebuggerConfig,
     /// Column numbers at which to draw the rulers. Defaults to `[]`, meaning no rulers.
     pub rulers: Vec<u16>,
     #[serde(default)]
@@ -374,6 +381,14 @@ pub struct Config {
     /// Display diagnostic below the line they occur.
     pub inline_diagnostics: InlineDiagnosticsConfig,
     pub end_of_line_diagnostics: DiagnosticFilter,
+    /// Display the author, date and summary of the commit that last changed the cursor line
+    /// at the end of the line. Defaults to `false`.
+    pub inline_blame: bool,
+    /// Show the changes staged for the commit next to a git commit message. Defaults to `true`.
+    pub commit_message_diff: bool,
+    /// The revision that the changes shown in the gutter are compared against: `index`, `HEAD`
+    /// or any other revision like `main` or `main...HEAD`. Defaults to `HEAD`.
+    pub diff_base: String,
     // Set to override the default clipboard provider
     pub clipboard_provider: ClipboardProvider,
     /// Whether to read settings from [EditorConfig](https://editorconfig.org) files. Defaults to
@@ -419,6 +434,23 @@ pub struct TerminalConfig {
     pub args: Vec<String>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DebuggerConfig {
+    /// Where to run the debuggee when the debug adapter asks for a terminal.
+    pub run_in_terminal: RunInTerminalConfig,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunInTerminalConfig {
+    /// In the external terminal of `editor.terminal`, or in the console if none is configured.
+    #[default]
+    External,
+    /// As a child process of the editor, with its output shown in the debug console.
+    Console,
+}
+
 #[cfg(windows)]
 pub fn get_terminal_provider() -> Option<TerminalConfig> {
     use helix_stdx::env::binary_exists;
@@ -487,6 +519,8 @@ pub struct LspConfig {
     pub snippets: bool,
     /// Whether to include declaration in the goto reference query
     pub goto_reference_include_declaration: bool,
+    /// Automatically highlight occurrences of the symbol under the cursor
+    pub auto_document_highlight: bool,
 }
 
 impl Default for LspConfig {
@@ -502,6 +536,7 @@ fn default() -> Self {
             snippets: true,
             goto_reference_include_declaration: true,
             display_color_swatches: true,
+            auto_document_highlight: false,
         }
     }
 }
@@ -644,6 +679,9 @@ pub enum StatusLineElement {
 
     /// The base of current working directory
     CurrentWorkingDirectory,
+
+    /// The state of the active debugging session
+    Debug,
 }
 
 // Cursor shape is read and used on every rendered frame and so needs
@@ -1045,6 +1083,7 @@ fn default() -> Self {
             search: SearchConfig::default(),
             lsp: LspConfig::default(),
             terminal: get_terminal_provider(),
+            debugger: DebuggerConfig::default(),
             rulers: Vec::new(),
             whitespace: WhitespaceConfig::default(),
             bufferline: BufferLine::default(),
@@ -1069,6 +1108,9 @@ fn default() -> Self {
             jump_label_alphabet: ('a'..='z').collect(),
             inline_diagnostics: InlineDiagnosticsConfig::default(),
             end_of_line_diagnostics: DiagnosticFilter::Enable(Severity::Hint),
+            inline_blame: false,
+            commit_message_diff: true,
+            diff_base: DIFF_BASE_HEAD.to_string(),
             clipboard_provider: ClipboardProvider::default(),
             editor_config: true,
             rainbow_brackets: false,
@@ -1086,11 +1128,13 @@ fn default() -> Self {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Breakpoint {
     pub id: Option<usize>,
     pub verified: bool,
     pub message: Option<String>,
+    /// Disabled breakpoints are kept by the editor but not sent to the debug adapter.
+    pub enabled: bool,
 
     pub line: usize,
     pub column: Option<usize>,
@@ -1099,6 +1143,314 @@ pub struct Breakpoint {
     pub log_message: Option<String>,
 }
 
+impl Default for Breakpoint {
+    fn default() -> Self {
+        Self {
+            id: None,
+            verified: false,
+            message: None,
+            enabled: true,
+            line: 0,
+            column: None,
+            condition: None,
+            hit_condition: None,
+            log_message: None,
+        }
+    }
+}
+
+/// A breakpoint on entering a function, set by name.
+#[derive(Debug, Clone)]
+pub struct FunctionBreakpoint {
+    pub id: Option<usize>,
+    pub verified: bool,
+    pub message: Option<String>,
+    pub enabled: bool,
+
+    pub name: String,
+    pub condition: Option<String>,
+    pub hit_condition: Option<String>,
+}
+
+impl FunctionBreakpoint {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: None,
+            verified: false,
+            message: None,
+            enabled: true,
+            name,
+            condition: None,
+            hit_condition: None,
+        }
+    }
+}
+
+/// A breakpoint on accessing a variable, also known as watchpoint.
+#[derive(Debug, Clone)]
+pub struct DataBreakpoint {
+    pub id: Option<usize>,
+    pub verified: bool,
+    pub message: Option<String>,
+    pub enabled: bool,
+
+    /// Identifier of the data assigned by the debug adapter.
+    pub data_id: String,
+    /// Human readable description of the data, for example the name of the variable.
+    pub description: String,
+    pub access_type: Option<dap::DataBreakpointAccessType>,
+    /// Whether the data identifier is still valid in later debug sessions.
+    pub can_persist: bool,
+    pub condition: Option<String>,
+    pub hit_condition: Option<String>,
+}
+
+/// A breakpoint on a memory address, relative to an instruction reference.
+#[derive(Debug, Clone)]
+pub struct InstructionBreakpoint {
+    pub id: Option<usize>,
+    pub verified: bool,
+    pub message: Option<String>,
+    pub enabled: bool,
+
+    pub instruction_reference: String,
+    pub offset: Option<i64>,
+    pub condition: Option<String>,
+    pub hit_condition: Option<String>,
+}
+
+impl InstructionBreakpoint {
+    pub fn new(instruction_reference: String, offset: Option<i64>) -> Self {
+        Self {
+            id: None,
+            verified: false,
+            message: None,
+            enabled: true,
+            instruction_reference,
+            offset,
+            condition: None,
+            hit_condition: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleCategory {
+    /// Messages of the debug adapter.
+    Console,
+    /// Messages of the debug adapter that should stand out.
+    Important,
+    /// Standard output of the debuggee.
+    Stdout,
+    /// Standard error of the debuggee.
+    Stderr,
+    /// An expression entered in the debug console.
+    Input,
+    /// The result of evaluating an [`ConsoleCategory::Input`].
+    Result,
+}
+
+impl ConsoleCategory {
+    /// Parses the category of a DAP `output` event. Categories other than `telemetry` which are
+    /// unknown are treated as `console`, as per the specification.
+    pub fn from_output_category(category: Option<&str>) -> Option<Self> {
+        match category {
+            Some("telemetry") => None,
+            Some("important") => Some(Self::Important),
+            Some("stdout") => Some(Self::Stdout),
+            Some("stderr") => Some(Self::Stderr),
+            _ => Some(Self::Console),
+        }
+    }
+}
+
+impl std::str::FromStr for ConsoleCategory {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "console" => Ok(Self::Console),
+            "important" => Ok(Self::Important),
+            "stdout" => Ok(Self::Stdout),
+            "stderr" => Ok(Self::Stderr),
+            _ => anyhow::bail!(
+                "Invalid output category '{s}', expected console, important, stdout or stderr"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    pub category: ConsoleCategory,
+    pub text: String,
+}
+
+/// Output of the debuggee and the debug adapter, together with the expressions evaluated in the
+/// debug console. Entries are kept across debugging sessions.
+#[derive(Debug, Default)]
+pub struct DebugConsole {
+    pub entries: Vec<ConsoleEntry>,
+    /// The scratch buffer displaying the console, if it has been opened.
+    pub doc: Option<DocumentId>,
+    /// Only output of this category is displayed in the console buffer. Inputs and their
+    /// results are always displayed.
+    pub filter: Option<ConsoleCategory>,
+    /// Whether the displayed text does not end with a line ending.
+    mid_line: bool,
+}
+
+impl DebugConsole {
+    /// Records an entry, returning the text that has to be appended to the console buffer.
+    pub fn push(&mut self, category: ConsoleCategory, text: String) -> Option<String> {
+        let entry = ConsoleEntry { category, text };
+        let display = self.display(&entry);
+        self.entries.push(entry);
+        display
+    }
+
+    /// The text of the console buffer for the current filter.
+    pub fn contents(&mut self) -> String {
+        self.mid_line = false;
+        let entries = std::mem::take(&mut self.entries);
+        let contents = entries
+            .iter()
+            .filter_map(|entry| self.display(entry))
+            .collect();
+        self.entries = entries;
+        contents
+    }
+
+    fn display(&mut self, entry: &ConsoleEntry) -> Option<String> {
+        let mut text = match entry.category {
+            ConsoleCategory::Input => format!("> {}\n", entry.text),
+            ConsoleCategory::Result => format!("{}\n", entry.text),
+            category if self.filter.is_none_or(|filter| filter == category) => entry.text.clone(),
+            _ => return None,
+        };
+        // Inputs and results always start on their own line, even if the program output did
+        // not end with a line ending.
+        if self.mid_line
+            && matches!(
+                entry.category,
+                ConsoleCategory::Input | ConsoleCategory::Result
+            )
+        {
+            text.insert(0, '\n');
+        }
+        if !text.is_empty() {
+            self.mid_line = !text.ends_with('\n');
+        }
+        Some(text)
+    }
+}
+
+/// An expression which is evaluated whenever the debuggee stops or the stack frame changes.
+#[derive(Debug, Clone)]
+pub struct DebugWatch {
+    pub expression: String,
+    /// The value in the current stack frame, or the error reported by the debug adapter.
+    /// `None` until the expression has been evaluated.
+    pub value: Option<Result<WatchValue, String>>,
+}
+
+impl DebugWatch {
+    pub fn new(expression: String) -> Self {
+        Self {
+            expression,
+            value: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WatchValue {
+    pub value: String,
+    pub ty: Option<String>,
+    /// Reference used to fetch the children of structured values, `0` if there are none.
+    pub variables_reference: usize,
+    pub expanded: bool,
+    /// Named children of the value, fetched when the value is expanded.
+    pub children: Vec<(String, WatchValue)>,
+}
+
+impl WatchValue {
+    pub fn has_children(&self) -> bool {
+        self.variables_reference > 0
+    }
+
+    /// Sets the children of a structured value from its variables.
+    pub fn set_children(&mut self, variables: Vec<dap::Variable>) {
+        self.children = variables
+            .into_iter()
+            .map(|variable| {
+                let child = WatchValue {
+                    value: variable.value,
+                    ty: variable.ty,
+                    variables_reference: variable.variables_reference,
+                    ..Default::default()
+                };
+                (variable.name, child)
+            })
+            .collect();
+    }
+}
+
+/// The instructions around the instruction pointer of the current stack frame.
+#[derive(Debug, Default)]
+pub struct Disassembly {
+    /// The scratch buffer displaying the instructions, if it has been opened.
+    pub doc: Option<DocumentId>,
+    /// The displayed instructions, one per line of the buffer.
+    pub instructions: Vec<dap::DisassembledInstruction>,
+}
+
+/// A hex dump of the memory at a memory reference, for example the one of a variable.
+#[derive(Debug, Default)]
+pub struct MemoryView {
+    /// The scratch buffer displaying the memory, if it has been opened.
+    pub doc: Option<DocumentId>,
+    pub memory_reference: Option<String>,
+}
+
+/// A document shown next to its diff base by `:diff-split`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSplit {
+    pub doc: DocumentId,
+    pub view: ViewId,
+    /// The read-only scratch buffer showing the diff base.
+    pub base_doc: DocumentId,
+    pub base_view: ViewId,
+    /// The versions of the diff and its base the panes were last updated to.
+    diff_version: Option<u64>,
+    base_version: Option<u64>,
+    /// The pane the other one was last scrolled along with, and its position then.
+    aligned_to: Option<(ViewId, ViewPosition)>,
+}
+
+impl DiffSplit {
+    pub fn new(doc: DocumentId, view: ViewId, base_doc: DocumentId, base_view: ViewId) -> Self {
+        Self {
+            doc,
+            view,
+            base_doc,
+            base_view,
+            diff_version: None,
+            base_version: None,
+            aligned_to: None,
+        }
+    }
+}
+
+/// The staged changes shown next to a commit message, see `commit-message-diff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitDiff {
+    /// The view of the commit message.
+    pub view: ViewId,
+    /// The read-only scratch buffer showing the staged diff.
+    pub diff_doc: DocumentId,
+}
+
 use futures_util::stream::{Flatten, Once};
 
 type Diagnostics = BTreeMap<Uri, Vec<(lsp::Diagnostic, DiagnosticProvider)>>;
@@ -1122,11 +1474,22 @@ pub struct Editor {
     pub macro_recording: Option<(char, Vec<KeyEvent>)>,
     pub macro_replaying: Vec<char>,
     pub language_servers: helix_lsp::Registry,
+    /// Work done progress reported by the language servers.
+    pub lsp_progress: helix_lsp::LspProgressMap,
     pub diagnostics: Diagnostics,
     pub diff_providers: DiffProviderRegistry,
 
     pub debug_adapters: dap::registry::Registry,
     pub breakpoints: HashMap<PathBuf, Vec<Breakpoint>>,
+    pub function_breakpoints: Vec<FunctionBreakpoint>,
+    pub data_breakpoints: Vec<DataBreakpoint>,
+    pub instruction_breakpoints: Vec<InstructionBreakpoint>,
+    pub debug_console: DebugConsole,
+    pub debug_watches: Vec<DebugWatch>,
+    pub disassembly: Disassembly,
+    pub memory_view: MemoryView,
+    pub diff_split: Option<DiffSplit>,
+    pub commit_diff: Option<CommitDiff>,
 
     pub syn_loader: Arc<ArcSwap<syntax::Loader>>,
     pub theme_loader: Arc<theme::Loader>,
@@ -1268,10 +1631,20 @@ pub fn new(
             macro_replaying: Vec::new(),
             theme: theme_loader.default(),
             language_servers,
+            lsp_progress: helix_lsp::LspProgressMap::new(),
             diagnostics: Diagnostics::new(),
             diff_providers: DiffProviderRegistry::default(),
             debug_adapters: dap::registry::Registry::new(),
             breakpoints: HashMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
+            instruction_breakpoints: Vec::new(),
+            debug_console: DebugConsole::default(),
+            debug_watches: Vec::new(),
+            disassembly: Disassembly::default(),
+            memory_view: MemoryView::default(),
+            diff_split: None,
+            commit_diff: None,
             syn_loader,
             theme_loader,
             last_theme: None,
@@ -1337,6 +1710,11 @@ pub fn refresh_config(&mut self, old_config: &Config) {
         self.auto_pairs = (&config.auto_pairs).into();
         self.reset_idle_timer();
         self._refresh();
+        if config.diff_base != old_config.diff_base {
+            for doc in self.documents.values_mut() {
+                doc.update_diff_bases(&self.diff_providers);
+            }
+        }
         helix_event::dispatch(crate::events::ConfigDidChange {
             editor: self,
             old: old_config,
@@ -1789,7 +2167,8 @@ fn new_document(&mut self, mut doc: Document) -> DocumentId {
         id
     }
 
-    fn new_file_from_document(&mut self, action: Action, doc: Document) -> DocumentId {
+    /// Opens the new document `doc`, which has no path, like a scratch buffer.
+    pub fn new_file_from_document(&mut self, action: Action, doc: Document) -> DocumentId {
         let id = self.new_document(doc);
         self.switch(id, action);
         id
@@ -1802,6 +2181,157 @@ pub fn new_file(&mut self, action: Action) -> DocumentId {
         )
     }
 
+    /// Opens a new scratch buffer containing `text`.
+    pub fn new_file_from_text(&mut self, action: Action, text: &str) -> DocumentId {
+        let doc = Document::from(
+            helix_core::Rope::from(text),
+            None,
+            self.config.clone(),
+            self.syn_loader.clone(),
+        );
+        self.new_file_from_document(action, doc)
+    }
+
+    /// Appends `text` to the end of the document with `doc_id`.
+    ///
+    /// This is meant for scratch buffers that are filled in the background, so the document is
+    /// kept unmodified and can be closed without a prompt. Returns `false` if the document does
+    /// not exist anymore.
+    pub fn append_to_document(&mut self, doc_id: DocumentId, text: &str) -> bool {
+        self.change_scratch_document(doc_id, text, false)
+    }
+
+    /// Replaces the whole text of the document with `doc_id`, like
+    /// [`Editor::append_to_document`].
+    pub fn replace_document_text(&mut self, doc_id: DocumentId, text: &str) -> bool {
+        self.change_scratch_document(doc_id, text, true)
+    }
+
+    fn change_scratch_document(&mut self, doc_id: DocumentId, text: &str, replace: bool) -> bool {
+        let Some(doc) = self.documents.get_mut(&doc_id) else {
+            return false;
+        };
+        // the document may not be visible, it is then changed without a view
+        let view_id = self
+            .tree
+            .views()
+            .find(|(view, _)| view.doc == doc_id)
+            .map(|(view, _)| view.id);
+        if let Some(view_id) = view_id {
+            doc.ensure_view_init(view_id);
+        }
+
+        let end = doc.text().len_chars();
+        let start = if replace { 0 } else { end };
+        let transaction = helix_core::Transaction::change(
+            doc.text(),
+            [(start, end, Some(text.into()))].into_iter(),
+        );
+        doc.apply_untracked(&transaction, view_id.unwrap_or_default());
+        doc.reset_modified();
+        true
+    }
+
+    /// Updates the `:diff-split` panes once the diff changed and scrolls the pane that isn't
+    /// focused along with the focused one once that one scrolled. The split is ended once one of
+    /// its views was closed or shows another document.
+    ///
+    /// The diff is computed in the background which requests a redraw once it is done, this is
+    /// called before rendering and only compares versions unless something changed.
+    pub fn sync_diff_split(&mut self) {
+        let Some(mut split) = self.diff_split else {
+            return;
+        };
+        let shows = |view: ViewId, doc: DocumentId| {
+            self.tree.try_get(view).is_some_and(|view| view.doc == doc)
+        };
+        let handle = (shows(split.view, split.doc) && shows(split.base_view, split.base_doc))
+            .then(|| self.documents.get(&split.doc)?.diff_handle())
+            .flatten();
+        let Some(handle) = handle else {
+            self.end_diff_split();
+            return;
+        };
+
+        let diff = handle.load();
+        if split.diff_version != Some(diff.version()) {
+            split.diff_version = Some(diff.version());
+            let base_text = (split.base_version != Some(diff.diff_base_version()))
+                .then(|| diff.diff_base().clone());
+            split.base_version = Some(diff.diff_base_version());
+            let hunks = (0..diff.len()).map(|n| diff.nth_hunk(n));
+            let (base_lines, doc_lines) = DiffSplitLines::new(hunks, diff.filler_rows());
+            drop(diff);
+
+            if let Some(base_text) = base_text {
+                if *self.documents[&split.base_doc].text() != base_text {
+                    self.replace_document_text(split.base_doc, &String::from(&base_text));
+                }
+            }
+            doc_mut!(self, &split.base_doc).set_diff_split_lines(split.base_view, base_lines);
+            doc_mut!(self, &split.doc).set_diff_split_lines(split.view, doc_lines);
+            // the rows of the panes changed
+            split.aligned_to = None;
+        } else {
+            drop(diff);
+        }
+
+        let (leader, follower) = if self.tree.focus == split.base_view {
+            ((split.base_doc, split.base_view), (split.doc, split.view))
+        } else {
+            ((split.doc, split.view), (split.base_doc, split.base_view))
+        };
+        let offset = self.documents[&leader.0].view_offset(leader.1);
+        if split.aligned_to != Some((leader.1, offset)) {
+            split.aligned_to = Some((leader.1, offset));
+            self.align_diff_split_pane(leader, follower, offset);
+        }
+        self.diff_split = Some(split);
+    }
+
+    /// Scrolls the `follower` pane of the `:diff-split` to the rows shown by the `leader` pane
+    /// scrolled to `offset`.
+    fn align_diff_split_pane(
+        &mut self,
+        leader: (DocumentId, ViewId),
+        follower: (DocumentId, ViewId),
+        offset: ViewPosition,
+    ) {
+        let doc = &self.documents[&leader.0];
+        let (Some(leader_lines), Some(follower_lines)) = (
+            doc.diff_split_lines(leader.1),
+            self.documents[&follower.0].diff_split_lines(follower.1),
+        ) else {
+            return;
+        };
+        let text = doc.text();
+        let line = text.char_to_line(offset.anchor.min(text.len_chars()));
+        let row = leader_lines.line_to_row(line) + offset.vertical_offset;
+        let (line, vertical_offset) = follower_lines.row_to_line(row);
+
+        let doc = doc_mut!(self, &follower.0);
+        let text = doc.text();
+        let last_line = text.len_lines() - 1;
+        let offset = ViewPosition {
+            anchor: text.line_to_char(line.min(last_line)),
+            horizontal_offset: offset.horizontal_offset,
+            vertical_offset: if line > last_line { 0 } else { vertical_offset },
+        };
+        doc.set_view_offset(follower.1, offset);
+    }
+
+    /// Stops aligning and highlighting the `:diff-split` panes, the views are kept open.
+    pub fn end_diff_split(&mut self) {
+        let Some(split) = self.diff_split.take() else {
+            return;
+        };
+        for (doc, view) in [(split.doc, split.view), (split.base_doc, split.base_view)] {
+            if let Some(doc) = self.documents.get_mut(&doc) {
+                doc.remove_diff_split_lines(view);
+            }
+        }
+    }
+
     pub fn new_file_from_stdin(&mut self, action: Action) -> Result<DocumentId, Error> {
         let (stdin, encoding, has_bom) = crate::document::read_to_string(&mut stdin(), None)?;
         let doc = Document::from(
@@ -1846,10 +2376,7 @@ pub fn open(&mut self, path: &Path, action: Action) -> Result<DocumentId, Docume
                 Editor::doc_diagnostics(&self.language_servers, &self.diagnostics, &doc);
             doc.replace_diagnostics(diagnostics, &[], None);
 
-            if let Some(diff_base) = self.diff_providers.get_diff_base(&path) {
-                doc.set_diff_base(diff_base);
-            }
-            doc.set_version_control_head(self.diff_providers.get_current_head_name(&path));
+            doc.update_diff_bases(&self.diff_providers);
 
             let id = self.new_document(doc);
             self.launch_language_servers(id);
@@ -1873,9 +2400,41 @@ pub fn close(&mut self, id: ViewId) {
             doc.remove_view(id);
         }
         self.tree.remove(id);
+        // the staged diff is only useful while the commit message is being edited
+        if let Some(diff) = self.commit_diff.filter(|diff| diff.view == id) {
+            self.commit_diff = None;
+            let views: Vec<_> = self
+                .tree
+                .views()
+                .filter(|(view, _)| view.doc == diff.diff_doc)
+                .map(|(view, _)| view.id)
+                .collect();
+            for view in views {
+                self.close(view);
+            }
+            // closing the buffer with no view left would open a new view instead of exiting
+            if self.tree.views().next().is_some() {
+                let _ = self.close_document(diff.diff_doc, true);
+            }
+        }
         self._refresh();
     }
 
+    /// Whether closing the view `id` leaves no view open, taking the views that are closed along
+    /// with it into account.
+    pub fn is_last_view(&self, id: ViewId) -> bool {
+        let attached = self
+            .commit_diff
+            .filter(|diff| diff.view == id)
+            .map_or(0, |diff| {
+                self.tree
+                    .views()
+                    .filter(|(view, _)| view.doc == diff.diff_doc)
+                    .count()
+            });
+        self.tree.views().count() == 1 + attached
+    }
+
     pub fn close_document(&mut self, doc_id: DocumentId, force: bool) -> Result<(), CloseError> {
         let doc = match self.documents.get(&doc_id) {
             Some(doc) => doc,
@@ -2280,6 +2839,32 @@ pub fn current_stack_frame(&self) -> Option<&dap::StackFrame> {
         self.debug_adapters.current_stack_frame()
     }
 
+    /// Returns the zero-based lines of the frames of the current stack which are located in the
+    /// file at `path`, together with whether the frame is the current stack frame.
+    pub fn stack_frame_lines(&self, path: &Path) -> Vec<(usize, bool)> {
+        let Some(debugger) = self.debug_adapters.get_active_client() else {
+            return Vec::new();
+        };
+        debugger
+            .current_stack()
+            .iter()
+            .enumerate()
+            .filter(|(_, frame)| {
+                frame
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.path.as_deref())
+                    == Some(path)
+            })
+            .map(|(idx, frame)| {
+                (
+                    frame.line.saturating_sub(1),
+                    Some(idx) == debugger.active_frame,
+                )
+            })
+            .collect()
+    }
+
     /// Returns the id of a view that this doc contains a selection for,
     /// making sure it is synced with the current changes
     /// if possible or there are no selections returns current_view
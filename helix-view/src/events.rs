@@ -21,6 +21,9 @@
         doc: Document
     }
     SelectionDidChange<'a> { doc: &'a mut Document, view: ViewId }
+    // the diff bases were loaded from version control again, `HEAD` may have moved. The document
+    // may not be added to the editor yet when it is being opened
+    DiffBasesDidChange<'a> { doc: &'a mut Document }
     DiagnosticsDidChange<'a> { editor: &'a mut Editor, doc: DocumentId }
     // called **after** a document loses focus (but not when its closed)
     DocumentFocusLost<'a> { editor: &'a mut Editor, doc: DocumentId }
@@ -34,6 +37,9 @@
         server_id: LanguageServerId
     }
 
+    // the debuggee stopped or another stack frame of the active debug session was selected
+    DebugFrameDidChange<'a> { editor: &'a mut Editor }
+
     // NOTE: this event is simple for now and is expected to change as the config system evolves.
     // Ideally it would say what changed.
     ConfigDidChange<'a> {
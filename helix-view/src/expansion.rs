@@ -215,11 +215,70 @@ fn expand_inner<'a>(editor: &Editor, content: Cow<'a, str>) -> Result<Cow<'a, st
     }
 }
 
+/// Expands a variable of VS Code configuration files such as `.vscode/launch.json`, for example
+/// `workspaceFolder` for `${workspaceFolder}`. `${input:<id>}` variables are not handled here
+/// since their values are asked from the user.
+pub fn expand_vscode_variable(editor: &Editor, name: &str) -> Result<Cow<'static, str>> {
+    let file = || {
+        doc!(editor)
+            .path()
+            .cloned()
+            .ok_or_else(|| anyhow!("'${{{name}}}' requires a buffer with a path"))
+    };
+    let file_name = |path: &std::path::Path| {
+        Cow::Owned(
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        )
+    };
+
+    match name {
+        "workspaceFolder" => expand_variable(editor, Variable::WorkspaceDirectory),
+        "workspaceFolderBasename" => Ok(file_name(&helix_loader::find_workspace().0)),
+        "cwd" => expand_variable(editor, Variable::CurrentWorkingDirectory),
+        "file" => Ok(Cow::Owned(file()?.to_string_lossy().into_owned())),
+        "relativeFile" => {
+            file()?;
+            expand_variable(editor, Variable::BufferName)
+        }
+        "fileBasename" => Ok(file_name(&file()?)),
+        "fileBasenameNoExtension" => Ok(Cow::Owned(
+            file()?
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        )),
+        "fileExtname" => Ok(Cow::Owned(
+            file()?
+                .extension()
+                .map(|extension| format!(".{}", extension.to_string_lossy()))
+                .unwrap_or_default(),
+        )),
+        "fileDirname" => Ok(Cow::Owned(
+            file()?
+                .parent()
+                .unwrap_or(std::path::Path::new(""))
+                .to_string_lossy()
+                .into_owned(),
+        )),
+        "lineNumber" => expand_variable(editor, Variable::CursorLine),
+        "selectedText" => expand_variable(editor, Variable::Selection),
+        "pathSeparator" => Ok(Cow::Borrowed(std::path::MAIN_SEPARATOR_STR)),
+        name => match name.strip_prefix("env:") {
+            Some(var) => Ok(Cow::Owned(std::env::var(var).unwrap_or_default())),
+            None => bail!("unknown variable '${{{name}}}'"),
+        },
+    }
+}
+
 // Note: the lifetime of the expanded variable (the `Cow`) must not be tied to the lifetime of
 // the borrow of `Editor`. That would prevent commands from mutating the `Editor` until the
 // command consumed or cloned all arguments - this is poor ergonomics. A sensible thing for this
 // function to return then, instead, would normally be a `String`. We can return some statically
-// known strings like the scratch buffer name or line ending strings though, so this function
+// known strings like line ending strings though, so this function
 // returns a `Cow<'static, str>` instead.
 fn expand_variable(editor: &Editor, variable: Variable) -> Result<Cow<'static, str>> {
     let (view, doc) = current_ref!(editor);
@@ -235,15 +294,7 @@ fn expand_variable(editor: &Editor, variable: Variable) -> Result<Cow<'static, s
             let position = helix_core::coords_at_pos(text, cursor);
             Ok(Cow::Owned((position.col + 1).to_string()))
         }
-        Variable::BufferName => {
-            // Note: usually we would use `Document::display_name` but we can statically borrow
-            // the scratch buffer name by partially reimplementing `display_name`.
-            if let Some(path) = doc.relative_path() {
-                Ok(Cow::Owned(path.to_string_lossy().into_owned()))
-            } else {
-                Ok(Cow::Borrowed(crate::document::SCRATCH_BUFFER_NAME))
-            }
-        }
+        Variable::BufferName => Ok(Cow::Owned(doc.display_name().into_owned())),
         Variable::LineEnding => Ok(Cow::Borrowed(doc.line_ending.as_str())),
         Variable::CurrentWorkingDirectory => Ok(std::borrow::Cow::Owned(
             helix_stdx::env::current_working_dir()
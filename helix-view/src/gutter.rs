@@ -3,6 +3,7 @@
 use helix_core::syntax::config::LanguageServerFeature;
 
 use crate::{
+    document::DIFF_BASE_HEAD,
     editor::GutterType,
     graphics::{Style, UnderlineStyle},
     Document, Editor, Theme, View,
@@ -97,8 +98,16 @@ pub fn diff<'doc>(
     let added = theme.get("diff.plus.gutter");
     let deleted = theme.get("diff.minus.gutter");
     let modified = theme.get("diff.delta.gutter");
+    let added_staged = theme.get("diff.plus.gutter.staged");
+    let deleted_staged = theme.get("diff.minus.gutter.staged");
+    let modified_staged = theme.get("diff.delta.gutter.staged");
     if let Some(diff_handle) = doc.diff_handle() {
         let hunks = diff_handle.load();
+        // lines that changed since HEAD but match the index are staged
+        let unstaged = doc
+            .index_diff_handle()
+            .filter(|_| doc.diff_base_revision() == DIFF_BASE_HEAD)
+            .map(|handle| handle.load());
         let mut hunk_i = 0;
         let mut hunk = hunks.nth_hunk(hunk_i);
         Box::new(
@@ -119,13 +128,22 @@ pub fn diff<'doc>(
                     return None;
                 }
 
+                let staged = unstaged
+                    .as_ref()
+                    .is_some_and(|unstaged| unstaged.hunk_at(line as u32, true).is_none());
                 let (icon, style) = if hunk.is_pure_insertion() {
-                    ("▍", added)
+                    if staged {
+                        ("▏", added_staged)
+                    } else {
+                        ("▍", added)
+                    }
                 } else if hunk.is_pure_removal() {
                     if !first_visual_line {
                         return None;
                     }
-                    ("▔", deleted)
+                    ("▔", if staged { deleted_staged } else { deleted })
+                } else if staged {
+                    ("▏", modified_staged)
                 } else {
                     ("▍", modified)
                 };
@@ -237,6 +255,7 @@ pub fn breakpoints<'doc>(
     let error = theme.get("error");
     let info = theme.get("info");
     let breakpoint_style = theme.get("ui.debug.breakpoint");
+    let disabled_style = theme.get("ui.debug.breakpoint.disabled");
 
     let breakpoints = doc.path().and_then(|path| editor.breakpoints.get(path));
 
@@ -254,6 +273,11 @@ pub fn breakpoints<'doc>(
                 .iter()
                 .find(|breakpoint| breakpoint.line == line)?;
 
+            if !breakpoint.enabled {
+                write!(out, "◌").unwrap();
+                return Some(disabled_style);
+            }
+
             let style = if breakpoint.condition.is_some() && breakpoint.log_message.is_some() {
                 error.underline_style(UnderlineStyle::Line)
             } else if breakpoint.condition.is_some() {
@@ -271,37 +295,39 @@ pub fn breakpoints<'doc>(
     )
 }
 
+/// Marks the line the debuggee is paused at and the lines of the other frames of the current
+/// stack, so that the call path is visible.
 fn execution_pause_indicator<'doc>(
     editor: &'doc Editor,
     doc: &'doc Document,
     theme: &Theme,
-    is_focused: bool,
+    _is_focused: bool,
 ) -> GutterFn<'doc> {
     let style = theme.get("ui.debug.active");
-    let current_stack_frame = editor.current_stack_frame();
-    let frame_line = current_stack_frame.map(|frame| frame.line - 1);
-    let frame_source_path = current_stack_frame.map(|frame| {
-        frame
-            .source
-            .as_ref()
-            .and_then(|source| source.path.as_ref())
-    });
-    let should_display_for_current_doc =
-        doc.path().is_some() && frame_source_path.unwrap_or(None) == doc.path();
+    let caller_style = theme.get("ui.debug.caller");
+    let frame_lines = doc
+        .path()
+        .map(|path| editor.stack_frame_lines(path))
+        .unwrap_or_default();
 
     Box::new(
         move |line: usize, _selected: bool, first_visual_line: bool, out: &mut String| {
-            if !first_visual_line
-                || !is_focused
-                || line != frame_line?
-                || !should_display_for_current_doc
-            {
+            if !first_visual_line {
                 return None;
             }
-
-            let sym = "▶";
-            write!(out, "{}", sym).unwrap();
-            Some(style)
+            // A recursive function may have several frames on the same line.
+            let active = frame_lines
+                .iter()
+                .filter(|(frame_line, _)| *frame_line == line)
+                .map(|(_, active)| *active)
+                .reduce(|a, b| a || b)?;
+            if active {
+                write!(out, "▶").unwrap();
+                Some(style)
+            } else {
+                write!(out, "▷").unwrap();
+                Some(caller_style)
+            }
         },
     )
 }
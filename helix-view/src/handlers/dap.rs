@@ -1,14 +1,29 @@
-use crate::editor::{Action, Breakpoint};
-use crate::{align_view, Align, Editor};
+use crate::editor::{
+    Action, Breakpoint, ConsoleCategory, DataBreakpoint, FunctionBreakpoint, InstructionBreakpoint,
+    RunInTerminalConfig, WatchValue,
+};
+use crate::events::DebugFrameDidChange;
+use crate::{align_view, Align, Document, DocumentId, Editor};
 use dap::requests::DisconnectArguments;
-use helix_core::Selection;
+use futures_util::future;
+use helix_core::syntax::{
+    self, config::LanguageServerFeature, LanguageLoader as _, QueryIterEvent, Syntax,
+};
+use helix_core::text_annotations::InlineAnnotation;
+use helix_core::{
+    line_ending::line_end_char_index, Assoc, ChangeSet, Range, Rope, RopeSlice, Selection,
+};
 use helix_dap::{
     self as dap, registry::DebugAdapterId, Client, ConnectionType, Payload, Request, ThreadId,
 };
-use helix_lsp::block_on;
-use log::{error, warn};
+use helix_lsp::{block_on, lsp, util::range_to_lsp_range, OffsetEncoding};
+use log::warn;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
+use std::future::Future;
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
 #[macro_export]
@@ -55,6 +70,664 @@ pub async fn fetch_stack_trace(debugger: &mut Client, thread_id: ThreadId) {
     debugger.active_frame = Some(0);
 }
 
+/// Fetches the children of a structured watch value.
+pub async fn fetch_watch_children(debugger: &Client, value: &mut WatchValue) -> dap::Result<()> {
+    let variables = debugger.variables(value.variables_reference).await?;
+    value.set_children(variables);
+    Ok(())
+}
+
+/// The lines inline values are shown at the end of: the lines of the current function up to the
+/// line the debuggee stopped at.
+pub struct InlineValuesLocation {
+    path: PathBuf,
+    frame_id: usize,
+    lines: RangeInclusive<usize>,
+    stopped_pos: usize,
+}
+
+/// The responses to the requests of [`request_inline_values`].
+pub struct InlineValueScopes {
+    location: InlineValuesLocation,
+    scopes: dap::Result<Vec<dap::Scope>>,
+    /// The inline values of a language server and the offset encoding of their ranges, if a
+    /// language server of the document supports inline values.
+    language_server: Option<(
+        OffsetEncoding,
+        helix_lsp::Result<Option<Vec<lsp::InlineValue>>>,
+    )>,
+}
+
+/// The variables of the stack frame and the inline values of a language server with their
+/// expressions evaluated, see [`evaluate_inline_values`].
+pub struct InlineValues {
+    location: InlineValuesLocation,
+    variables: HashMap<String, String>,
+    language_server: Option<Vec<(usize, LanguageServerInlineValue)>>,
+}
+
+/// An inline value of a language server. Lookups are resolved once the variables are known.
+enum LanguageServerInlineValue {
+    Text(String),
+    Lookup { name: String, case_sensitive: bool },
+}
+
+/// Shows the values of the variables in scope at the end of the lines of the current function,
+/// up to the line the debuggee stopped at.
+///
+/// The values are provided by a language server supporting `textDocument/inlineValue`. Without
+/// one, the identifiers of the syntax tree are matched against the variables of the stack frame.
+///
+/// This requests the scopes of the stack frame and the inline values of the language server.
+/// [`evaluate_inline_values`] then requests the variables of the scopes and evaluates the
+/// expressions of the language server, and [`set_inline_values`] shows the values.
+pub fn request_inline_values(
+    editor: &mut Editor,
+) -> Option<impl Future<Output = InlineValueScopes> + Send + 'static> {
+    clear_inline_values(editor);
+
+    let debugger = editor.debug_adapters.get_active_client()?;
+    let frame = debugger.current_stack_frame()?;
+    let path = frame.source.as_ref()?.path.clone()?;
+    let doc = editor.document_by_path(&path)?;
+    if frame.line == 0 {
+        return None;
+    }
+    let text = doc.text();
+    let stopped_pos = dap_pos_to_pos(text, frame.line, frame.column)?.min(text.len_chars());
+    let stopped_line = text.char_to_line(stopped_pos);
+    let first_line = function_start_line(doc, &editor.syn_loader.load(), stopped_pos)
+        .unwrap_or(stopped_line)
+        .min(stopped_line);
+    let location = InlineValuesLocation {
+        path,
+        frame_id: frame.id,
+        lines: first_line..=stopped_line,
+        stopped_pos,
+    };
+
+    let scopes = debugger.scopes(frame.id);
+    let language_server = request_language_server_inline_values(doc, &location);
+    Some(async move {
+        let (scopes, language_server) = match language_server {
+            Some((offset_encoding, request)) => {
+                let (scopes, inline_values) = future::join(scopes, request).await;
+                (scopes, Some((offset_encoding, inline_values)))
+            }
+            None => (scopes.await, None),
+        };
+        InlineValueScopes {
+            location,
+            scopes,
+            language_server,
+        }
+    })
+}
+
+/// Requests the variables of the scopes and evaluates the expressions of the language server
+/// requested by [`request_inline_values`].
+pub fn evaluate_inline_values(
+    editor: &mut Editor,
+    response: InlineValueScopes,
+) -> Option<impl Future<Output = InlineValues> + Send + 'static> {
+    let InlineValueScopes {
+        location,
+        scopes,
+        language_server,
+    } = response;
+    let debugger = editor.debug_adapters.get_active_client()?;
+    let doc = editor.document_by_path(&location.path)?;
+
+    let scopes = scopes.unwrap_or_else(|err| {
+        log::error!("scopes request failed: {err}");
+        Vec::new()
+    });
+    let variables = future::join_all(
+        scopes
+            .iter()
+            .filter(|scope| !scope.expensive)
+            .map(|scope| debugger.variables(scope.variables_reference)),
+    );
+
+    let mut values = None;
+    let mut expressions = Vec::new();
+    if let Some((offset_encoding, inline_values)) = language_server {
+        let inline_values = inline_values.unwrap_or_else(|err| {
+            log::error!("inline value request failed: {err}");
+            None
+        });
+        let text = doc.text();
+        let range_text = |range: lsp::Range| {
+            helix_lsp::util::lsp_range_to_range(text, range, offset_encoding)
+                .map(|range| text.slice(range.from()..range.to()).to_string())
+        };
+
+        let mut language_server_values = Vec::new();
+        for inline_value in inline_values.unwrap_or_default() {
+            match inline_value {
+                lsp::InlineValue::Text(inline_value) => language_server_values.push((
+                    inline_value.range.start.line as usize,
+                    LanguageServerInlineValue::Text(inline_value.text),
+                )),
+                lsp::InlineValue::VariableLookup(lookup) => {
+                    let Some(name) = lookup.variable_name.or_else(|| range_text(lookup.range))
+                    else {
+                        continue;
+                    };
+                    language_server_values.push((
+                        lookup.range.start.line as usize,
+                        LanguageServerInlineValue::Lookup {
+                            name,
+                            case_sensitive: lookup.case_sensitive_lookup,
+                        },
+                    ));
+                }
+                lsp::InlineValue::EvaluatableExpression(expression) => {
+                    let Some(source) = expression
+                        .expression
+                        .or_else(|| range_text(expression.range))
+                    else {
+                        continue;
+                    };
+                    let request = debugger.eval_in_context(
+                        source.clone(),
+                        Some(location.frame_id),
+                        Some("watch"),
+                    );
+                    let line = expression.range.start.line as usize;
+                    expressions.push(async move {
+                        let response = request.await.ok()?;
+                        let value = format!("{source} = {}", response.result);
+                        Some((line, LanguageServerInlineValue::Text(value)))
+                    });
+                }
+            }
+        }
+        values = Some(language_server_values);
+    }
+
+    Some(async move {
+        let (scope_variables, expressions) =
+            future::join(variables, future::join_all(expressions)).await;
+        let mut variables = HashMap::new();
+        // Variables of inner scopes shadow the ones of outer scopes.
+        for variable in scope_variables.into_iter().flatten().flatten() {
+            variables.entry(variable.name).or_insert(variable.value);
+        }
+        let language_server = values.map(|mut values| {
+            values.extend(expressions.into_iter().flatten());
+            values
+        });
+        InlineValues {
+            location,
+            variables,
+            language_server,
+        }
+    })
+}
+
+/// Shows the inline values evaluated by [`evaluate_inline_values`].
+pub fn set_inline_values(editor: &mut Editor, values: InlineValues) {
+    let InlineValues {
+        location,
+        variables,
+        language_server,
+    } = values;
+    let loader = editor.syn_loader.load();
+    let Some(doc) = editor.document_by_path(&location.path) else {
+        return;
+    };
+    let text = doc.text().slice(..);
+    let lines = location.lines;
+
+    let values = match language_server {
+        Some(values) => values
+            .into_iter()
+            .filter_map(|(line, value)| match value {
+                LanguageServerInlineValue::Text(text) => Some((line, text)),
+                LanguageServerInlineValue::Lookup {
+                    name,
+                    case_sensitive,
+                } => {
+                    let value = if case_sensitive {
+                        variables.get(&name)
+                    } else {
+                        variables
+                            .iter()
+                            .find(|(variable, _)| variable.eq_ignore_ascii_case(&name))
+                            .map(|(_, value)| value)
+                    }?;
+                    Some((line, format!("{name} = {value}")))
+                }
+            })
+            .collect(),
+        None => match doc.syntax() {
+            Some(syntax) => {
+                inline_values_from_syntax(syntax, text, &loader, lines.clone(), &variables)
+            }
+            None => Vec::new(),
+        },
+    };
+
+    let mut entries: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (line, entry) in values {
+        // the document may have been changed since the values were requested
+        if !lines.contains(&line) || line >= text.len_lines() {
+            continue;
+        }
+        let line_entries = entries.entry(line).or_default();
+        if !line_entries.contains(&entry) {
+            line_entries.push(entry);
+        }
+    }
+    let inline_values = entries
+        .into_iter()
+        .map(|(line, entries)| {
+            InlineAnnotation::new(
+                line_end_char_index(&text, line),
+                format!("  {}", entries.join(", ")),
+            )
+        })
+        .collect();
+
+    if let Some(doc) = editor.document_by_path_mut(&location.path) {
+        doc.inline_values = inline_values;
+    }
+}
+
+/// Removes the inline values of all documents, they are only valid while the debuggee is stopped.
+pub fn clear_inline_values(editor: &mut Editor) {
+    for doc in editor.documents_mut() {
+        doc.inline_values.clear();
+    }
+}
+
+/// Number of instructions disassembled before and after the instruction pointer.
+const DISASSEMBLY_CONTEXT: usize = 32;
+/// Number of bytes displayed by the memory view.
+const MEMORY_VIEW_SIZE: usize = 256;
+const MEMORY_VIEW_BYTES_PER_LINE: usize = 16;
+
+/// Instructions disassembled around the instruction pointer and the index of the current one.
+pub type Disassembly = (Vec<dap::DisassembledInstruction>, Option<usize>);
+
+/// Requests the disassembly of the instructions around the instruction pointer of the current
+/// stack frame if the disassembly buffer is open. The response is shown by [`set_disassembly`].
+pub fn disassemble_current_frame(
+    editor: &mut Editor,
+) -> Option<impl Future<Output = anyhow::Result<Disassembly>>> {
+    if !editor
+        .disassembly
+        .doc
+        .is_some_and(|doc_id| editor.documents.contains_key(&doc_id))
+    {
+        editor.disassembly.doc = None;
+        return None;
+    }
+
+    let request = request_disassembly(editor);
+    Some(async move {
+        let (request, instruction_pointer) = request?;
+        let instructions = request.await?;
+        let address = parse_address(&instruction_pointer);
+        let current = instructions.iter().position(|instruction| {
+            instruction.address == instruction_pointer
+                || address.is_some() && parse_address(&instruction.address) == address
+        });
+        Ok((instructions, current))
+    })
+}
+
+fn request_disassembly(
+    editor: &Editor,
+) -> anyhow::Result<(
+    impl Future<Output = helix_dap::Result<Vec<dap::DisassembledInstruction>>>,
+    String,
+)> {
+    let Some(debugger) = editor.debug_adapters.get_active_client() else {
+        anyhow::bail!("No active debug session");
+    };
+    let supported = debugger
+        .caps
+        .as_ref()
+        .and_then(|caps| caps.supports_disassemble_request)
+        .unwrap_or(false);
+    if !supported {
+        anyhow::bail!("The debug adapter does not support disassembling");
+    }
+    let Some(frame) = debugger.current_stack_frame() else {
+        anyhow::bail!("No stack frame is selected");
+    };
+    let Some(instruction_pointer) = frame.instruction_pointer_reference.clone() else {
+        anyhow::bail!("The current stack frame has no instruction pointer");
+    };
+
+    let request = debugger.disassemble(
+        instruction_pointer.clone(),
+        -(DISASSEMBLY_CONTEXT as i64),
+        2 * DISASSEMBLY_CONTEXT + 1,
+    );
+    Ok((request, instruction_pointer))
+}
+
+/// Shows the disassembly requested by [`disassemble_current_frame`] in the disassembly buffer,
+/// if it is still open, and moves the cursor to the current instruction.
+pub fn set_disassembly(editor: &mut Editor, disassembly: anyhow::Result<Disassembly>) {
+    let Some(doc_id) = editor
+        .disassembly
+        .doc
+        .filter(|doc_id| editor.documents.contains_key(doc_id))
+    else {
+        editor.disassembly.doc = None;
+        return;
+    };
+
+    let (text, current) = match disassembly {
+        Ok((instructions, current)) => {
+            let text = format_disassembly(&instructions, current);
+            editor.disassembly.instructions = instructions;
+            (text, current)
+        }
+        Err(err) => {
+            editor.disassembly.instructions.clear();
+            (format!("{err}\n"), None)
+        }
+    };
+    editor.replace_document_text(doc_id, &text);
+    if let Some(line) = current {
+        select_line(editor, doc_id, line);
+    }
+}
+
+/// Parses a hexadecimal memory address like `0x7ffe4a3c`.
+fn parse_address(address: &str) -> Option<u64> {
+    let digits = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address);
+    u64::from_str_radix(digits, 16).ok()
+}
+
+fn format_disassembly(
+    instructions: &[dap::DisassembledInstruction],
+    current: Option<usize>,
+) -> String {
+    let address_width = instructions
+        .iter()
+        .map(|instruction| instruction.address.len())
+        .max()
+        .unwrap_or(0);
+    let bytes_width = instructions
+        .iter()
+        .filter_map(|instruction| instruction.instruction_bytes.as_ref())
+        .map(String::len)
+        .max()
+        .unwrap_or(0);
+
+    let mut text = String::new();
+    for (idx, instruction) in instructions.iter().enumerate() {
+        let marker = if Some(idx) == current { "=>" } else { "  " };
+        let bytes = instruction.instruction_bytes.as_deref().unwrap_or_default();
+        let mut line = format!(
+            "{marker} {:address_width$}  {bytes:bytes_width$}  {}",
+            instruction.address, instruction.instruction
+        );
+
+        let file = instruction
+            .location
+            .as_ref()
+            .and_then(|source| source.name.as_deref().or(source.path.as_ref()?.to_str()));
+        let location = match (file, instruction.line) {
+            (Some(file), Some(line)) => Some(format!("{file}:{line}")),
+            (Some(file), None) => Some(file.to_owned()),
+            (None, _) => None,
+        };
+        let comment: Vec<_> = instruction.symbol.iter().cloned().chain(location).collect();
+        if !comment.is_empty() {
+            write!(line, "  ; {}", comment.join(" ")).unwrap();
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Reads the memory of the memory view if it is open. The response, together with the memory
+/// reference it was read from, is shown by [`set_memory_view`].
+pub fn read_memory_view(
+    editor: &mut Editor,
+) -> Option<impl Future<Output = (String, anyhow::Result<String>)>> {
+    if !editor
+        .memory_view
+        .doc
+        .is_some_and(|doc_id| editor.documents.contains_key(&doc_id))
+    {
+        editor.memory_view.doc = None;
+        return None;
+    }
+    let memory_reference = editor.memory_view.memory_reference.clone()?;
+
+    let request = request_memory(editor, memory_reference.clone());
+    Some(async move {
+        let text = async {
+            let response = request?.await?;
+            let Some(bytes) = response.bytes() else {
+                anyhow::bail!("The debug adapter sent invalid memory contents");
+            };
+            let unreadable = response.unreadable_bytes.unwrap_or(0);
+            Ok(format_memory(&response.address, &bytes, unreadable))
+        };
+        (memory_reference, text.await)
+    })
+}
+
+fn request_memory(
+    editor: &Editor,
+    memory_reference: String,
+) -> anyhow::Result<impl Future<Output = helix_dap::Result<dap::requests::ReadMemoryResponse>>> {
+    let Some(debugger) = editor.debug_adapters.get_active_client() else {
+        anyhow::bail!("No active debug session");
+    };
+    let supported = debugger
+        .caps
+        .as_ref()
+        .and_then(|caps| caps.supports_read_memory_request)
+        .unwrap_or(false);
+    if !supported {
+        anyhow::bail!("The debug adapter does not support reading memory");
+    }
+
+    Ok(debugger.read_memory(memory_reference, 0, MEMORY_VIEW_SIZE))
+}
+
+/// Shows the memory read by [`read_memory_view`] from `memory_reference` in the memory view, if
+/// it still shows that memory.
+pub fn set_memory_view(
+    editor: &mut Editor,
+    memory_reference: String,
+    text: anyhow::Result<String>,
+) {
+    let Some(doc_id) = editor
+        .memory_view
+        .doc
+        .filter(|doc_id| editor.documents.contains_key(doc_id))
+    else {
+        editor.memory_view.doc = None;
+        return;
+    };
+    if editor.memory_view.memory_reference.as_ref() != Some(&memory_reference) {
+        return;
+    }
+
+    let text = text.unwrap_or_else(|err| format!("{err}\n"));
+    editor.replace_document_text(doc_id, &text);
+}
+
+/// Formats memory as a hex dump. Unreadable bytes following the read ones are shown as `??`.
+fn format_memory(address: &str, bytes: &[u8], unreadable: usize) -> String {
+    let bytes: Vec<_> = bytes
+        .iter()
+        .copied()
+        .map(Some)
+        .chain(std::iter::repeat_n(None, unreadable))
+        .collect();
+    let start = parse_address(address);
+
+    let mut text = String::new();
+    for (line, chunk) in bytes.chunks(MEMORY_VIEW_BYTES_PER_LINE).enumerate() {
+        let offset = line * MEMORY_VIEW_BYTES_PER_LINE;
+        match start {
+            Some(start) => write!(text, "0x{:016x} ", start + offset as u64).unwrap(),
+            None => write!(text, "{address}+{offset:#06x} ").unwrap(),
+        }
+        for (idx, byte) in chunk.iter().enumerate() {
+            // Separate the two halves of the line.
+            if idx == MEMORY_VIEW_BYTES_PER_LINE / 2 {
+                text.push(' ');
+            }
+            match byte {
+                Some(byte) => write!(text, " {byte:02x}").unwrap(),
+                None => text.push_str(" ??"),
+            }
+        }
+        let padding = MEMORY_VIEW_BYTES_PER_LINE - chunk.len();
+        if chunk.len() <= MEMORY_VIEW_BYTES_PER_LINE / 2 {
+            text.push(' ');
+        }
+        text.push_str(&" ".repeat(padding * 3));
+        text.push_str("  |");
+        text.extend(chunk.iter().map(|byte| match byte {
+            Some(byte) if byte.is_ascii_graphic() || *byte == b' ' => *byte as char,
+            _ => '.',
+        }));
+        text.push_str("|\n");
+    }
+    text
+}
+
+/// Moves the cursor of every view of `doc_id` to the start of `line`, centering it.
+fn select_line(editor: &mut Editor, doc_id: DocumentId, line: usize) {
+    let Some(doc) = editor.documents.get_mut(&doc_id) else {
+        return;
+    };
+    let pos = doc
+        .text()
+        .line_to_char(line.min(doc.text().len_lines() - 1));
+    for (view, _) in editor.tree.views_mut() {
+        if view.doc == doc_id {
+            doc.set_selection(view.id, Selection::point(pos));
+            align_view(doc, view, Align::Center);
+        }
+    }
+}
+
+/// Returns the first line of the innermost function surrounding `pos`.
+fn function_start_line(doc: &Document, loader: &syntax::Loader, pos: usize) -> Option<usize> {
+    let syntax = doc.syntax()?;
+    let text = doc.text().slice(..);
+    let byte_pos = text.char_to_byte(pos);
+    let root = syntax.tree().root_node();
+    let node = loader
+        .textobject_query(syntax.root_language())?
+        .capture_nodes("function.around", &root, text)?
+        .filter(|node| node.byte_range().contains(&byte_pos))
+        .min_by_key(|node| node.byte_range().len())?;
+    Some(text.byte_to_line(node.start_byte()))
+}
+
+/// Requests the inline values of the lines of `location` from a language server. Returns `None`
+/// if no language server of the document supports inline values.
+fn request_language_server_inline_values(
+    doc: &Document,
+    location: &InlineValuesLocation,
+) -> Option<(
+    OffsetEncoding,
+    impl Future<Output = helix_lsp::Result<Option<Vec<lsp::InlineValue>>>>,
+)> {
+    let language_server = doc
+        .language_servers_with_feature(LanguageServerFeature::InlineValues)
+        .next()?;
+    let offset_encoding = language_server.offset_encoding();
+    let text = doc.text();
+
+    let range = Range::new(
+        text.line_to_char(*location.lines.start()),
+        line_end_char_index(&text.slice(..), *location.lines.end()),
+    );
+    let stopped_range = Range::point(location.stopped_pos);
+    let context = lsp::InlineValueContext {
+        frame_id: location.frame_id as i32,
+        stopped_location: range_to_lsp_range(text, stopped_range, offset_encoding),
+    };
+    let request = language_server.text_document_inline_value(
+        doc.identifier(),
+        range_to_lsp_range(text, range, offset_encoding),
+        context,
+    )?;
+    Some((offset_encoding, request))
+}
+
+/// Matches the variables highlighted within `lines` against the variables of the stack frame.
+///
+/// A node is highlighted as a variable if the first pattern of the highlight query capturing it
+/// captures it as `variable` or one of its sub-scopes. Members are not variables of the frame.
+fn inline_values_from_syntax(
+    syntax: &Syntax,
+    text: RopeSlice,
+    loader: &syntax::Loader,
+    lines: RangeInclusive<usize>,
+    variables: &HashMap<String, String>,
+) -> Vec<(usize, String)> {
+    let start = text.line_to_byte(*lines.start()) as u32;
+    let end = text.line_to_byte(*lines.end() + 1) as u32;
+    let mut query_iter = syntax.query_iter::<_, (), _>(
+        text,
+        |lang| {
+            loader
+                .get_config(lang)
+                .map(|config| &config.highlight_query.query)
+        },
+        start..end,
+    );
+
+    // The byte range of every captured node with the first pattern capturing it and whether it
+    // captures the node as a variable.
+    let mut highlights = BTreeMap::new();
+    while let Some(event) = query_iter.next() {
+        let QueryIterEvent::Match(mat) = event else {
+            continue;
+        };
+        let Some(config) = loader.get_config(query_iter.current_language()) else {
+            continue;
+        };
+        let name = config.highlight_query.query.capture_name(mat.capture);
+        // the locals query is part of the highlight query but doesn't highlight
+        if name.starts_with("local.") {
+            continue;
+        }
+        let is_variable = (name == "variable" || name.starts_with("variable."))
+            && !name.starts_with("variable.other.member");
+        let range = mat.node.byte_range();
+        let highlight = highlights
+            .entry((range.start, range.end))
+            .or_insert((mat.pattern, is_variable));
+        if mat.pattern < highlight.0 {
+            *highlight = (mat.pattern, is_variable);
+        }
+    }
+
+    highlights
+        .into_iter()
+        .filter(|(_, (_, is_variable))| *is_variable)
+        .filter_map(|((start, end), _)| {
+            let line = text.byte_to_line(start as usize);
+            let name = text.byte_slice(start as usize..end as usize).to_string();
+            let value = variables.get(&name)?;
+            Some((line, format!("{name} = {value}")))
+        })
+        .collect()
+}
+
 pub fn jump_to_stack_frame(editor: &mut Editor, frame: &helix_dap::StackFrame) {
     let path = if let Some(helix_dap::Source {
         path: Some(ref path),
@@ -85,46 +758,73 @@ pub fn jump_to_stack_frame(editor: &mut Editor, frame: &helix_dap::StackFrame) {
     align_view(doc, view, Align::Center);
 }
 
+/// The conditions of breakpoints the debug adapter supports. Conditions it doesn't support are
+/// left out, the breakpoints are still set.
+struct SupportedConditions {
+    conditions: bool,
+    hit_conditions: bool,
+    log_points: bool,
+}
+
+impl SupportedConditions {
+    fn new(debugger: &dap::Client) -> Self {
+        let caps = debugger.capabilities();
+        Self {
+            conditions: caps.supports_conditional_breakpoints.unwrap_or_default(),
+            hit_conditions: caps
+                .supports_hit_conditional_breakpoints
+                .unwrap_or_default(),
+            log_points: caps.supports_log_points.unwrap_or_default(),
+        }
+    }
+
+    fn condition(&self, condition: &Option<String>) -> Option<String> {
+        condition.clone().filter(|_| self.conditions)
+    }
+
+    fn hit_condition(&self, hit_condition: &Option<String>) -> Option<String> {
+        hit_condition.clone().filter(|_| self.hit_conditions)
+    }
+
+    fn log_message(&self, log_message: &Option<String>) -> Option<String> {
+        log_message.clone().filter(|_| self.log_points)
+    }
+}
+
 pub fn breakpoints_changed(
     debugger: &mut dap::Client,
     path: PathBuf,
     breakpoints: &mut [Breakpoint],
 ) -> Result<(), anyhow::Error> {
-    // TODO: handle capabilities correctly again, by filtering breakpoints when emitting
-    // if breakpoint.condition.is_some()
-    //     && !debugger
-    //         .caps
-    //         .as_ref()
-    //         .unwrap()
-    //         .supports_conditional_breakpoints
-    //         .unwrap_or_default()
-    // {
-    //     bail!(
-    //         "Can't edit breakpoint: debugger does not support conditional breakpoints"
-    //     )
-    // }
-    // if breakpoint.log_message.is_some()
-    //     && !debugger
-    //         .caps
-    //         .as_ref()
-    //         .unwrap()
-    //         .supports_log_points
-    //         .unwrap_or_default()
-    // {
-    //     bail!("Can't edit breakpoint: debugger does not support logpoints")
-    // }
+    let conditions = SupportedConditions::new(debugger);
+
     let source_breakpoints = breakpoints
         .iter()
+        .filter(|breakpoint| breakpoint.enabled)
         .map(|breakpoint| helix_dap::SourceBreakpoint {
             line: breakpoint.line + 1, // convert from 0-indexing to 1-indexing (TODO: could set debugger to 0-indexing on init)
+            condition: conditions.condition(&breakpoint.condition),
+            hit_condition: conditions.hit_condition(&breakpoint.hit_condition),
+            log_message: conditions.log_message(&breakpoint.log_message),
             ..Default::default()
         })
         .collect::<Vec<_>>();
 
+    for breakpoint in breakpoints
+        .iter_mut()
+        .filter(|breakpoint| !breakpoint.enabled)
+    {
+        breakpoint.id = None;
+        breakpoint.verified = false;
+    }
+
     let request = debugger.set_breakpoints(path, source_breakpoints);
     match block_on(request) {
         Ok(Some(dap_breakpoints)) => {
-            for (breakpoint, dap_breakpoint) in breakpoints.iter_mut().zip(dap_breakpoints) {
+            let enabled = breakpoints
+                .iter_mut()
+                .filter(|breakpoint| breakpoint.enabled);
+            for (breakpoint, dap_breakpoint) in enabled.zip(dap_breakpoints) {
                 breakpoint.id = dap_breakpoint.id;
                 breakpoint.verified = dap_breakpoint.verified;
                 breakpoint.message = dap_breakpoint.message;
@@ -142,7 +842,322 @@ pub fn breakpoints_changed(
     Ok(())
 }
 
+pub fn function_breakpoints_changed(
+    debugger: &mut dap::Client,
+    breakpoints: &mut [FunctionBreakpoint],
+) -> Result<(), anyhow::Error> {
+    if !debugger
+        .capabilities()
+        .supports_function_breakpoints
+        .unwrap_or_default()
+    {
+        anyhow::bail!("Debugger does not support function breakpoints");
+    }
+    let conditions = SupportedConditions::new(debugger);
+
+    let function_breakpoints = breakpoints
+        .iter()
+        .filter(|breakpoint| breakpoint.enabled)
+        .map(|breakpoint| dap::FunctionBreakpoint {
+            name: breakpoint.name.clone(),
+            condition: conditions.condition(&breakpoint.condition),
+            hit_condition: conditions.hit_condition(&breakpoint.hit_condition),
+        })
+        .collect();
+
+    let dap_breakpoints = block_on(debugger.set_function_breakpoints(function_breakpoints))?;
+    let statuses = breakpoints.iter_mut().map(|breakpoint| {
+        let enabled = breakpoint.enabled;
+        (
+            enabled,
+            &mut breakpoint.id,
+            &mut breakpoint.verified,
+            &mut breakpoint.message,
+        )
+    });
+    update_breakpoint_statuses(statuses, dap_breakpoints);
+    Ok(())
+}
+
+pub fn data_breakpoints_changed(
+    debugger: &mut dap::Client,
+    breakpoints: &mut [DataBreakpoint],
+) -> Result<(), anyhow::Error> {
+    if !debugger
+        .capabilities()
+        .supports_data_breakpoints
+        .unwrap_or_default()
+    {
+        anyhow::bail!("Debugger does not support data breakpoints");
+    }
+    let conditions = SupportedConditions::new(debugger);
+
+    let data_breakpoints = breakpoints
+        .iter()
+        .filter(|breakpoint| breakpoint.enabled)
+        .map(|breakpoint| dap::DataBreakpoint {
+            data_id: breakpoint.data_id.clone(),
+            access_type: breakpoint.access_type,
+            condition: conditions.condition(&breakpoint.condition),
+            hit_condition: conditions.hit_condition(&breakpoint.hit_condition),
+        })
+        .collect();
+
+    let dap_breakpoints = block_on(debugger.set_data_breakpoints(data_breakpoints))?;
+    let statuses = breakpoints.iter_mut().map(|breakpoint| {
+        let enabled = breakpoint.enabled;
+        (
+            enabled,
+            &mut breakpoint.id,
+            &mut breakpoint.verified,
+            &mut breakpoint.message,
+        )
+    });
+    update_breakpoint_statuses(statuses, dap_breakpoints);
+    Ok(())
+}
+
+pub fn instruction_breakpoints_changed(
+    debugger: &mut dap::Client,
+    breakpoints: &mut [InstructionBreakpoint],
+) -> Result<(), anyhow::Error> {
+    if !debugger
+        .capabilities()
+        .supports_instruction_breakpoints
+        .unwrap_or_default()
+    {
+        anyhow::bail!("Debugger does not support instruction breakpoints");
+    }
+    let conditions = SupportedConditions::new(debugger);
+
+    let instruction_breakpoints = breakpoints
+        .iter()
+        .filter(|breakpoint| breakpoint.enabled)
+        .map(|breakpoint| dap::InstructionBreakpoint {
+            instruction_reference: breakpoint.instruction_reference.clone(),
+            offset: breakpoint.offset,
+            condition: conditions.condition(&breakpoint.condition),
+            hit_condition: conditions.hit_condition(&breakpoint.hit_condition),
+        })
+        .collect();
+
+    let dap_breakpoints = block_on(debugger.set_instruction_breakpoints(instruction_breakpoints))?;
+    let statuses = breakpoints.iter_mut().map(|breakpoint| {
+        let enabled = breakpoint.enabled;
+        (
+            enabled,
+            &mut breakpoint.id,
+            &mut breakpoint.verified,
+            &mut breakpoint.message,
+        )
+    });
+    update_breakpoint_statuses(statuses, dap_breakpoints);
+    Ok(())
+}
+
+/// Sets a data breakpoint on the variable `name` of the container `variables_reference`, or
+/// removes it if there already is one. Returns whether the breakpoint was added.
+pub fn toggle_data_breakpoint(
+    editor: &mut Editor,
+    variables_reference: usize,
+    name: String,
+    frame_id: Option<usize>,
+) -> Result<bool, anyhow::Error> {
+    let Some(debugger) = editor.debug_adapters.get_active_client_mut() else {
+        anyhow::bail!("No active debug session");
+    };
+    if !debugger
+        .capabilities()
+        .supports_data_breakpoints
+        .unwrap_or_default()
+    {
+        anyhow::bail!("Debugger does not support data breakpoints");
+    }
+
+    let info = block_on(debugger.data_breakpoint_info(Some(variables_reference), name, frame_id))?;
+    let Some(data_id) = info.data_id else {
+        anyhow::bail!("{}", info.description);
+    };
+
+    let added = match editor
+        .data_breakpoints
+        .iter()
+        .position(|breakpoint| breakpoint.data_id == data_id)
+    {
+        Some(pos) => {
+            editor.data_breakpoints.remove(pos);
+            false
+        }
+        None => {
+            editor.data_breakpoints.push(DataBreakpoint {
+                id: None,
+                verified: false,
+                message: None,
+                enabled: true,
+                data_id,
+                description: info.description,
+                access_type: None,
+                can_persist: info.can_persist.unwrap_or_default(),
+                condition: None,
+                hit_condition: None,
+            });
+            true
+        }
+    };
+    data_breakpoints_changed(debugger, &mut editor.data_breakpoints)?;
+    Ok(added)
+}
+
+/// Sends all breakpoints that are not tied to a source line, used when a session starts.
+pub fn send_non_source_breakpoints(editor: &mut Editor, id: DebugAdapterId) {
+    let Some(debugger) = editor.debug_adapters.get_client_mut(id) else {
+        return;
+    };
+    let caps = debugger.capabilities();
+    let supports_function_breakpoints = caps.supports_function_breakpoints.unwrap_or_default();
+    let supports_instruction_breakpoints =
+        caps.supports_instruction_breakpoints.unwrap_or_default();
+
+    // Data identifiers are only valid within the debug session that created them.
+    editor
+        .data_breakpoints
+        .retain(|breakpoint| breakpoint.can_persist);
+
+    if supports_function_breakpoints && !editor.function_breakpoints.is_empty() {
+        if let Err(err) = function_breakpoints_changed(debugger, &mut editor.function_breakpoints) {
+            log::error!("Failed to set function breakpoints: {err}");
+        }
+    }
+    if !editor.data_breakpoints.is_empty() {
+        if let Err(err) = data_breakpoints_changed(debugger, &mut editor.data_breakpoints) {
+            log::error!("Failed to set data breakpoints: {err}");
+        }
+    }
+    if supports_instruction_breakpoints && !editor.instruction_breakpoints.is_empty() {
+        if let Err(err) =
+            instruction_breakpoints_changed(debugger, &mut editor.instruction_breakpoints)
+        {
+            log::error!("Failed to set instruction breakpoints: {err}");
+        }
+    }
+}
+
+/// Updates the state of the enabled breakpoints from the response of the debug adapter, which
+/// contains one breakpoint for each breakpoint that was sent, in the same order.
+fn update_breakpoint_statuses<'a>(
+    statuses: impl Iterator<
+        Item = (
+            bool,
+            &'a mut Option<usize>,
+            &'a mut bool,
+            &'a mut Option<String>,
+        ),
+    >,
+    dap_breakpoints: Vec<dap::Breakpoint>,
+) {
+    let mut dap_breakpoints = dap_breakpoints.into_iter();
+    for (enabled, id, verified, message) in statuses {
+        let dap_breakpoint = if enabled {
+            dap_breakpoints.next()
+        } else {
+            None
+        };
+        match dap_breakpoint {
+            Some(dap_breakpoint) => {
+                *id = dap_breakpoint.id;
+                *verified = dap_breakpoint.verified;
+                *message = dap_breakpoint.message;
+            }
+            None => {
+                *id = None;
+                *verified = false;
+            }
+        }
+    }
+}
+
 impl Editor {
+    /// Records debug console output, appending it to the console buffer if it is open.
+    pub fn debug_console_push(&mut self, category: ConsoleCategory, text: String) {
+        let Some(text) = self.debug_console.push(category, text) else {
+            return;
+        };
+        if let Some(doc_id) = self.debug_console.doc {
+            if !self.append_to_document(doc_id, &text) {
+                self.debug_console.doc = None;
+            }
+        }
+    }
+
+    /// Opens the debug console buffer, displaying only output of the `filter` category if given.
+    pub fn open_debug_console(&mut self, action: Action, filter: Option<ConsoleCategory>) {
+        if let Some(doc_id) = self
+            .debug_console
+            .doc
+            .filter(|doc_id| self.documents.contains_key(doc_id))
+        {
+            if self.debug_console.filter == filter {
+                let view_id = self
+                    .tree
+                    .views()
+                    .find(|(view, _)| view.doc == doc_id)
+                    .map(|(view, _)| view.id);
+                match view_id {
+                    Some(view_id) => self.focus(view_id),
+                    None => self.switch(doc_id, action),
+                }
+                return;
+            }
+            // The console is recreated with the contents for the new filter.
+            let _ = self.close_document(doc_id, true);
+        }
+
+        self.debug_console.filter = filter;
+        let contents = self.debug_console.contents();
+        let doc_id = self.new_file_from_text(action, &contents);
+        let (view, doc) = current!(self);
+        doc.readonly = true;
+        // Follow the output by placing the cursor at the end.
+        let end = doc.text().len_chars();
+        doc.set_selection(view.id, Selection::point(end));
+        self.debug_console.doc = Some(doc_id);
+    }
+
+    /// Opens the buffer disassembling the current stack frame. Its contents are filled by
+    /// [`set_disassembly`].
+    pub fn open_disassembly(&mut self, action: Action) {
+        let doc_id = self.open_debug_buffer(self.disassembly.doc, action);
+        self.disassembly.doc = Some(doc_id);
+    }
+
+    /// Opens the memory view for `memory_reference`. Its contents are filled by
+    /// [`set_memory_view`].
+    pub fn open_memory_view(&mut self, action: Action, memory_reference: String) {
+        let doc_id = self.open_debug_buffer(self.memory_view.doc, action);
+        self.memory_view.doc = Some(doc_id);
+        self.memory_view.memory_reference = Some(memory_reference);
+    }
+
+    /// Focuses the read-only buffer `doc_id` if it is still open, otherwise opens a new one.
+    fn open_debug_buffer(&mut self, doc_id: Option<DocumentId>, action: Action) -> DocumentId {
+        if let Some(doc_id) = doc_id.filter(|doc_id| self.documents.contains_key(doc_id)) {
+            let view_id = self
+                .tree
+                .views()
+                .find(|(view, _)| view.doc == doc_id)
+                .map(|(view, _)| view.id);
+            match view_id {
+                Some(view_id) => self.focus(view_id),
+                None => self.switch(doc_id, action),
+            }
+            return doc_id;
+        }
+
+        let doc_id = self.new_file_from_text(action, "");
+        doc_mut!(self, &doc_id).readonly = true;
+        doc_id
+    }
+
     pub async fn handle_debugger_message(
         &mut self,
         id: DebugAdapterId,
@@ -184,6 +1199,7 @@ pub async fn handle_debugger_message(
                                 debugger.request::<dap::requests::Threads>(()).await
                             {
                                 for thread in response.threads {
+                                    debugger.thread_states.insert(thread.id, reason.clone());
                                     fetch_stack_trace(debugger, thread.id).await;
                                 }
                                 select_thread_id(self, thread_id.unwrap_or_default(), false).await;
@@ -214,6 +1230,7 @@ pub async fn handle_debugger_message(
 
                         self.set_status(status);
                         self.debug_adapters.set_active_client(id);
+                        helix_event::dispatch(DebugFrameDidChange { editor: self });
                     }
                     Event::Continued(events::ContinuedBody { thread_id, .. }) => {
                         let debugger = match self.debug_adapters.get_client_mut(id) {
@@ -226,6 +1243,7 @@ pub async fn handle_debugger_message(
                             .insert(thread_id, "running".to_owned());
                         if debugger.thread_id == Some(thread_id) {
                             debugger.resume_application();
+                            clear_inline_values(self);
                         }
                     }
                     Event::Thread(thread) => {
@@ -273,6 +1291,28 @@ pub async fn handle_debugger_message(
                                             breakpoint.column.or(breakpoints[i].column);
                                     }
                                 }
+                                let statuses = self
+                                    .function_breakpoints
+                                    .iter_mut()
+                                    .map(|b| (b.id, &mut b.verified, &mut b.message))
+                                    .chain(
+                                        self.data_breakpoints
+                                            .iter_mut()
+                                            .map(|b| (b.id, &mut b.verified, &mut b.message)),
+                                    )
+                                    .chain(
+                                        self.instruction_breakpoints
+                                            .iter_mut()
+                                            .map(|b| (b.id, &mut b.verified, &mut b.message)),
+                                    );
+                                for (id, verified, message) in statuses {
+                                    if id.is_some() && id == breakpoint.id {
+                                        *verified = breakpoint.verified;
+                                        if breakpoint.message.is_some() {
+                                            message.clone_from(&breakpoint.message);
+                                        }
+                                    }
+                                }
                             }
                             "removed" => {
                                 for breakpoints in self.breakpoints.values_mut() {
@@ -282,6 +1322,12 @@ pub async fn handle_debugger_message(
                                         breakpoints.remove(i);
                                     }
                                 }
+                                if breakpoint.id.is_some() {
+                                    self.function_breakpoints.retain(|b| b.id != breakpoint.id);
+                                    self.data_breakpoints.retain(|b| b.id != breakpoint.id);
+                                    self.instruction_breakpoints
+                                        .retain(|b| b.id != breakpoint.id);
+                                }
                             }
                             reason => {
                                 warn!("Unknown breakpoint event: {}", reason);
@@ -291,21 +1337,30 @@ pub async fn handle_debugger_message(
                     Event::Output(events::OutputBody {
                         category, output, ..
                     }) => {
-                        let prefix = match category {
-                            Some(category) => {
-                                if &category == "telemetry" {
-                                    return false;
-                                }
-                                format!("Debug ({}):", category)
-                            }
-                            None => "Debug:".to_owned(),
+                        let Some(console_category) =
+                            ConsoleCategory::from_output_category(category.as_deref())
+                        else {
+                            return false;
                         };
-
                         log::info!("{}", output);
-                        self.set_status(format!("{} {}", prefix, output));
+
+                        // Without an open console the output is only visible in the statusline.
+                        let console_open = self
+                            .debug_console
+                            .doc
+                            .is_some_and(|doc_id| self.documents.contains_key(&doc_id));
+                        if !console_open {
+                            let prefix = match &category {
+                                Some(category) => format!("Debug ({}):", category),
+                                None => "Debug:".to_owned(),
+                            };
+                            self.set_status(format!("{} {}", prefix, output.trim_end()));
+                        }
+                        self.debug_console_push(console_category, output);
                     }
                     Event::Initialized(_) => {
                         self.set_status("Debugger initialized...");
+                        send_non_source_breakpoints(self, id);
                         let debugger = match self.debug_adapters.get_client_mut(id) {
                             Some(debugger) => debugger,
                             None => return false,
@@ -323,6 +1378,7 @@ pub async fn handle_debugger_message(
                         }; // TODO: do we need to handle error?
                     }
                     Event::Terminated(terminated) => {
+                        clear_inline_values(self);
                         let debugger = match self.debug_adapters.get_client_mut(id) {
                             Some(debugger) => debugger,
                             None => return false,
@@ -355,16 +1411,17 @@ pub async fn handle_debugger_message(
                         match restart_arg {
                             Some(Value::Bool(false)) | None => {
                                 self.debug_adapters.remove_client(id);
-                                self.debug_adapters.unset_active_client();
                                 self.set_status(
                                     "Terminated debugging session and disconnected debugger.",
                                 );
 
-                                // Go through all breakpoints and set verfified to false
+                                // Once no session is left, go through all breakpoints and set verfified to false
                                 // this should update the UI to show the breakpoints are no longer connected
-                                for breakpoints in self.breakpoints.values_mut() {
-                                    for breakpoint in breakpoints.iter_mut() {
-                                        breakpoint.verified = false;
+                                if self.debug_adapters.iter().next().is_none() {
+                                    for breakpoints in self.breakpoints.values_mut() {
+                                        for breakpoint in breakpoints.iter_mut() {
+                                            breakpoint.verified = false;
+                                        }
                                     }
                                 }
                             }
@@ -413,90 +1470,77 @@ pub async fn handle_debugger_message(
                 let reply = match Request::parse(&request.command, request.arguments) {
                     Ok(Request::RunInTerminal(arguments)) => {
                         let config = self.config();
-                        let Some(config) = config.terminal.as_ref() else {
-                            self.set_error("No external terminal defined");
-                            return true;
+                        let terminal = match config.debugger.run_in_terminal {
+                            RunInTerminalConfig::External => config.terminal.as_ref(),
+                            RunInTerminalConfig::Console => None,
                         };
 
-                        let process = match std::process::Command::new(&config.command)
-                            .args(&config.args)
-                            .arg(arguments.args.join(" "))
-                            .spawn()
-                        {
-                            Ok(process) => process,
-                            Err(err) => {
-                                self.set_error(format!(
-                                    "Error starting external terminal: {}",
-                                    err
-                                ));
-                                return true;
+                        let process_id = match terminal {
+                            Some(terminal) => {
+                                match std::process::Command::new(&terminal.command)
+                                    .args(&terminal.args)
+                                    .arg(arguments.args.join(" "))
+                                    .spawn()
+                                {
+                                    Ok(process) => Ok(Some(process.id())),
+                                    Err(err) => {
+                                        self.set_error(format!(
+                                            "Error starting external terminal: {}",
+                                            err
+                                        ));
+                                        return true;
+                                    }
+                                }
                             }
+                            None => self.debug_adapters.run_debuggee(id, &arguments),
                         };
 
-                        Ok(json!(dap::requests::RunInTerminalResponse {
-                            process_id: Some(process.id()),
-                            shell_process_id: None,
-                        }))
+                        match process_id {
+                            Ok(process_id) => Ok(json!(dap::requests::RunInTerminalResponse {
+                                process_id,
+                                shell_process_id: None,
+                            })),
+                            Err(err) => {
+                                self.set_error(format!("Error starting debuggee: {}", err));
+                                Err(err)
+                            }
+                        }
                     }
                     Ok(Request::StartDebugging(arguments)) => {
-                        let debugger = match self.debug_adapters.get_client_mut(id) {
-                            Some(debugger) => debugger,
-                            None => {
-                                self.set_error("No active debugger found.");
-                                return true;
-                            }
-                        };
-                        // Currently we only support starting a child debugger if the parent is using the TCP transport
-                        let socket = match debugger.socket {
-                            Some(socket) => socket,
-                            None => {
-                                self.set_error("Child debugger can only be started if the parent debugger is using TCP transport.");
-                                return true;
-                            }
-                        };
-
-                        let config = match debugger.config.clone() {
-                            Some(config) => config,
-                            None => {
-                                error!("No configuration found for the debugger.");
-                                return true;
-                            }
-                        };
+                        match self.debug_adapters.start_child_client(id) {
+                            Ok(client_id) => {
+                                let client = match self.debug_adapters.get_client_mut(client_id) {
+                                    Some(child) => child,
+                                    None => {
+                                        self.set_error("Failed to get child debugger.");
+                                        return true;
+                                    }
+                                };
 
-                        let result = self.debug_adapters.start_client(Some(socket), &config);
+                                let relaunch_resp =
+                                    if let ConnectionType::Launch = arguments.request {
+                                        client.launch(arguments.configuration).await
+                                    } else {
+                                        client.attach(arguments.configuration).await
+                                    };
+                                if let Err(err) = relaunch_resp {
+                                    self.set_error(format!(
+                                        "Failed to start debugging session: {:?}",
+                                        err
+                                    ));
+                                    return true;
+                                }
+                                self.set_status("Started child debugging session");
 
-                        let client_id = match result {
-                            Ok(child) => child,
-                            Err(err) => {
-                                self.set_error(format!(
-                                    "Failed to create child debugger: {:?}",
-                                    err
-                                ));
-                                return true;
+                                Ok(json!({
+                                    "success": true,
+                                }))
                             }
-                        };
-
-                        let client = match self.debug_adapters.get_client_mut(client_id) {
-                            Some(child) => child,
-                            None => {
-                                self.set_error("Failed to get child debugger.");
-                                return true;
+                            Err(err) => {
+                                self.set_error(format!("Failed to create child debugger: {}", err));
+                                Err(err)
                             }
-                        };
-
-                        let relaunch_resp = if let ConnectionType::Launch = arguments.request {
-                            client.launch(arguments.configuration).await
-                        } else {
-                            client.attach(arguments.configuration).await
-                        };
-                        if let Err(err) = relaunch_resp {
-                            self.set_error(format!("Failed to start debugging session: {:?}", err));
-                            return true;
                         }
-
-                        Ok(json!({
-                            "success": true,
-                        }))
                     }
                     Err(err) => Err(err),
                 };
@@ -512,3 +1556,371 @@ pub async fn handle_debugger_message(
         true
     }
 }
+
+/// Breakpoints as stored in the workspace breakpoints file. Data and instruction breakpoints
+/// refer to memory of a running debuggee and are not stored.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct PersistedBreakpoints {
+    source: BTreeMap<PathBuf, Vec<PersistedSourceBreakpoint>>,
+    function: Vec<PersistedFunctionBreakpoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PersistedSourceBreakpoint {
+    line: usize,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hit_condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    log_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PersistedFunctionBreakpoint {
+    name: String,
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hit_condition: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Moves `breakpoints` along with the lines they were set on when `changes` turn `old_text` into
+/// `text`, returning whether any breakpoint moved.
+///
+/// Breakpoints whose lines are deleted end up on the same line, they are merged into the first
+/// of them: its condition, hit condition and log message are taken from the others if it has none.
+pub fn move_breakpoints(
+    breakpoints: &mut Vec<Breakpoint>,
+    old_text: &Rope,
+    changes: &ChangeSet,
+    text: &Rope,
+) -> bool {
+    let mut moved = false;
+    for breakpoint in breakpoints.iter_mut() {
+        if breakpoint.line >= old_text.len_lines() {
+            continue;
+        }
+        let pos = changes.map_pos(old_text.line_to_char(breakpoint.line), Assoc::After);
+        let line = text.char_to_line(pos.min(text.len_chars()));
+        moved |= line != breakpoint.line;
+        breakpoint.line = line;
+    }
+
+    let mut merged: Vec<Breakpoint> = Vec::with_capacity(breakpoints.len());
+    for breakpoint in breakpoints.drain(..) {
+        let Some(first) = merged
+            .iter_mut()
+            .find(|first| first.line == breakpoint.line)
+        else {
+            merged.push(breakpoint);
+            continue;
+        };
+        first.enabled |= breakpoint.enabled;
+        first.condition = first.condition.take().or(breakpoint.condition);
+        first.hit_condition = first.hit_condition.take().or(breakpoint.hit_condition);
+        first.log_message = first.log_message.take().or(breakpoint.log_message);
+    }
+    *breakpoints = merged;
+    moved
+}
+
+impl PersistedBreakpoints {
+    fn new(
+        breakpoints: &HashMap<PathBuf, Vec<Breakpoint>>,
+        function_breakpoints: &[FunctionBreakpoint],
+    ) -> Self {
+        let source = breakpoints
+            .iter()
+            .filter(|(_, breakpoints)| !breakpoints.is_empty())
+            .map(|(path, breakpoints)| {
+                let breakpoints = breakpoints
+                    .iter()
+                    .map(|breakpoint| PersistedSourceBreakpoint {
+                        line: breakpoint.line,
+                        enabled: breakpoint.enabled,
+                        condition: breakpoint.condition.clone(),
+                        hit_condition: breakpoint.hit_condition.clone(),
+                        log_message: breakpoint.log_message.clone(),
+                    })
+                    .collect();
+                (path.clone(), breakpoints)
+            })
+            .collect();
+        let function = function_breakpoints
+            .iter()
+            .map(|breakpoint| PersistedFunctionBreakpoint {
+                name: breakpoint.name.clone(),
+                enabled: breakpoint.enabled,
+                condition: breakpoint.condition.clone(),
+                hit_condition: breakpoint.hit_condition.clone(),
+            })
+            .collect();
+        Self { source, function }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.source.is_empty() && self.function.is_empty()
+    }
+
+    fn restore(self) -> (HashMap<PathBuf, Vec<Breakpoint>>, Vec<FunctionBreakpoint>) {
+        let breakpoints = self
+            .source
+            .into_iter()
+            .map(|(path, breakpoints)| {
+                let breakpoints = breakpoints
+                    .into_iter()
+                    .map(|breakpoint| Breakpoint {
+                        line: breakpoint.line,
+                        enabled: breakpoint.enabled,
+                        condition: breakpoint.condition,
+                        hit_condition: breakpoint.hit_condition,
+                        log_message: breakpoint.log_message,
+                        ..Default::default()
+                    })
+                    .collect();
+                (path, breakpoints)
+            })
+            .collect();
+        let function_breakpoints = self
+            .function
+            .into_iter()
+            .map(|breakpoint| FunctionBreakpoint {
+                enabled: breakpoint.enabled,
+                condition: breakpoint.condition,
+                hit_condition: breakpoint.hit_condition,
+                ..FunctionBreakpoint::new(breakpoint.name)
+            })
+            .collect();
+        (breakpoints, function_breakpoints)
+    }
+}
+
+impl Editor {
+    /// Restores the breakpoints of the workspace saved by `save_breakpoints`.
+    pub fn load_breakpoints(&mut self) -> anyhow::Result<()> {
+        let path = helix_loader::workspace_breakpoints_file();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let persisted: PersistedBreakpoints = serde_json::from_str(&contents)?;
+        let (breakpoints, function_breakpoints) = persisted.restore();
+        self.breakpoints.extend(breakpoints);
+        self.function_breakpoints = function_breakpoints;
+        Ok(())
+    }
+
+    /// Saves the source and function breakpoints of the workspace, they are restored by
+    /// `load_breakpoints` on startup.
+    pub fn save_breakpoints(&self) -> anyhow::Result<()> {
+        let persisted = PersistedBreakpoints::new(&self.breakpoints, &self.function_breakpoints);
+        let path = helix_loader::workspace_breakpoints_file();
+        if persisted.is_empty() {
+            // Don't leave files behind for workspaces without breakpoints.
+            return match std::fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            };
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&persisted)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use helix_core::Transaction;
+    use std::path::Path;
+
+    #[test]
+    fn test_inline_values_from_syntax() {
+        let loader = helix_core::config::default_lang_loader();
+        let source = Rope::from_str(
+            "fn main() {\n    let count = point.x + offset;\n    println!(\"{count}\");\n}\n",
+        );
+        let language = loader.language_for_name("rust").unwrap();
+        let syntax = Syntax::new(source.slice(..), language, &loader).unwrap();
+        let variables: HashMap<String, String> = [("count", "3"), ("point", "{...}"), ("x", "1")]
+            .into_iter()
+            .chain([("offset", "2"), ("main", "fn")])
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        // `main` is a function and `x` a field, neither is shown
+        assert_eq!(
+            inline_values_from_syntax(&syntax, source.slice(..), &loader, 0..=1, &variables),
+            vec![
+                (1, "count = 3".to_string()),
+                (1, "point = {...}".to_string()),
+                (1, "offset = 2".to_string()),
+            ]
+        );
+        assert_eq!(
+            inline_values_from_syntax(&syntax, source.slice(..), &loader, 2..=2, &variables),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn test_move_breakpoints() {
+        let old_text = Rope::from("a\nb\nc\nd\n");
+        let mut breakpoints = vec![
+            Breakpoint {
+                line: 1,
+                condition: Some("x > 1".to_string()),
+                ..Default::default()
+            },
+            Breakpoint {
+                line: 2,
+                enabled: false,
+                hit_condition: Some("3".to_string()),
+                log_message: Some("{x}".to_string()),
+                ..Default::default()
+            },
+            Breakpoint {
+                line: 3,
+                ..Default::default()
+            },
+        ];
+
+        // inserting a line above moves every breakpoint down
+        let transaction = Transaction::change(&old_text, [(0, 0, Some("z\n".into()))].into_iter());
+        let mut text = old_text.clone();
+        transaction.apply(&mut text);
+        let mut moved = breakpoints.clone();
+        assert!(move_breakpoints(
+            &mut moved,
+            &old_text,
+            transaction.changes(),
+            &text
+        ));
+        let lines: Vec<_> = moved.iter().map(|breakpoint| breakpoint.line).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+
+        // editing within a line doesn't move anything
+        let transaction = Transaction::change(&old_text, [(2, 3, Some("bb".into()))].into_iter());
+        let mut text = old_text.clone();
+        transaction.apply(&mut text);
+        let mut moved = breakpoints.clone();
+        assert!(!move_breakpoints(
+            &mut moved,
+            &old_text,
+            transaction.changes(),
+            &text
+        ));
+
+        // deleting `b\n` moves its breakpoint onto the line of `c` which has one already, they
+        // are merged
+        let transaction = Transaction::change(&old_text, [(2, 4, None)].into_iter());
+        let mut text = old_text.clone();
+        transaction.apply(&mut text);
+        assert!(move_breakpoints(
+            &mut breakpoints,
+            &old_text,
+            transaction.changes(),
+            &text
+        ));
+        assert_eq!(breakpoints.len(), 2);
+        assert_eq!(breakpoints[0].line, 1);
+        assert!(breakpoints[0].enabled);
+        assert_eq!(breakpoints[0].condition.as_deref(), Some("x > 1"));
+        assert_eq!(breakpoints[0].hit_condition.as_deref(), Some("3"));
+        assert_eq!(breakpoints[0].log_message.as_deref(), Some("{x}"));
+        assert_eq!(breakpoints[1].line, 2);
+    }
+
+    #[test]
+    fn test_persisted_breakpoints_round_trip() {
+        let breakpoints = HashMap::from([
+            (
+                PathBuf::from("/src/main.rs"),
+                vec![
+                    Breakpoint {
+                        line: 4,
+                        condition: Some("x > 1".to_string()),
+                        log_message: Some("{x}".to_string()),
+                        // the state of a session isn't stored
+                        id: Some(1),
+                        verified: true,
+                        ..Default::default()
+                    },
+                    Breakpoint {
+                        line: 9,
+                        enabled: false,
+                        hit_condition: Some("3".to_string()),
+                        ..Default::default()
+                    },
+                ],
+            ),
+            (PathBuf::from("/src/lib.rs"), Vec::new()),
+        ]);
+        let function_breakpoints = vec![FunctionBreakpoint {
+            enabled: false,
+            condition: Some("n == 0".to_string()),
+            ..FunctionBreakpoint::new("main".to_string())
+        }];
+
+        let persisted = PersistedBreakpoints::new(&breakpoints, &function_breakpoints);
+        let json = serde_json::to_string(&persisted).unwrap();
+        let persisted: PersistedBreakpoints = serde_json::from_str(&json).unwrap();
+        let (restored, restored_functions) = persisted.restore();
+
+        // files without breakpoints are left out
+        assert_eq!(restored.len(), 1);
+        let restored = &restored[Path::new("/src/main.rs")];
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].line, 4);
+        assert!(restored[0].enabled);
+        assert_eq!(restored[0].condition.as_deref(), Some("x > 1"));
+        assert_eq!(restored[0].log_message.as_deref(), Some("{x}"));
+        assert_eq!(restored[0].id, None);
+        assert!(!restored[0].verified);
+        assert_eq!(restored[1].line, 9);
+        assert!(!restored[1].enabled);
+        assert_eq!(restored[1].hit_condition.as_deref(), Some("3"));
+
+        assert_eq!(restored_functions.len(), 1);
+        assert_eq!(restored_functions[0].name, "main");
+        assert!(!restored_functions[0].enabled);
+        assert_eq!(restored_functions[0].condition.as_deref(), Some("n == 0"));
+        assert_eq!(restored_functions[0].hit_condition, None);
+
+        // `enabled` defaults to true for hand written files
+        let persisted: PersistedBreakpoints =
+            serde_json::from_str(r#"{"source": {"/src/main.rs": [{"line": 2}]}}"#).unwrap();
+        let (restored, _) = persisted.restore();
+        assert!(restored[Path::new("/src/main.rs")][0].enabled);
+    }
+
+    #[test]
+    fn test_format_memory() {
+        let bytes: Vec<u8> = b"Hello, helix!\0\x01\x02abc".to_vec();
+        assert_eq!(
+            format_memory("0x1000", &bytes, 2),
+            "0x0000000000001000  48 65 6c 6c 6f 2c 20 68  65 6c 69 78 21 00 01 02  |Hello, helix!...|\n\
+             0x0000000000001010  61 62 63 ?? ??                                    |abc..|\n"
+        );
+        assert_eq!(
+            format_memory("stack", b"a", 0),
+            "stack+0x0000  61                                                |a|\n"
+        );
+    }
+}
@@ -6,7 +6,7 @@
 use crate::events::{
     DiagnosticsDidChange, DocumentDidChange, DocumentDidClose, LanguageServerInitialized,
 };
-use crate::{DocumentId, Editor};
+use crate::{DocumentId, Editor, ViewId};
 use helix_core::diagnostic::DiagnosticProvider;
 use helix_core::Uri;
 use helix_event::register_hook;
@@ -17,6 +17,11 @@
 
 pub struct DocumentColorsEvent(pub DocumentId);
 
+pub struct DocumentHighlightEvent {
+    pub doc: DocumentId,
+    pub view: ViewId,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SignatureHelpInvoked {
     Automatic,
@@ -1,6 +1,6 @@
 use crate::{
     align_view,
-    annotations::diagnostics::InlineDiagnostics,
+    annotations::{diagnostics::InlineDiagnostics, diff_split::DiffFiller},
     document::{DocumentColorSwatches, DocumentInlayHints},
     editor::{GutterConfig, GutterType},
     graphics::Rect,
@@ -491,6 +491,9 @@ pub fn text_annotations<'a>(
             }
         }
 
+        let inline_value_style = theme.and_then(|t| t.find_highlight("ui.virtual.inline-value"));
+        text_annotations.add_inline_annotations(&doc.inline_values, inline_value_style);
+
         let width = self.inner_width(doc);
         let enable_cursor_line = self
             .diagnostics_handler
@@ -510,6 +513,11 @@ pub fn text_annotations<'a>(
             ));
         }
 
+        // added last so that the other virtual lines are aligned as well
+        if let Some(lines) = doc.diff_split_lines.get(&self.id) {
+            text_annotations.add_line_annotation(DiffFiller::new(doc, lines));
+        }
+
         text_annotations
     }
 